@@ -1,15 +1,16 @@
 #![cfg(test)]
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, CosmosMsg, DepsMut, Empty, Response, WasmMsg,
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, DepsMut, Empty, Response, WasmMsg,
 };
 
 use bs721::{
-    Approval, ApprovalResponse, Bs721Query, Bs721ReceiveMsg, CollectionInfo, ContractInfoResponse, Expiration, NftInfoResponse, OperatorsResponse, OwnerOfResponse, RoyaltyInfoResponse
+    Approval, ApprovalResponse, Bs721Query, Bs721ReceiveMsg, CollectionInfo, ContractInfoResponse, Expiration, Metadata, NftInfoResponse, OperatorsResponse, MetadataError, OwnerOfResponse, RoyaltyInfoResponse, UpdateCollectionInfoMsg
 };
 
+use crate::msg::{NftInfoBatchItem, NftInfoBatchResponse, TokenMetadataResponse, MAX_BATCH_SIZE};
 use crate::{
-    Bs721Contract, ContractError, ExecuteMsg, Extension, InstantiateMsg, MintMsg, QueryMsg,
+    entry, Bs721Contract, ContractError, ExecuteMsg, Extension, InstantiateMsg, MintMsg, QueryMsg,
 };
 
 const MINTER: &str = "merlin";
@@ -80,6 +81,7 @@ fn minting() {
         token_id: token_id.clone(),
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
+        content_hash: None,
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         extension: None,
@@ -113,6 +115,7 @@ fn minting() {
         info,
         NftInfoResponse::<Extension> {
             token_uri: Some(token_uri),
+            content_hash: None,
             seller_fee_bps: Option::from(100u16),
             payment_addr: Option::from(Addr::unchecked("addr")),
             extension: None,
@@ -138,6 +141,7 @@ fn minting() {
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         token_uri: None,
+        content_hash: None,
         extension: None,
     });
 
@@ -165,6 +169,7 @@ fn burning() {
         token_id: token_id.clone(),
         owner: MINTER.to_string(),
         token_uri: Some(token_uri),
+        content_hash: None,
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         extension: None,
@@ -217,6 +222,7 @@ fn transferring_nft() {
         token_id: token_id.clone(),
         owner: String::from("venus"),
         token_uri: Some(token_uri),
+        content_hash: None,
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         extension: None,
@@ -273,6 +279,7 @@ fn sending_nft() {
         token_id: token_id.clone(),
         owner: String::from("venus"),
         token_uri: Some(token_uri),
+        content_hash: None,
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         extension: None,
@@ -341,6 +348,7 @@ fn approving_revoking() {
         token_id: token_id.clone(),
         owner: String::from("demeter"),
         token_uri: Some(token_uri),
+        content_hash: None,
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         extension: None,
@@ -426,7 +434,7 @@ fn approving_revoking() {
         include_expired: None,
     };
     let res: OwnerOfResponse = from_json(
-        &contract
+        contract
             .query(deps.as_ref(), mock_env(), query_msg.clone())
             .unwrap(),
     )
@@ -460,7 +468,7 @@ fn approving_revoking() {
 
     // Approvals are now removed / cleared
     let res: OwnerOfResponse = from_json(
-        &contract
+        contract
             .query(deps.as_ref(), mock_env(), query_msg)
             .unwrap(),
     )
@@ -490,6 +498,7 @@ fn approving_all_revoking_all() {
         token_id: token_id1.clone(),
         owner: String::from("demeter"),
         token_uri: Some(token_uri1),
+        content_hash: None,
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         extension: None,
@@ -504,6 +513,7 @@ fn approving_all_revoking_all() {
         token_id: token_id2.clone(),
         owner: String::from("demeter"),
         token_uri: Some(token_uri2),
+        content_hash: None,
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         extension: None,
@@ -712,6 +722,7 @@ fn query_tokens_by_owner() {
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         token_uri: None,
+        content_hash: None,
         extension: None,
     });
     contract
@@ -724,6 +735,7 @@ fn query_tokens_by_owner() {
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         token_uri: None,
+        content_hash: None,
         extension: None,
     });
     contract
@@ -736,6 +748,7 @@ fn query_tokens_by_owner() {
         seller_fee_bps: Option::from(100u16),
         payment_addr: Option::from("addr".to_string()),
         token_uri: None,
+        content_hash: None,
         extension: None,
     });
     contract
@@ -775,3 +788,470 @@ fn query_tokens_by_owner() {
         .unwrap();
     assert_eq!(&by_demeter[1..], &tokens.tokens[..]);
 }
+
+#[test]
+fn query_token_metadata_without_extension() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: "1".to_string(),
+        owner: "demeter".to_string(),
+        seller_fee_bps: None,
+        payment_addr: None,
+        token_uri: None,
+        content_hash: None,
+        extension: None,
+    });
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap();
+
+    // name and collection fields are used as fallback
+    let res: TokenMetadataResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TokenMetadata {
+                    token_id: "1".to_string(),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    let expected_json = r#"{"name":"Magic Power #1","description":"","image":"https://www.beautiful.network","external_url":"https://www.beautiful.network","attributes":[]}"#;
+    assert_eq!(res.json, expected_json);
+    assert_eq!(
+        res.data_uri,
+        format!(
+            "data:application/json;base64,{}",
+            Binary::from(expected_json.as_bytes()).to_base64()
+        )
+    );
+
+    contract
+        .token_metadata(deps.as_ref(), "unknown".to_string())
+        .unwrap_err();
+}
+
+#[test]
+fn query_batch_token_info() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    for (token_id, owner) in [("1", "demeter"), ("2", "ceres")] {
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            seller_fee_bps: None,
+            payment_addr: None,
+            token_uri: Some(format!("ipfs://{}", token_id)),
+            content_hash: None,
+            extension: None,
+        });
+        contract
+            .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+            .unwrap();
+    }
+
+    let token_ids = vec!["1".to_string(), "missing".to_string(), "2".to_string()];
+
+    let res: NftInfoBatchResponse<Extension> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::NftInfoBatch {
+                    token_ids: token_ids.clone(),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.tokens,
+        vec![
+            NftInfoBatchItem {
+                token_id: "1".to_string(),
+                info: Some(NftInfoResponse {
+                    token_uri: Some("ipfs://1".to_string()),
+                    content_hash: None,
+                    seller_fee_bps: None,
+                    payment_addr: None,
+                    extension: None,
+                }),
+            },
+            NftInfoBatchItem {
+                token_id: "missing".to_string(),
+                info: None,
+            },
+            NftInfoBatchItem {
+                token_id: "2".to_string(),
+                info: Some(NftInfoResponse {
+                    token_uri: Some("ipfs://2".to_string()),
+                    content_hash: None,
+                    seller_fee_bps: None,
+                    payment_addr: None,
+                    extension: None,
+                }),
+            },
+        ]
+    );
+
+    let res = contract
+        .owner_of_batch(deps.as_ref(), mock_env(), token_ids, false)
+        .unwrap();
+    let owners: Vec<_> = res
+        .tokens
+        .into_iter()
+        .map(|item| item.owner.map(|owner| owner.owner))
+        .collect();
+    assert_eq!(
+        owners,
+        vec![Some("demeter".to_string()), None, Some("ceres".to_string())]
+    );
+
+    // input size is bounded
+    let token_ids: Vec<String> = (0..=MAX_BATCH_SIZE).map(|id| id.to_string()).collect();
+    contract
+        .nft_info_batch(deps.as_ref(), token_ids.clone())
+        .unwrap_err();
+    contract
+        .owner_of_batch(deps.as_ref(), mock_env(), token_ids, false)
+        .unwrap_err();
+}
+
+#[test]
+fn minting_with_content_hash() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string();
+    let mint_msg = |token_id: &str, content_hash: &str| {
+        ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: String::from("medusa"),
+            token_uri: Some(format!("ipfs://{}", cid)),
+            content_hash: Some(content_hash.to_string()),
+            seller_fee_bps: None,
+            payment_addr: None,
+            extension: None,
+        })
+    };
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            mint_msg("1", "Qm-not-a-cid"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Metadata(MetadataError::InvalidContentHash {
+            field: "content_hash".to_string()
+        })
+    );
+
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            mint_msg("1", &cid),
+        )
+        .unwrap();
+    let info = contract.nft_info(deps.as_ref(), "1".to_string()).unwrap();
+    assert_eq!(info.content_hash, Some(cid));
+}
+
+#[test]
+fn base_minting_validates_metadata() {
+    let mut deps = mock_dependencies();
+    setup_contract(deps.as_mut());
+
+    let mint_msg = |image: &str| {
+        ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: "1".to_string(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            content_hash: None,
+            seller_fee_bps: None,
+            payment_addr: None,
+            extension: Some(Metadata {
+                image: Some(image.to_string()),
+                ..Default::default()
+            }),
+        })
+    };
+
+    let err = entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MINTER, &[]),
+        mint_msg("http://cover"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Metadata(MetadataError::UnsupportedUrlScheme {
+            field: "image".to_string(),
+            scheme: "http".to_string()
+        })
+    );
+
+    entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MINTER, &[]),
+        mint_msg("ipfs://cover"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn update_collection_image_hash() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let update_msg = |image_hash: Option<Option<String>>| ExecuteMsg::UpdateCollectionInfo {
+        new_collection_info: UpdateCollectionInfoMsg {
+            description: None,
+            image: None,
+            image_hash,
+            external_link: None,
+            explicit_content: None,
+            royalty_info: None,
+            creator: None,
+        },
+    };
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            update_msg(Some(Some("invalid".to_string()))),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Metadata(MetadataError::InvalidContentHash {
+            field: "image_hash".to_string()
+        })
+    );
+
+    let digest = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            update_msg(Some(Some(digest.clone()))),
+        )
+        .unwrap();
+    let info = contract.query_collection_info(deps.as_ref()).unwrap();
+    assert_eq!(info.image_hash, Some(digest));
+
+    // unset fields are left untouched while the hash can be removed
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            update_msg(None),
+        )
+        .unwrap();
+    assert!(contract
+        .query_collection_info(deps.as_ref())
+        .unwrap()
+        .image_hash
+        .is_some());
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            update_msg(Some(None)),
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_collection_info(deps.as_ref())
+            .unwrap()
+            .image_hash,
+        None
+    );
+}
+
+#[test]
+fn minter_updates_token_uri() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: "1".to_string(),
+        owner: String::from("medusa"),
+        token_uri: Some("ipfs://placeholder".to_string()),
+        content_hash: Some("a".repeat(64)),
+        seller_fee_bps: None,
+        payment_addr: None,
+        extension: None,
+    });
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap();
+
+    let update_msg = ExecuteMsg::UpdateTokenUri {
+        token_id: "1".to_string(),
+        token_uri: Some("ipfs://revealed/1.json".to_string()),
+        content_hash: None,
+    };
+
+    // only the minter can update the uri, not even the owner
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("medusa", &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), update_msg)
+        .unwrap();
+    let info = contract.nft_info(deps.as_ref(), "1".to_string()).unwrap();
+    assert_eq!(info.token_uri, Some("ipfs://revealed/1.json".to_string()));
+    assert_eq!(
+        contract.tokens.load(&deps.storage, "1").unwrap().content_hash,
+        None,
+        "expected the hash of the previous content cleared"
+    );
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            ExecuteMsg::UpdateTokenUri {
+                token_id: "1".to_string(),
+                token_uri: Some("ipfs://final/1.json".to_string()),
+                content_hash: Some("ipfs://final/1.json".to_string()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Metadata(MetadataError::InvalidContentHash {
+            field: "content_hash".to_string()
+        })
+    );
+
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            ExecuteMsg::UpdateTokenUri {
+                token_id: "1".to_string(),
+                token_uri: Some("ipfs://final/1.json".to_string()),
+                content_hash: Some("b".repeat(64)),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        contract.tokens.load(&deps.storage, "1").unwrap().content_hash,
+        Some("b".repeat(64))
+    );
+
+    // the token must exist
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            ExecuteMsg::UpdateTokenUri {
+                token_id: "2".to_string(),
+                token_uri: None,
+                content_hash: None,
+            },
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn minter_updates_start_trading_time_and_renounces() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let start_trading_time = mock_env().block.time.plus_seconds(60);
+    let update_msg = ExecuteMsg::UpdateStartTradingTime {
+        start_trading_time: Some(start_trading_time),
+    };
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            update_msg.clone(),
+        )
+        .unwrap();
+    let info = contract.query_collection_info(deps.as_ref()).unwrap();
+    assert_eq!(info.start_trading_time, Some(start_trading_time));
+
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            ExecuteMsg::RenounceMinter {},
+        )
+        .unwrap();
+
+    // the former minter can no longer mint nor update the collection
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: "1".to_string(),
+        owner: String::from("medusa"),
+        token_uri: None,
+        content_hash: None,
+        seller_fee_bps: None,
+        payment_addr: None,
+        extension: None,
+    });
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), update_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            ExecuteMsg::SetMinter {
+                new_minter: MINTER.to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
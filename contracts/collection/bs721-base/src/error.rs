@@ -1,3 +1,4 @@
+use bs721::MetadataError;
 use cosmwasm_std::StdError;
 use cw_utils::PaymentError;
 use thiserror::Error;
@@ -14,6 +15,9 @@ pub enum ContractError {
     #[error("{0}")]
     Parse(#[from] ParseError),
 
+    #[error("{0}")]
+    Metadata(#[from] MetadataError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
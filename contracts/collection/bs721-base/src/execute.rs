@@ -2,12 +2,13 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use cosmwasm_std::{
-    Binary, CustomMsg, Decimal, Deps, DepsMut, Env, Event, MessageInfo, Response,
+    Addr, Binary, CustomMsg, Decimal, Deps, DepsMut, Env, Event, MessageInfo, Response, Storage,
+    Timestamp,
 };
 
 use bs721::{
     Bs721Execute, Bs721ReceiveMsg, CollectionInfo, Expiration, RoyaltyInfo,
-    RoyaltyInfoResponse, UpdateCollectionInfoMsg,
+    RoyaltyInfoResponse, UpdateCollectionInfoMsg, validate_content_hash,
 };
 use cw721::ContractInfoResponse as CW721ContractInfoResponse;
 use cw_utils::maybe_addr;
@@ -56,6 +57,9 @@ where
             return Err(ContractError::DescriptionTooLong {});
         }
         let image = Url::parse(&msg.collection_info.image)?;
+        if let Some(ref image_hash) = msg.collection_info.image_hash {
+            validate_content_hash("image_hash", image_hash)?;
+        }
 
         if let Some(ref external_link) = msg.collection_info.external_link {
             Url::parse(external_link)?;
@@ -76,6 +80,7 @@ where
             creator: msg.collection_info.creator,
             description: msg.collection_info.description,
             image: msg.collection_info.image,
+            image_hash: msg.collection_info.image_hash,
             external_link: msg.collection_info.external_link,
             explicit_content: msg.collection_info.explicit_content,
             start_trading_time: msg.collection_info.start_trading_time,
@@ -128,6 +133,15 @@ where
                 msg,
             } => self.send_nft(deps, env, info, contract, token_id, msg),
             ExecuteMsg::Burn { token_id } => self.burn(deps, env, info, token_id),
+            ExecuteMsg::UpdateTokenUri {
+                token_id,
+                token_uri,
+                content_hash,
+            } => self.update_token_uri(deps, info, token_id, token_uri, content_hash),
+            ExecuteMsg::UpdateStartTradingTime { start_trading_time } => {
+                self.update_start_trading_time(deps, info, start_trading_time)
+            }
+            ExecuteMsg::RenounceMinter {} => self.renounce_minter(deps, info),
             ExecuteMsg::Extension { msg: _ } => Ok(Response::default()),
             ExecuteMsg::UpdateCollectionInfo {
                 new_collection_info,
@@ -179,6 +193,13 @@ where
             .unwrap_or_else(|| collection.image.to_string());
         Url::parse(&collection.image)?;
 
+        if let Some(image_hash) = new_collection_info.image_hash {
+            collection.image_hash = image_hash;
+        }
+        if let Some(ref image_hash) = collection.image_hash {
+            validate_content_hash("image_hash", image_hash)?;
+        }
+
         collection.external_link = new_collection_info
             .external_link
             .unwrap_or_else(|| collection.external_link.as_ref().map(|s| s.to_string()));
@@ -241,10 +262,7 @@ where
         info: MessageInfo,
         new_minter: String,
     ) -> Result<Response<C>, ContractError> {
-        let minter = self.minter.load(deps.storage)?;
-        if info.sender != minter {
-            return Err(ContractError::Unauthorized {});
-        }
+        self.only_minter(deps.storage, &info.sender)?;
 
         let new_minter_addr = deps.api.addr_validate(&new_minter)?;
         self.minter.save(deps.storage, &new_minter_addr)?;
@@ -261,11 +279,7 @@ where
         info: MessageInfo,
         msg: MintMsg<T>,
     ) -> Result<Response<C>, ContractError> {
-        let minter = self.minter.load(deps.storage)?;
-
-        if info.sender != minter {
-            return Err(ContractError::Unauthorized {});
-        }
+        self.only_minter(deps.storage, &info.sender)?;
 
         // seller fee and payment address are optional, if one is set, both must be set
         if (msg.seller_fee_bps.is_some() && msg.payment_addr.is_none())
@@ -281,11 +295,16 @@ where
             }
         }
 
+        if let Some(ref content_hash) = msg.content_hash {
+            validate_content_hash("content_hash", content_hash)?;
+        }
+
         // create the token
         let token = TokenInfo {
             owner: deps.api.addr_validate(&msg.owner)?,
             approvals: vec![],
             token_uri: msg.token_uri,
+            content_hash: msg.content_hash,
             extension: msg.extension,
             seller_fee_bps: msg.seller_fee_bps,
             payment_addr: maybe_addr(deps.api, msg.payment_addr)?,
@@ -304,6 +323,70 @@ where
             .add_attribute("owner", msg.owner)
             .add_attribute("token_id", msg.token_id))
     }
+
+    pub fn update_token_uri(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+        token_uri: Option<String>,
+        content_hash: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_minter(deps.storage, &info.sender)?;
+        if let Some(content_hash) = &content_hash {
+            validate_content_hash("content_hash", content_hash)?;
+        }
+
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        token.token_uri = token_uri;
+        token.content_hash = content_hash;
+        self.tokens.save(deps.storage, &token_id, &token)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_token_uri")
+            .add_attribute("minter", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn update_start_trading_time(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        start_trading_time: Option<Timestamp>,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_minter(deps.storage, &info.sender)?;
+
+        let mut collection = self.collection_info.load(deps.storage)?;
+        collection.start_trading_time = start_trading_time;
+        self.collection_info.save(deps.storage, &collection)?;
+
+        let time = start_trading_time.map_or("none".to_string(), |time| time.to_string());
+        Ok(Response::new()
+            .add_attribute("action", "update_start_trading_time")
+            .add_attribute("start_trading_time", time))
+    }
+
+    pub fn renounce_minter(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_minter(deps.storage, &info.sender)?;
+
+        self.minter.remove(deps.storage);
+
+        Ok(Response::new()
+            .add_attribute("action", "renounce_minter")
+            .add_attribute("minter", info.sender))
+    }
+
+    /// Returns an error if `sender` is not the minter, or if the minter has been renounced.
+    fn only_minter(&self, storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+        if self.minter.may_load(storage)?.as_ref() != Some(sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
 }
 
 impl<'a, T, C, E, Q> Bs721Execute<T, C> for Bs721Contract<'a, T, C, E, Q>
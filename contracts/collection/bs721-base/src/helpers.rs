@@ -11,6 +11,7 @@ use cosmwasm_std::{
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::msg::{NftInfoBatchResponse, OwnerOfBatchResponse, TokenMetadataResponse};
 use crate::{ExecuteMsg, QueryMsg};
 
 #[cw_serde]
@@ -150,6 +151,42 @@ impl<Q: CustomMsg, E: CustomMsg> Bs721Contract<Q, E> {
         self.query(querier, req)
     }
 
+    /// With metadata extension, tokens not found are returned without info
+    pub fn nft_info_batch<U: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        token_ids: Vec<String>,
+    ) -> StdResult<NftInfoBatchResponse<U>> {
+        let req = QueryMsg::NftInfoBatch { token_ids };
+        self.query(querier, req)
+    }
+
+    /// Tokens not found are returned without owner
+    pub fn owner_of_batch(
+        &self,
+        querier: &QuerierWrapper,
+        token_ids: Vec<String>,
+        include_expired: bool,
+    ) -> StdResult<OwnerOfBatchResponse> {
+        let req = QueryMsg::OwnerOfBatch {
+            token_ids,
+            include_expired: Some(include_expired),
+        };
+        self.query(querier, req)
+    }
+
+    /// With metadata extension, returns the metadata JSON document of the token
+    pub fn token_metadata<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+    ) -> StdResult<TokenMetadataResponse> {
+        let req = QueryMsg::TokenMetadata {
+            token_id: token_id.into(),
+        };
+        self.query(querier, req)
+    }
+
     /// With enumerable extension
     pub fn tokens<T: Into<String>>(
         &self,
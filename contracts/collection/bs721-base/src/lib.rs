@@ -2,6 +2,7 @@ mod contract_tests;
 mod error;
 mod execute;
 pub mod helpers;
+pub mod metadata_onchain;
 pub mod msg;
 mod query;
 pub mod state;
@@ -9,10 +10,11 @@ pub mod state;
 pub use crate::error::ContractError;
 pub use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg, MinterResponse, QueryMsg};
 pub use crate::state::Bs721Contract;
+use bs721::Metadata;
 use cosmwasm_std::Empty;
 
-// This is a simple type to let us handle empty extensions
-pub type Extension = Option<Empty>;
+// Token metadata stored on chain, so that minters can set it and TokenMetadata can export it
+pub type Extension = Option<Metadata>;
 
 // Version info for migration
 pub const CONTRACT_NAME: &str = "crates.io:bs721-base";
@@ -50,6 +52,8 @@ pub mod entry {
         info: MessageInfo,
         msg: ExecuteMsg<Extension, Empty>,
     ) -> Result<Response, ContractError> {
+        metadata_onchain::validate_mint(&msg)?;
+
         Bs721Contract::<Extension, Empty, Empty, Empty>::default().execute(deps, env, info, msg)
     }
 
@@ -0,0 +1,230 @@
+//! Preset of the bs721-base contract storing [`Metadata`] on chain as the token extension.
+//!
+//! The entry points are exported as plain functions so that a contract crate can wrap them with
+//! `#[entry_point]`. The metadata of every minted token is validated before being stored.
+
+use bs721::Metadata;
+use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+
+use crate::{Bs721Contract, ContractError, ExecuteMsg, InstantiateMsg, MintMsg, QueryMsg};
+
+// Version info for migration
+pub const CONTRACT_NAME: &str = "crates.io:bs721-metadata-onchain";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub type Extension = Option<Metadata>;
+
+pub type Bs721MetadataContract<'a> = Bs721Contract<'a, Extension, Empty, Empty, Empty>;
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let res = Bs721MetadataContract::default().instantiate(deps, env, info, msg)?;
+
+    Ok(res
+        .add_attribute("contract_name", CONTRACT_NAME)
+        .add_attribute("contract_version", CONTRACT_VERSION))
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg<Extension, Empty>,
+) -> Result<Response, ContractError> {
+    validate_mint(&msg)?;
+
+    Bs721MetadataContract::default().execute(deps, env, info, msg)
+}
+
+/// Validates the metadata of the token minted by `msg`, if any.
+pub(crate) fn validate_mint(msg: &ExecuteMsg<Extension, Empty>) -> Result<(), ContractError> {
+    if let ExecuteMsg::Mint(MintMsg {
+        extension: Some(metadata),
+        ..
+    }) = msg
+    {
+        metadata.validate()?;
+    }
+
+    Ok(())
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg<Empty>) -> StdResult<Binary> {
+    Bs721MetadataContract::default().query(deps, env, msg)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit tests
+// -------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bs721::{
+        AudioMetadata, Bs721Query, CollectionInfo, DisplayType, MediaType, MetadataError,
+        NftInfoResponse, RoyaltyInfoResponse, Trait,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    const MINTER: &str = "minter";
+
+    fn mint_msg(token_id: &str, extension: Extension) -> ExecuteMsg<Extension, Empty> {
+        ExecuteMsg::Mint(MintMsg {
+            token_id: token_id.to_string(),
+            owner: "owner".to_string(),
+            token_uri: None,
+            content_hash: None,
+            seller_fee_bps: None,
+            payment_addr: None,
+            extension,
+        })
+    }
+
+    fn setup(deps: DepsMut) {
+        instantiate(
+            deps,
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {
+                name: "Album".to_string(),
+                symbol: "ALB".to_string(),
+                uri: None,
+                minter: MINTER.to_string(),
+                collection_info: CollectionInfo::<RoyaltyInfoResponse>::default(),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn mint_validates_metadata() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        let metadata = Metadata {
+            name: Some("Track #1".to_string()),
+            image: Some("ipfs://cover".to_string()),
+            animation_url: Some("ipfs://track".to_string()),
+            media_type: Some(MediaType::Audio),
+            attributes: Some(vec![Trait {
+                display_type: None,
+                trait_type: "Genre".to_string(),
+                value: "Techno".to_string(),
+            }]),
+            ..Default::default()
+        };
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            mint_msg("1", Some(metadata.clone())),
+        )
+        .unwrap();
+
+        let info = Bs721MetadataContract::default()
+            .nft_info(deps.as_ref(), "1".to_string())
+            .unwrap();
+        assert_eq!(
+            info,
+            NftInfoResponse {
+                token_uri: None,
+                content_hash: None,
+                seller_fee_bps: None,
+                payment_addr: None,
+                extension: Some(metadata.clone()),
+            }
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            mint_msg(
+                "2",
+                Some(Metadata {
+                    image: Some("http://cover".to_string()),
+                    ..metadata
+                }),
+            ),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Metadata(MetadataError::UnsupportedUrlScheme {
+                field: "image".to_string(),
+                scheme: "http".to_string()
+            }),
+            "expected to fail since only secure urls are allowed"
+        );
+
+        // tokens without metadata are still allowed
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            mint_msg("3", None),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn token_metadata_exports_json() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        let metadata = Metadata {
+            name: Some("Track #1".to_string()),
+            description: Some("First track".to_string()),
+            image: Some("ipfs://cover".to_string()),
+            animation_url: Some("ipfs://track".to_string()),
+            media_type: Some(MediaType::Audio),
+            attributes: Some(vec![Trait {
+                display_type: Some(DisplayType::Number),
+                trait_type: "Edition".to_string(),
+                value: "1".to_string(),
+            }]),
+            audio: Some(AudioMetadata {
+                artist: Some("Artist".to_string()),
+                duration: Some(180),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            mint_msg("1", Some(metadata)),
+        )
+        .unwrap();
+
+        let res = Bs721MetadataContract::default()
+            .token_metadata(deps.as_ref(), "1".to_string())
+            .unwrap();
+        assert_eq!(
+            res.json,
+            concat!(
+                r#"{"name":"Track #1","description":"First track","image":"ipfs://cover","#,
+                r#""external_url":"https://www.beautiful.network","#,
+                r#""animation_url":"ipfs://track","attributes":["#,
+                r#"{"display_type":"number","trait_type":"Edition","value":"1"},"#,
+                r#"{"trait_type":"Artist","value":"Artist"},"#,
+                r#"{"display_type":"number","trait_type":"Duration","value":"180"}]}"#
+            )
+        );
+        assert_eq!(
+            res.data_uri,
+            format!(
+                "data:application/json;base64,{}",
+                Binary::from(res.json.as_bytes()).to_base64()
+            )
+        );
+    }
+}
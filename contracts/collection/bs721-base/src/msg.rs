@@ -1,4 +1,7 @@
-use bs721::{CollectionInfo, Expiration, RoyaltyInfoResponse, UpdateCollectionInfoMsg};
+use bs721::{
+    CollectionInfo, Expiration, NftInfoResponse, OwnerOfResponse, RoyaltyInfoResponse,
+    UpdateCollectionInfoMsg,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Binary, Timestamp};
 use schemars::JsonSchema;
@@ -71,6 +74,23 @@ pub enum ExecuteMsg<T, E> {
         token_id: String,
     },
 
+    /// Update the URI of a token, can only be called by the contract minter. The content hash of
+    /// the token is replaced, and cleared if not provided, so that it never refers to the content
+    /// of a previous URI.
+    UpdateTokenUri {
+        token_id: String,
+        token_uri: Option<String>,
+        content_hash: Option<String>,
+    },
+
+    /// Update the time trading starts, can only be called by the contract minter
+    UpdateStartTradingTime {
+        start_trading_time: Option<Timestamp>,
+    },
+
+    /// Remove the minter, no more NFTs can be created
+    RenounceMinter {},
+
     /// Update collection info
     UpdateCollectionInfo {
         new_collection_info: UpdateCollectionInfoMsg<RoyaltyInfoResponse>,
@@ -90,6 +110,9 @@ pub struct MintMsg<T> {
     /// Should point to a JSON file that conforms to the ERC721
     /// Metadata JSON Schema
     pub token_uri: Option<String>,
+    /// Sha256 digest or IPFS CIDv1 of the content referenced by the token uri,
+    /// used to prove which file has been minted
+    pub content_hash: Option<String>,
     /// Seller fee basis points, 0-10000
     /// 0 means no fee, 100 means 1%, 10000 means 100%
     /// This is the fee paid by the buyer to the original creator
@@ -100,6 +123,9 @@ pub struct MintMsg<T> {
     pub extension: T,
 }
 
+/// Maximum number of tokens that can be requested by a batch query.
+pub const MAX_BATCH_SIZE: usize = 50;
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg<Q: JsonSchema> {
@@ -154,6 +180,18 @@ pub enum QueryMsg<Q: JsonSchema> {
         /// unset or false will filter out expired approvals, you must set to true to see them
         include_expired: Option<bool>,
     },
+    /// Returns the result of `NftInfo` for each of the given tokens, at most `MAX_BATCH_SIZE`.
+    /// Tokens that do not exist are reported without info instead of failing the query.
+    #[returns(NftInfoBatchResponse<Q>)]
+    NftInfoBatch { token_ids: Vec<String> },
+    /// Returns the result of `OwnerOf` for each of the given tokens, at most `MAX_BATCH_SIZE`.
+    /// Tokens that do not exist are reported without owner instead of failing the query.
+    #[returns(OwnerOfBatchResponse)]
+    OwnerOfBatch {
+        token_ids: Vec<String>,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
 
     /// With Enumerable extension.
     /// Returns all tokens owned by the given address, [] if unset.
@@ -178,6 +216,11 @@ pub enum QueryMsg<Q: JsonSchema> {
     #[returns(CollectionInfoResponse)]
     CollectionInfo {},
 
+    /// Returns the on-chain metadata of a token assembled as an *ERC721 Metadata JSON Schema*
+    /// document, along with the same document encoded as a base64 `data:application/json` uri.
+    #[returns(TokenMetadataResponse)]
+    TokenMetadata { token_id: String },
+
 
     /// Extension query
     #[returns(())]
@@ -195,8 +238,42 @@ pub struct CollectionInfoResponse {
     pub creator: String,
     pub description: String,
     pub image: String,
+    pub image_hash: Option<String>,
     pub external_link: Option<String>,
     pub explicit_content: Option<bool>,
     pub start_trading_time: Option<Timestamp>,
     pub royalty_info: Option<RoyaltyInfoResponse>,
 }
+
+#[cw_serde]
+pub struct TokenMetadataResponse {
+    /// Metadata JSON document of the token.
+    pub json: String,
+    /// Metadata JSON document encoded as `data:application/json;base64,<document>`. Can be used
+    /// in place of the `token_uri` by clients reading metadata only from an uri.
+    pub data_uri: String,
+}
+
+#[cw_serde]
+pub struct NftInfoBatchResponse<T> {
+    pub tokens: Vec<NftInfoBatchItem<T>>,
+}
+
+#[cw_serde]
+pub struct NftInfoBatchItem<T> {
+    pub token_id: String,
+    /// None if the token does not exist.
+    pub info: Option<NftInfoResponse<T>>,
+}
+
+#[cw_serde]
+pub struct OwnerOfBatchResponse {
+    pub tokens: Vec<OwnerOfBatchItem>,
+}
+
+#[cw_serde]
+pub struct OwnerOfBatchItem {
+    pub token_id: String,
+    /// None if the token does not exist.
+    pub owner: Option<OwnerOfResponse>,
+}
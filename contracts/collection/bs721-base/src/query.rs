@@ -2,18 +2,22 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, BlockInfo, CustomMsg, Deps, Env, Order, StdError, StdResult,
+    from_json, to_json_binary, to_json_string, to_json_vec, Addr, Binary, BlockInfo, CustomMsg,
+    Deps, Env, Order, StdError, StdResult,
 };
 
 use bs721::{
     AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, Bs721Query, ContractInfoResponse,
-    Expiration, NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse,
-    RoyaltyInfoResponse, TokensResponse,
+    DisplayType, Expiration, Metadata, NftInfoResponse, NumTokensResponse, OperatorsResponse,
+    OwnerOfResponse, RoyaltyInfoResponse, TokensResponse,
 };
 use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
 
-use crate::msg::{CollectionInfoResponse, MinterResponse, QueryMsg};
+use crate::msg::{
+    CollectionInfoResponse, MinterResponse, NftInfoBatchItem, NftInfoBatchResponse,
+    OwnerOfBatchItem, OwnerOfBatchResponse, QueryMsg, TokenMetadataResponse, MAX_BATCH_SIZE,
+};
 use crate::state::{Approval, Bs721Contract, TokenInfo};
 
 const DEFAULT_LIMIT: u32 = 10;
@@ -39,6 +43,7 @@ where
         let info = self.tokens.load(deps.storage, &token_id)?;
         Ok(NftInfoResponse {
             token_uri: info.token_uri,
+            content_hash: info.content_hash,
             seller_fee_bps: info.seller_fee_bps,
             payment_addr: info.payment_addr,
             extension: info.extension,
@@ -205,6 +210,7 @@ where
             },
             info: NftInfoResponse {
                 token_uri: info.token_uri,
+                content_hash: info.content_hash,
                 seller_fee_bps: info.seller_fee_bps,
                 payment_addr: info.payment_addr,
                 extension: info.extension,
@@ -250,6 +256,18 @@ where
                 token_id,
                 include_expired.unwrap_or(false),
             )?),
+            QueryMsg::NftInfoBatch { token_ids } => {
+                to_json_binary(&self.nft_info_batch(deps, token_ids)?)
+            }
+            QueryMsg::OwnerOfBatch {
+                token_ids,
+                include_expired,
+            } => to_json_binary(&self.owner_of_batch(
+                deps,
+                env,
+                token_ids,
+                include_expired.unwrap_or(false),
+            )?),
             QueryMsg::AllOperators {
                 owner,
                 include_expired,
@@ -293,11 +311,67 @@ where
                 include_expired.unwrap_or(false),
             )?),
             QueryMsg::CollectionInfo {} => to_json_binary(&self.query_collection_info(deps)?),
+            QueryMsg::TokenMetadata { token_id } => {
+                to_json_binary(&self.token_metadata(deps, token_id)?)
+            }
 
             QueryMsg::Extension { msg: _ } => Ok(Binary::default()),
         }
     }
 
+    pub fn nft_info_batch(
+        &self,
+        deps: Deps,
+        token_ids: Vec<String>,
+    ) -> StdResult<NftInfoBatchResponse<T>> {
+        check_batch_size(&token_ids)?;
+
+        let tokens = token_ids
+            .into_iter()
+            .map(|token_id| {
+                let info = self
+                    .tokens
+                    .may_load(deps.storage, &token_id)?
+                    .map(|info| NftInfoResponse {
+                        token_uri: info.token_uri,
+                        content_hash: info.content_hash,
+                        seller_fee_bps: info.seller_fee_bps,
+                        payment_addr: info.payment_addr,
+                        extension: info.extension,
+                    });
+                Ok(NftInfoBatchItem { token_id, info })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(NftInfoBatchResponse { tokens })
+    }
+
+    pub fn owner_of_batch(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_ids: Vec<String>,
+        include_expired: bool,
+    ) -> StdResult<OwnerOfBatchResponse> {
+        check_batch_size(&token_ids)?;
+
+        let tokens = token_ids
+            .into_iter()
+            .map(|token_id| {
+                let owner = self
+                    .tokens
+                    .may_load(deps.storage, &token_id)?
+                    .map(|info| OwnerOfResponse {
+                        owner: info.owner.to_string(),
+                        approvals: humanize_approvals(&env.block, &info, include_expired),
+                    });
+                Ok(OwnerOfBatchItem { token_id, owner })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(OwnerOfBatchResponse { tokens })
+    }
+
     pub fn query_collection_info(&self, deps: Deps) -> StdResult<CollectionInfoResponse> {
         let info = self.collection_info.load(deps.storage)?;
 
@@ -314,12 +388,121 @@ where
             creator: info.creator,
             description: info.description,
             image: info.image,
+            image_hash: info.image_hash,
             external_link: info.external_link,
             explicit_content: info.explicit_content,
             start_trading_time: info.start_trading_time,
             royalty_info: royalty_info_res,
         })
     }
+
+    /// Assembles the metadata JSON document of a token. The extension is read as [`Metadata`], so
+    /// any extension whose fields are a subset of it is exported, while other extensions return
+    /// an error. Missing fields fall back to the collection values, while the name defaults to
+    /// `<collection name> #<token id>`.
+    pub fn token_metadata(&self, deps: Deps, token_id: String) -> StdResult<TokenMetadataResponse> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+        let contract_info = self.contract_info.load(deps.storage)?;
+        let collection_info = self.collection_info.load(deps.storage)?;
+
+        let metadata = from_json::<Option<Metadata>>(to_json_vec(&token.extension)?)
+            .map_err(|_| {
+                StdError::generic_err("token extension is not compatible with the metadata schema")
+            })?
+            .unwrap_or_default();
+
+        let mut attributes: Vec<MetadataJsonAttribute> = metadata
+            .attributes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|attribute| MetadataJsonAttribute {
+                display_type: attribute.display_type,
+                trait_type: attribute.trait_type,
+                value: attribute.value,
+            })
+            .collect();
+        if let Some(audio) = metadata.audio {
+            let audio_attributes = [
+                ("Artist", audio.artist, None),
+                ("Album", audio.album, None),
+                ("Genre", audio.genre, None),
+                (
+                    "Duration",
+                    audio.duration.map(|duration| duration.to_string()),
+                    Some(DisplayType::Number),
+                ),
+                ("ISRC", audio.isrc, None),
+            ];
+            attributes.extend(audio_attributes.into_iter().filter_map(
+                |(trait_type, value, display_type)| {
+                    value.map(|value| MetadataJsonAttribute {
+                        display_type,
+                        trait_type: trait_type.to_string(),
+                        value,
+                    })
+                },
+            ));
+        }
+
+        let json = to_json_string(&MetadataJson {
+            name: metadata
+                .name
+                .unwrap_or_else(|| format!("{} #{}", contract_info.name, token_id)),
+            description: metadata.description.or(Some(collection_info.description)),
+            image: metadata.image.or(Some(collection_info.image)),
+            image_data: metadata.image_data,
+            external_url: metadata.external_url.or(collection_info.external_link),
+            animation_url: metadata.animation_url,
+            youtube_url: metadata.youtube_url,
+            background_color: metadata.background_color,
+            attributes,
+        })?;
+        let data_uri = format!(
+            "data:application/json;base64,{}",
+            Binary::from(json.as_bytes()).to_base64()
+        );
+
+        Ok(TokenMetadataResponse { json, data_uri })
+    }
+}
+
+/// Metadata document following the *ERC721 Metadata JSON Schema*, unset fields are omitted.
+#[derive(Serialize)]
+struct MetadataJson {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image_data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    animation_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    youtube_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<String>,
+    attributes: Vec<MetadataJsonAttribute>,
+}
+
+#[derive(Serialize)]
+struct MetadataJsonAttribute {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_type: Option<DisplayType>,
+    trait_type: String,
+    value: String,
+}
+
+fn check_batch_size(token_ids: &[String]) -> StdResult<()> {
+    if token_ids.len() > MAX_BATCH_SIZE {
+        return Err(StdError::generic_err(format!(
+            "cannot query more than {} tokens at once",
+            MAX_BATCH_SIZE
+        )));
+    }
+    Ok(())
 }
 
 fn parse_approval(item: StdResult<(Addr, Expiration)>) -> StdResult<bs721::Approval> {
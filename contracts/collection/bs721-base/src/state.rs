@@ -132,6 +132,9 @@ pub struct TokenInfo<T> {
     /// Metadata JSON Schema
     pub token_uri: Option<String>,
 
+    /// Sha256 digest or IPFS CIDv1 of the content referenced by the token uri
+    pub content_hash: Option<String>,
+
     /// Seller fee basis points, 0-10000
     /// 0 means no fee, 100 means 1%, 10000 means 100%
     /// This is the fee paid by the buyer to the original creator
@@ -1,24 +1,37 @@
 use std::ops::Add;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MaxPerAddressResponse, PriceResponse, QueryMsg};
-use crate::state::{Config, EditionMetadata, Trait, ADDRESS_TOKENS, CONFIG};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MaxPerAddressResponse, PriceResponse, QueryMsg,
+    ReferralStatsResponse, TopReferrersResponse,
+};
+use crate::state::{
+    referrals, Config, EditionMetadata, ReferralStats, Trait, ADDRESS_TOKENS, CONFIG,
+};
+
+use bs_mint_hooks::{
+    handle_reply,
+    post::{add_postmint_hook, prepare_postmint_hooks, query_postmint_hooks, remove_postmint_hook},
+    pre::{add_premint_hook, prepare_premint_hooks, query_premint_hooks, remove_premint_hook},
+};
 
 use cosmos_sdk_proto::{cosmos::distribution::v1beta1::MsgFundCommunityPool, traits::Message};
 
-use bs721::{Bs721QueryMsg, CollectionInfo, NumTokensResponse, RoyaltyInfoResponse};
+use bs721::{Bs721QueryMsg, CollectionInfo, DisplayType, NumTokensResponse, RoyaltyInfoResponse};
 use bs721_base::{
     ExecuteMsg as Bs721BaseExecuteMsg, InstantiateMsg as Bs721BaseInstantiateMsg, MintMsg,
 };
+use bs_std::fees::{split_fees, validate_fee_split, MAX_BPS};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coin, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
-    DepsMut, Empty, Env, MessageInfo, QuerierWrapper, QueryRequest, Reply, ReplyOn, Response,
+    attr, coin, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut,
+    Empty, Env, MessageInfo, Order, QuerierWrapper, QueryRequest, Reply, ReplyOn, Response,
     StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 
 use cw_utils::{must_pay, nonpayable, parse_reply_instantiate_data};
 
@@ -27,6 +40,9 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// ID used to recognize the instantiate token reply in the reply entry point.
 const INSTANTIATE_TOKEN_REPLY_ID: u64 = 1;
+/// Default and maximum number of items returned by paginated queries.
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -38,6 +54,7 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     msg.validate(env.clone())?;
+    validate_fee_split(deps.api, &msg.fee_split)?;
 
     let start_time = if msg.start_time < env.block.time {
         env.block.time
@@ -48,18 +65,26 @@ pub fn instantiate(
     let payment_address = deps.api.addr_validate(msg.payment_address.as_str())?;
     let bs721_admin = deps.api.addr_validate(msg.bs721_admin.as_str())?;
 
+    let creator = msg
+        .creator
+        .map(|address| deps.api.addr_validate(address.as_str()))
+        .transpose()?
+        .unwrap_or(info.sender);
+
     let config = Config {
-        creator: info.sender,
+        creator,
         symbol: msg.symbol.clone(),
         name: msg.name.clone(),
         uri: msg.uri.clone(),
+        content_hash: msg.content_hash.clone(),
         payment_denom: msg.payment_denom.clone(),
         max_per_address: msg.max_per_address,
         bs721_address: None,
         next_token_id: 1, // first token ID is 1
         seller_fee_bps: msg.seller_fee_bps,
-        referral_fee_bps: msg.referral_fee_bps,
+        fee_split: msg.fee_split,
         protocol_fee_bps: msg.protocol_fee_bps,
+        accrue_referrals: msg.accrue_referrals,
         payment_address,
         start_time,
         max_edition: msg.max_edition,
@@ -94,6 +119,9 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    // mint hooks reply only on error, reverting the mint.
+    handle_reply(reply.id)?;
+
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     let mut res = Response::new();
@@ -148,7 +176,80 @@ pub fn execute(
                 .transpose()?;
             execute_burn(deps, env, info, token_ids, min_out_amount, referral)
         }
+        ExecuteMsg::ClaimReferral {} => execute_claim_referral(deps, info),
+        ExecuteMsg::AddPreMintHook { hook } => {
+            only_creator(deps.storage, &info.sender)?;
+            Ok(add_premint_hook(deps, hook)?)
+        }
+        ExecuteMsg::AddPostMintHook { hook } => {
+            only_creator(deps.storage, &info.sender)?;
+            Ok(add_postmint_hook(deps, hook)?)
+        }
+        ExecuteMsg::RemovePreMintHook { hook } => {
+            only_creator(deps.storage, &info.sender)?;
+            Ok(remove_premint_hook(deps, hook)?)
+        }
+        ExecuteMsg::RemovePostMintHook { hook } => {
+            only_creator(deps.storage, &info.sender)?;
+            Ok(remove_postmint_hook(deps, hook)?)
+        }
+    }
+}
+
+/// Returns an error if `sender` is not the creator of the collection.
+fn only_creator(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let config: Config = CONFIG.load(storage)?;
+    if sender != config.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Adds a referred mint or burn to the stats of the referral. With `accrue` the fee is added to
+/// the amount the referral can claim.
+fn record_referral(
+    storage: &mut dyn Storage,
+    referral: &Addr,
+    mints: u32,
+    fee: Uint128,
+    accrue: bool,
+) -> StdResult<()> {
+    referrals().update(storage, referral, |stats| -> StdResult<_> {
+        let mut stats = stats.unwrap_or_default();
+        stats.mints += mints;
+        stats.earned += fee;
+        if accrue {
+            stats.claimable += fee;
+        }
+        Ok(stats)
+    })?;
+    Ok(())
+}
+
+/// Sends to the sender the referral fees accrued in the contract.
+fn execute_claim_referral(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut stats = referrals()
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if stats.claimable.is_zero() {
+        return Err(ContractError::NothingToClaim {});
     }
+
+    let amount = stats.claimable;
+    stats.claimable = Uint128::zero();
+    referrals().save(deps.storage, &info.sender, &stats)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![coin(amount.u128(), config.payment_denom)],
+        })
+        .add_attribute("action", "claim_referral")
+        .add_attribute("referral", info.sender.to_string())
+        .add_attribute("amount", amount))
 }
 
 // Sum of squares of first n natural numbers
@@ -170,69 +271,63 @@ fn compute_base_price(storage: &dyn Storage, supply: Uint128, amount: Uint128) -
     (sum2 - sum1) * Uint128::new(1_000_000) / Uint128::from(config.ratio)
 }
 
-fn buy_price(storage: &dyn Storage, supply: Uint128, amount: Uint128) -> PriceResponse {
+fn buy_price(storage: &dyn Storage, supply: Uint128, amount: Uint128) -> StdResult<PriceResponse> {
     let config = CONFIG.load(storage).unwrap();
 
-    let base_price = Decimal::from_ratio(compute_base_price(storage, supply, amount), 1u32);
-
-    let royalties_fee = Decimal::from_ratio(config.seller_fee_bps, 10000u32);
-    let royalties_price = base_price * royalties_fee;
-
-    let referral_fee = Decimal::from_ratio(config.referral_fee_bps, 10000u32);
-    let referral_price = royalties_price * referral_fee;
-
-    let protocol_fee = Decimal::from_ratio(config.protocol_fee_bps, 10000u32);
-    let protocol_price = base_price * protocol_fee;
-
-    let total_price =
-        base_price + (royalties_price - referral_price) + referral_price + protocol_price;
-
-    PriceResponse {
-        base_price: base_price.to_uint_floor(),
-        royalties: (royalties_price - referral_price).to_uint_floor(),
-        referral: referral_price.to_uint_floor(),
-        protocol_fee: protocol_price.to_uint_floor(),
-        total_price: total_price.to_uint_floor(),
-    }
+    let base_price = compute_base_price(storage, supply, amount);
+
+    // the seller fee is split among its recipients, the referral share being assigned to the
+    // royalties when minting without a referral. The protocol fee is charged on top of it.
+    let fee = base_price.multiply_ratio(config.seller_fee_bps, MAX_BPS);
+    let fees = split_fees(&config.fee_split, fee, true)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let protocol_fee = base_price.multiply_ratio(config.protocol_fee_bps, MAX_BPS);
+
+    Ok(PriceResponse {
+        base_price,
+        royalties: fees.royalties,
+        referral: fees.referral,
+        protocol_fee,
+        addresses: fees.addresses,
+        total_price: base_price + fee + protocol_fee,
+    })
 }
 
-fn sell_price(storage: &dyn Storage, supply: Uint128, amount: Uint128) -> PriceResponse {
+fn sell_price(storage: &dyn Storage, supply: Uint128, amount: Uint128) -> StdResult<PriceResponse> {
     if amount > supply {
-        return PriceResponse {
+        return Ok(PriceResponse {
             base_price: Uint128::zero(),
             royalties: Uint128::zero(),
             referral: Uint128::zero(),
             protocol_fee: Uint128::zero(),
+            addresses: vec![],
             total_price: Uint128::zero(),
-        };
+        });
     }
 
     let config = CONFIG.load(storage).unwrap();
 
-    let base_price = Decimal::from_ratio(
-        compute_base_price(storage, supply.checked_sub(amount).unwrap(), amount),
-        1u32,
-    );
-
-    let royalties_fee = Decimal::from_ratio(config.seller_fee_bps, 10000u32);
-    let royalties_price = base_price * royalties_fee;
-
-    let referral_fee = Decimal::from_ratio(config.referral_fee_bps, 10000u32);
-    let referral_price = royalties_price * referral_fee;
-
-    let protocol_fee = Decimal::from_ratio(config.protocol_fee_bps, 10000u32);
-    let protocol_price = base_price * protocol_fee;
-
-    let total_price =
-        base_price - (royalties_price - referral_price) - referral_price - protocol_price;
-
-    PriceResponse {
-        base_price: base_price.to_uint_floor(),
-        royalties: (royalties_price - referral_price).to_uint_floor(),
-        referral: referral_price.to_uint_floor(),
-        protocol_fee: protocol_price.to_uint_floor(),
-        total_price: total_price.to_uint_floor(),
-    }
+    let base_price = compute_base_price(storage, supply.checked_sub(amount).unwrap(), amount);
+
+    // the seller fee is split among its recipients, the referral share being assigned to the
+    // royalties when burning without a referral. The protocol fee is charged on top of it.
+    let fee = base_price.multiply_ratio(config.seller_fee_bps, MAX_BPS);
+    let fees = split_fees(&config.fee_split, fee, true)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let protocol_fee = base_price.multiply_ratio(config.protocol_fee_bps, MAX_BPS);
+    let total_price = base_price
+        .checked_sub(fee)
+        .and_then(|price| price.checked_sub(protocol_fee))
+        .map_err(StdError::overflow)?;
+
+    Ok(PriceResponse {
+        base_price,
+        royalties: fees.royalties,
+        referral: fees.referral,
+        protocol_fee,
+        addresses: fees.addresses,
+        total_price,
+    })
 }
 
 fn query_supply(querier: QuerierWrapper, storage: &dyn Storage) -> Uint128 {
@@ -284,7 +379,7 @@ fn execute_burn(
     }
 
     let supply = query_supply(deps.querier, deps.storage);
-    let price = sell_price(deps.storage, supply, amount.into());
+    let price = sell_price(deps.storage, supply, amount.into())?;
 
     if min_out_amount > price.total_price.u128() {
         return Err(ContractError::MinOutAmount {
@@ -298,15 +393,24 @@ fn execute_burn(
 
     let mut royalties_sum = price.royalties;
 
-    // Pay referral
-    if !referral.is_none() {
+    // Pay referral, or accrue it until claimed
+    if let Some(referral) = referral {
+        record_referral(
+            deps.storage,
+            &referral,
+            0,
+            price.referral,
+            config.accrue_referrals,
+        )?;
         if !price.referral.is_zero() {
-            bank_msgs.push(BankMsg::Send {
-                to_address: referral.clone().unwrap().to_string(),
-                amount: vec![coin(price.referral.u128(), payment_denom.clone())],
-            });
+            if !config.accrue_referrals {
+                bank_msgs.push(BankMsg::Send {
+                    to_address: referral.to_string(),
+                    amount: vec![coin(price.referral.u128(), payment_denom.clone())],
+                });
+            }
 
-            attributes.push(attr("referral", referral.unwrap().to_string()));
+            attributes.push(attr("referral", referral.to_string()));
             attributes.push(attr("referral_amount", price.referral.u128().to_string()));
         }
     } else {
@@ -321,6 +425,14 @@ fn execute_burn(
 
     attributes.push(attr("royalties", royalties_sum.u128().to_string()));
 
+    // Pay fee split addresses
+    for fee in price.addresses.iter().filter(|fee| !fee.amount.is_zero()) {
+        bank_msgs.push(BankMsg::Send {
+            to_address: fee.address.to_string(),
+            amount: vec![coin(fee.amount.u128(), payment_denom.clone())],
+        });
+    }
+
     attributes.push(attr(
         "royalties_recipient",
         config.payment_address.clone().to_string(),
@@ -344,7 +456,7 @@ fn execute_burn(
 
     // decrease the number of tokens minted by the sender
     let already_minted = (ADDRESS_TOKENS.key(&info.sender).may_load(deps.storage)?).unwrap_or(0);
-    let new_total_mint = already_minted.checked_sub(amount).unwrap_or(0);
+    let new_total_mint = already_minted.saturating_sub(amount);
     ADDRESS_TOKENS.save(deps.storage, &info.sender, &new_total_mint)?;
 
     let token_ids_str: Vec<String> = token_ids.iter().map(|&n| n.to_string()).collect();
@@ -395,14 +507,14 @@ fn execute_mint(
     if let Some(max_per_address) = config.max_per_address {
         if new_total_mint > max_per_address {
             return Err(ContractError::MaxPerAddressExceeded {
-                remaining: max_per_address.checked_sub(already_minted).unwrap_or(0),
+                remaining: max_per_address.saturating_sub(already_minted),
             });
         }
     }
 
     let sent_amount = must_pay(&info, &payment_denom)?;
     let supply = query_supply(deps.querier, deps.storage);
-    let price = buy_price(deps.storage, supply, amount.into());
+    let price = buy_price(deps.storage, supply, amount.into())?;
 
     if sent_amount < price.total_price {
         return Err(ContractError::InvalidPaymentAmount(
@@ -421,14 +533,14 @@ fn execute_mint(
         let mut attributes: Vec<Trait> = vec![Trait {
             trait_type: "Edition".to_string(),
             value: token_id.to_string(),
-            display_type: Some("number".to_string()),
+            display_type: Some(DisplayType::Number),
         }];
 
         if let Some(max_edition) = config.max_edition {
             attributes.push(Trait {
                 trait_type: "Max Editions".to_string(),
                 value: max_edition.to_string(),
-                display_type: Some("number".to_string()),
+                display_type: Some(DisplayType::Number),
             });
             attributes.push(Trait {
                 trait_type: "Edition Type".to_string(),
@@ -448,6 +560,7 @@ fn execute_mint(
                 owner: info.sender.to_string(),
                 token_id: token_id.to_string(),
                 token_uri: Some(config.uri.clone()),
+                content_hash: config.content_hash.clone(),
                 extension: EditionMetadata {
                     name: format!("{} #{}", config.name, token_id),
                     attributes: Some(attributes),
@@ -462,7 +575,25 @@ fn execute_mint(
             funds: vec![],
         };
 
-        res = res.add_message(msg);
+        // a failing pre-mint hook reverts the whole mint.
+        let collection = config.bs721_address.clone().unwrap();
+        let premint_hooks = prepare_premint_hooks(
+            deps.as_ref(),
+            collection.clone(),
+            Some(token_id.to_string()),
+            info.sender.to_string(),
+        )?;
+        let postmint_hooks = prepare_postmint_hooks(
+            deps.as_ref(),
+            collection,
+            Some(token_id.to_string()),
+            info.sender.to_string(),
+        )?;
+
+        res = res
+            .add_submessages(premint_hooks)
+            .add_message(msg)
+            .add_submessages(postmint_hooks);
 
         token_ids.push(token_id);
 
@@ -475,15 +606,24 @@ fn execute_mint(
 
     let mut royalties_sum = price.royalties;
 
-    // Pay referral
-    if !referral.is_none() {
+    // Pay referral, or accrue it until claimed
+    if let Some(referral) = referral {
+        record_referral(
+            deps.storage,
+            &referral,
+            amount,
+            price.referral,
+            config.accrue_referrals,
+        )?;
         if !price.referral.is_zero() {
-            bank_msgs.push(BankMsg::Send {
-                to_address: referral.clone().unwrap().to_string(),
-                amount: vec![coin(price.referral.u128(), payment_denom.clone())],
-            });
+            if !config.accrue_referrals {
+                bank_msgs.push(BankMsg::Send {
+                    to_address: referral.to_string(),
+                    amount: vec![coin(price.referral.u128(), payment_denom.clone())],
+                });
+            }
 
-            attributes.push(attr("referral", referral.unwrap().to_string()));
+            attributes.push(attr("referral", referral.to_string()));
             attributes.push(attr("referral_amount", price.referral.u128().to_string()));
         }
     } else {
@@ -498,6 +638,14 @@ fn execute_mint(
 
     attributes.push(attr("royalties", royalties_sum.u128().to_string()));
 
+    // Pay fee split addresses
+    for fee in price.addresses.iter().filter(|fee| !fee.amount.is_zero()) {
+        bank_msgs.push(BankMsg::Send {
+            to_address: fee.address.to_string(),
+            amount: vec![coin(fee.amount.u128(), payment_denom.clone())],
+        });
+    }
+
     attributes.push(attr(
         "royalties_recipient",
         config.payment_address.clone().to_string(),
@@ -552,11 +700,10 @@ pub fn before_mint_checks(
     }
 
     let max_editions = config.max_edition.unwrap_or(0);
-    if max_editions > 0 {
-        if (config.next_token_id - 1) + edition_to_mint > max_editions {
+    if max_editions > 0
+        && (config.next_token_id - 1) + edition_to_mint > max_editions {
             return Err(ContractError::SoldOut {});
         }
-    }
 
     Ok(())
 }
@@ -574,6 +721,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::SellPrice { amount } => {
             to_json_binary(&query_sell_price(deps, Uint128::new(amount))?)
         }
+        QueryMsg::ReferralStats { address } => {
+            to_json_binary(&query_referral_stats(deps, address)?)
+        }
+        QueryMsg::TopReferrers { start_after, limit } => {
+            to_json_binary(&query_top_referrers(deps, start_after, limit)?)
+        }
+        QueryMsg::PreMintHooks {} => query_premint_hooks(deps),
+        QueryMsg::PostMintHooks {} => query_postmint_hooks(deps),
     }
 }
 
@@ -585,13 +740,66 @@ fn query_max_per_address(deps: Deps, address: String) -> StdResult<MaxPerAddress
 
     if let Some(max_per_address) = config.max_per_address {
         return Ok(MaxPerAddressResponse {
-            remaining: Some(max_per_address.checked_sub(already_minted).unwrap_or(0)),
+            remaining: Some(max_per_address.saturating_sub(already_minted)),
         });
     }
 
     Ok(MaxPerAddressResponse { remaining: None })
 }
 
+fn query_referral_stats(deps: Deps, address: String) -> StdResult<ReferralStatsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let stats = referrals()
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+
+    Ok(referral_stats_response(&config, address, stats))
+}
+
+fn query_top_referrers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TopReferrersResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    // referrers are iterated from the highest earned, so the cursor is the upper bound.
+    let max = start_after
+        .map(|address| -> StdResult<_> {
+            let address = deps.api.addr_validate(&address)?;
+            let stats = referrals().load(deps.storage, &address)?;
+            Ok(Bound::exclusive((stats.earned.u128(), address)))
+        })
+        .transpose()?;
+
+    let referrers = referrals()
+        .idx
+        .earned
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            let (address, stats) = item?;
+            Ok(referral_stats_response(&config, address, stats))
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(TopReferrersResponse { referrers })
+}
+
+fn referral_stats_response(
+    config: &Config,
+    address: Addr,
+    stats: ReferralStats,
+) -> ReferralStatsResponse {
+    ReferralStatsResponse {
+        address,
+        mints: stats.mints,
+        earned: coin(stats.earned.u128(), &config.payment_denom),
+        claimable: coin(stats.claimable.u128(), &config.payment_denom),
+    }
+}
+
 fn query_config(deps: Deps) -> StdResult<Config> {
     let config: Config = CONFIG.load(deps.storage)?;
     Ok(config)
@@ -600,13 +808,13 @@ fn query_config(deps: Deps) -> StdResult<Config> {
 fn query_buy_price(deps: Deps, amount: Uint128) -> StdResult<PriceResponse> {
     let supply = query_supply(deps.querier, deps.storage);
 
-    Ok(buy_price(deps.storage, supply, amount))
+    buy_price(deps.storage, supply, amount)
 }
 
 fn query_sell_price(deps: Deps, amount: Uint128) -> StdResult<PriceResponse> {
     let supply = query_supply(deps.querier, deps.storage);
 
-    Ok(sell_price(deps.storage, supply, amount))
+    sell_price(deps.storage, supply, amount)
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -614,7 +822,12 @@ fn query_sell_price(deps: Deps, amount: Uint128) -> StdResult<PriceResponse> {
 // -------------------------------------------------------------------------------------------------
 #[cfg(test)]
 mod test {
-    use cosmwasm_std::{testing::mock_dependencies, Timestamp};
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Decimal, Timestamp,
+    };
+
+    use bs_std::fees::{royalties_fee_split, AddressFee, FeeRecipient, FeeShare};
 
     use super::*;
 
@@ -650,10 +863,12 @@ mod test {
             bs721_address: None,
             name: "Test".to_string(),
             uri: "ipfs://Qm......".to_string(),
+            content_hash: None,
             next_token_id: 1,
             seller_fee_bps: 0,
-            referral_fee_bps: 0,
+            fee_split: royalties_fee_split(0, 0),
             protocol_fee_bps: 0,
+            accrue_referrals: false,
             payment_address: Addr::unchecked("payment_address"),
             start_time: Timestamp::from_seconds(0),
             max_edition: None,
@@ -680,10 +895,12 @@ mod test {
             bs721_address: None,
             name: "Test".to_string(),
             uri: "ipfs://Qm......".to_string(),
+            content_hash: None,
             next_token_id: 1,
             seller_fee_bps: 0,
-            referral_fee_bps: 0,
+            fee_split: royalties_fee_split(0, 0),
             protocol_fee_bps: 0,
+            accrue_referrals: false,
             payment_address: Addr::unchecked("payment_address"),
             start_time: Timestamp::from_seconds(0),
             max_edition: None,
@@ -710,10 +927,12 @@ mod test {
             bs721_address: None,
             name: "Test".to_string(),
             uri: "ipfs://Qm......".to_string(),
+            content_hash: None,
             next_token_id: 1,
             seller_fee_bps: 100,
-            referral_fee_bps: 0,
+            fee_split: royalties_fee_split(0, 0),
             protocol_fee_bps: 30,
+            accrue_referrals: false,
             payment_address: Addr::unchecked("payment_address"),
             start_time: Timestamp::from_seconds(0),
             max_edition: None,
@@ -724,7 +943,7 @@ mod test {
 
         let supply = Uint128::new(0);
         let amount = Uint128::new(1);
-        let price_response = buy_price(deps.as_ref().storage, supply, amount);
+        let price_response = buy_price(deps.as_ref().storage, supply, amount).unwrap();
 
         assert_eq!(
             price_response.base_price,
@@ -760,10 +979,12 @@ mod test {
             bs721_address: None,
             name: "Test".to_string(),
             uri: "ipfs://Qm......".to_string(),
+            content_hash: None,
             next_token_id: 1,
             seller_fee_bps: 100,
-            referral_fee_bps: 0,
+            fee_split: royalties_fee_split(0, 0),
             protocol_fee_bps: 30,
+            accrue_referrals: false,
             payment_address: Addr::unchecked("payment_address"),
             start_time: Timestamp::from_seconds(0),
             max_edition: None,
@@ -774,7 +995,7 @@ mod test {
 
         let supply = Uint128::new(1);
         let amount = Uint128::new(1);
-        let price_response = sell_price(deps.as_ref().storage, supply, amount);
+        let price_response = sell_price(deps.as_ref().storage, supply, amount).unwrap();
 
         assert_eq!(
             price_response.base_price,
@@ -798,6 +1019,118 @@ mod test {
         );
     }
 
+    #[test]
+    fn buy_price_with_fee_split_addresses() {
+        let mut deps = mock_dependencies();
+
+        let config = Config {
+            creator: Addr::unchecked("creator"),
+            symbol: "TEST".to_string(),
+            payment_denom: "ubtsg".to_string(),
+            max_per_address: None,
+            bs721_address: None,
+            name: "Test".to_string(),
+            uri: "ipfs://Qm......".to_string(),
+            content_hash: None,
+            next_token_id: 1,
+            seller_fee_bps: 1_000,
+            fee_split: vec![
+                FeeShare {
+                    recipient: FeeRecipient::Royalties,
+                    bps: 7_000,
+                },
+                FeeShare {
+                    recipient: FeeRecipient::Address("label".to_string()),
+                    bps: 3_000,
+                },
+            ],
+            protocol_fee_bps: 30,
+            accrue_referrals: false,
+            payment_address: Addr::unchecked("payment_address"),
+            start_time: Timestamp::from_seconds(0),
+            max_edition: None,
+            ratio: 1,
+        };
+
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let price_response =
+            buy_price(deps.as_ref().storage, Uint128::zero(), Uint128::one()).unwrap();
+        assert_eq!(
+            price_response,
+            PriceResponse {
+                base_price: Uint128::new(1_000_000),
+                royalties: Uint128::new(70_000),
+                referral: Uint128::zero(),
+                protocol_fee: Uint128::new(3_000),
+                addresses: vec![AddressFee {
+                    address: Addr::unchecked("label"),
+                    amount: Uint128::new(30_000),
+                }],
+                total_price: Uint128::new(1_103_000),
+            }
+        );
+    }
+
+    #[test]
+    fn instantiate_msg_validate_total_fee() {
+        let mut msg = InstantiateMsg {
+            symbol: "TEST".to_string(),
+            name: "Test".to_string(),
+            uri: "ipfs://Qm......".to_string(),
+            content_hash: None,
+            payment_denom: "ubtsg".to_string(),
+            max_per_address: None,
+            payment_address: "payment_address".to_string(),
+            seller_fee_bps: 9_000,
+            fee_split: royalties_fee_split(0, 0),
+            protocol_fee_bps: 1_000,
+            accrue_referrals: false,
+            start_time: Timestamp::from_seconds(0),
+            max_edition: None,
+            bs721_code_id: 1,
+            ratio: 1,
+            bs721_admin: "bs721_admin".to_string(),
+            creator: None,
+        };
+        msg.validate(mock_env()).unwrap();
+
+        msg.protocol_fee_bps = 1_001;
+        assert_eq!(
+            msg.validate(mock_env()).unwrap_err(),
+            ContractError::TotalFeeBps {},
+            "expected to fail since the fees exceed the base price"
+        );
+    }
+
+    #[test]
+    fn sell_price_fails_when_fees_exceed_base_price() {
+        let mut deps = mock_dependencies();
+
+        let config = Config {
+            creator: Addr::unchecked("creator"),
+            symbol: "TEST".to_string(),
+            payment_denom: "ubtsg".to_string(),
+            max_per_address: None,
+            bs721_address: None,
+            name: "Test".to_string(),
+            uri: "ipfs://Qm......".to_string(),
+            content_hash: None,
+            next_token_id: 1,
+            seller_fee_bps: 9_000,
+            fee_split: royalties_fee_split(0, 0),
+            protocol_fee_bps: 2_000,
+            accrue_referrals: false,
+            payment_address: Addr::unchecked("payment_address"),
+            start_time: Timestamp::from_seconds(0),
+            max_edition: None,
+            ratio: 1,
+        };
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        sell_price(deps.as_ref().storage, Uint128::one(), Uint128::one()).unwrap_err();
+    }
+
     #[test]
     fn compute_base_price_with_values() {
         let mut deps = mock_dependencies();
@@ -845,10 +1178,12 @@ mod test {
                 bs721_address: None,
                 name: "Test".to_string(),
                 uri: "ipfs://Qm......".to_string(),
+                content_hash: None,
                 next_token_id: 1,
                 seller_fee_bps: 0,
-                referral_fee_bps: 0,
+                fee_split: royalties_fee_split(0, 0),
                 protocol_fee_bps: 0,
+                accrue_referrals: false,
                 payment_address: Addr::unchecked("payment_address"),
                 start_time: Timestamp::from_seconds(0),
                 max_edition: None,
@@ -867,4 +1202,75 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn referral_stats_and_claim() {
+        let mut deps = mock_dependencies();
+
+        let config = Config {
+            creator: Addr::unchecked("creator"),
+            symbol: "TEST".to_string(),
+            payment_denom: "ubtsg".to_string(),
+            max_per_address: None,
+            bs721_address: None,
+            name: "Test".to_string(),
+            uri: "ipfs://Qm......".to_string(),
+            content_hash: None,
+            next_token_id: 1,
+            seller_fee_bps: 0,
+            fee_split: royalties_fee_split(0, 0),
+            protocol_fee_bps: 0,
+            accrue_referrals: true,
+            payment_address: Addr::unchecked("payment_address"),
+            start_time: Timestamp::from_seconds(0),
+            max_edition: None,
+            ratio: 1,
+        };
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let referral1 = Addr::unchecked("referral1");
+        let referral2 = Addr::unchecked("referral2");
+        record_referral(deps.as_mut().storage, &referral1, 2, Uint128::new(20), true).unwrap();
+        record_referral(deps.as_mut().storage, &referral2, 1, Uint128::new(30), true).unwrap();
+        // burns don't count as referred mints
+        record_referral(deps.as_mut().storage, &referral1, 0, Uint128::new(5), true).unwrap();
+
+        let top = query_top_referrers(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            top.referrers,
+            vec![
+                ReferralStatsResponse {
+                    address: referral2.clone(),
+                    mints: 1,
+                    earned: coin(30, "ubtsg"),
+                    claimable: coin(30, "ubtsg"),
+                },
+                ReferralStatsResponse {
+                    address: referral1.clone(),
+                    mints: 2,
+                    earned: coin(25, "ubtsg"),
+                    claimable: coin(25, "ubtsg"),
+                },
+            ]
+        );
+        let top =
+            query_top_referrers(deps.as_ref(), Some("referral2".to_string()), Some(1)).unwrap();
+        assert_eq!(top.referrers.len(), 1);
+        assert_eq!(top.referrers[0].address, referral1);
+
+        let res = execute_claim_referral(deps.as_mut(), mock_info("referral1", &[])).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "referral1".to_string(),
+                amount: vec![coin(25, "ubtsg")],
+            })
+        );
+        let stats = query_referral_stats(deps.as_ref(), "referral1".to_string()).unwrap();
+        assert_eq!(stats.earned, coin(25, "ubtsg"));
+        assert_eq!(stats.claimable, coin(0, "ubtsg"));
+
+        let err = execute_claim_referral(deps.as_mut(), mock_info("referral1", &[])).unwrap_err();
+        assert_eq!(err, ContractError::NothingToClaim {});
+    }
 }
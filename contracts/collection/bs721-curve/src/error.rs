@@ -1,3 +1,6 @@
+use bs721::MetadataError;
+use bs_mint_hooks::MintHookError;
+use bs_std::fees::FeeSplitError;
 use cosmwasm_std::{StdError, Uint128};
 use cw_utils::PaymentError;
 use thiserror::Error;
@@ -10,9 +13,21 @@ pub enum ContractError {
     #[error("{0}")]
     Payment(#[from] PaymentError),
 
+    #[error("{0}")]
+    MintHook(#[from] MintHookError),
+
+    #[error("{0}")]
+    FeeSplit(#[from] FeeSplitError),
+
+    #[error("{0}")]
+    Metadata(#[from] MetadataError),
+
     #[error("unauthorized")]
     Unauthorized {},
 
+    #[error("protocol fee is charged on the base price and cannot be a share of the fee split")]
+    ProtocolShareInFeeSplit {},
+
     #[error("BS721 contract already linked")]
     Bs721BaseAlreadyLinked {},
 
@@ -40,9 +55,15 @@ pub enum ContractError {
     #[error("{profile} fee bps must be less than 10000")]
     FeeBps { profile: String },
 
+    #[error("seller and protocol fee bps must sum to at most 10000")]
+    TotalFeeBps {},
+
     #[error("max number of mint, remaining: {remaining}")]
     MaxPerAddressExceeded { remaining: u32 },
 
     #[error("min out amount: {min_out_amount} is less then amount: {amount}")]
     MinOutAmount { amount: u128, min_out_amount: u128 },
+
+    #[error("nothing to claim")]
+    NothingToClaim {},
 }
@@ -0,0 +1,153 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, QuerierWrapper, StdResult, WasmMsg, WasmQuery,
+};
+use bs_controllers::HooksResponse;
+use serde::de::DeserializeOwned;
+
+use crate::msg::{
+    ExecuteMsg, MaxPerAddressResponse, PriceResponse, QueryMsg, ReferralStatsResponse,
+    TopReferrersResponse,
+};
+use crate::state::Config;
+
+/// CurveContract is a wrapper around Addr that provides a lot of helpers
+#[cw_serde]
+pub struct CurveContract(pub Addr);
+
+impl CurveContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    pub fn call_with_funds<T: Into<ExecuteMsg>>(
+        &self,
+        msg: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&msg.into())?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds,
+        }
+        .into())
+    }
+
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        req: QueryMsg,
+    ) -> StdResult<T> {
+        let query = WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&req)?,
+        }
+        .into();
+        querier.query(&query)
+    }
+
+    /// Mints `amount` tokens, `funds` must cover the buy price returned by `buy_price`.
+    pub fn mint(
+        &self,
+        amount: u32,
+        referral: Option<String>,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(ExecuteMsg::Mint { amount, referral }, funds)
+    }
+
+    /// Burns the tokens selling them back to the curve for at least `min_out_amount`.
+    pub fn burn(
+        &self,
+        token_ids: Vec<u32>,
+        min_out_amount: u128,
+        referral: Option<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Burn {
+            token_ids,
+            min_out_amount,
+            referral,
+        })
+    }
+
+    /// Claims the referral fees accrued by the sender.
+    pub fn claim_referral(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ClaimReferral {})
+    }
+
+    /// Only callable by the creator.
+    pub fn add_premint_hook(&self, hook: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::AddPreMintHook { hook })
+    }
+
+    /// Only callable by the creator.
+    pub fn add_postmint_hook(&self, hook: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::AddPostMintHook { hook })
+    }
+
+    /// Only callable by the creator.
+    pub fn remove_premint_hook(&self, hook: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RemovePreMintHook { hook })
+    }
+
+    /// Only callable by the creator.
+    pub fn remove_postmint_hook(&self, hook: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RemovePostMintHook { hook })
+    }
+
+    pub fn config(&self, querier: &QuerierWrapper) -> StdResult<Config> {
+        self.query(querier, QueryMsg::GetConfig {})
+    }
+
+    pub fn max_per_address<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<MaxPerAddressResponse> {
+        let req = QueryMsg::MaxPerAddress {
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn buy_price(&self, querier: &QuerierWrapper, amount: u128) -> StdResult<PriceResponse> {
+        self.query(querier, QueryMsg::BuyPrice { amount })
+    }
+
+    pub fn sell_price(&self, querier: &QuerierWrapper, amount: u128) -> StdResult<PriceResponse> {
+        self.query(querier, QueryMsg::SellPrice { amount })
+    }
+
+    pub fn referral_stats<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<ReferralStatsResponse> {
+        let req = QueryMsg::ReferralStats {
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn top_referrers(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TopReferrersResponse> {
+        self.query(querier, QueryMsg::TopReferrers { start_after, limit })
+    }
+
+    pub fn premint_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::PreMintHooks {})
+    }
+
+    pub fn postmint_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::PostMintHooks {})
+    }
+}
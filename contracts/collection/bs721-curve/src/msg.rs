@@ -1,5 +1,9 @@
+use bs721::validate_content_hash;
+use bs_controllers::HooksResponse;
+use bs_mint_hooks::{sg_mint_hooks_execute, sg_mint_hooks_query};
+use bs_std::fees::{AddressFee, FeeRecipient, FeeShare};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Env, StdError, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Coin, Env, StdError, Timestamp, Uint128};
 
 use crate::{state::Config, ContractError};
 
@@ -12,18 +16,26 @@ pub struct InstantiateMsg {
     pub name: String,
     /// BS721 Uri
     pub uri: String,
+    /// Hash of the content shared by every edition, as hex encoded sha256 digest or base32 CIDv1,
+    /// stored on each minted token.
+    pub content_hash: Option<String>,
     /// Denom used to pay for the NFTs
     pub payment_denom: String,
     /// Maximum amount of tokens an address can mint.
     pub max_per_address: Option<u32>,
     /// Payment address for the royalties.
     pub payment_address: String,
-    /// Basis per point of the `price` sent to the royalties address during mint or burn.
+    /// Basis per point of the base price charged as seller fee during mint or burn.
     pub seller_fee_bps: u16,
-    /// Basis per point of the `price` sent to the referred address during mint or burn.
-    pub referral_fee_bps: u16,
-    /// Basis per point of the `price` sent to the community pool during mint or burn.
+    /// Split of the seller fee among the royalties, the referral and any other address such as a
+    /// label or a charity. The shares must sum to 10000 bps.
+    pub fee_split: Vec<FeeShare>,
+    /// Basis per point of the base price sent to the community pool during mint or burn, on top
+    /// of the seller fee.
     pub protocol_fee_bps: u16,
+    /// If true, referral fees are accrued in the contract and claimed by each referral with
+    /// `ClaimReferral`, instead of being sent on every mint or burn.
+    pub accrue_referrals: bool,
     /// Start time of the launchparty.
     pub start_time: Timestamp,
     /// Max edition of the collection launchparty.
@@ -33,9 +45,14 @@ pub struct InstantiateMsg {
     /// Ratio, is the cooeficient of the curve
     pub ratio: u32,
     pub bs721_admin: String,
+    /// Creator of the collection, allowed to manage the mint hooks. If not provided it will be
+    /// the sender.
+    pub creator: Option<String>,
 }
 
-/// Possible state-changing messages that the launchparty-curve contract can handle.
+/// Possible state-changing messages that the launchparty-curve contract can handle. Mint hooks
+/// can be added and removed only by the creator.
+#[sg_mint_hooks_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Allows to mint a bs721 token and, optionally, to refer an address.
@@ -52,9 +69,13 @@ pub enum ExecuteMsg {
         min_out_amount: u128,
         referral: Option<String>,
     },
+
+    /// Sends to the sender the accrued referral fees.
+    ClaimReferral {},
 }
 
 /// Possible query messages that the launchparty-curve contract can handle.
+#[sg_mint_hooks_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -71,6 +92,17 @@ pub enum QueryMsg {
 
     #[returns(PriceResponse)]
     SellPrice { amount: u128 },
+
+    /// Returns the referral activity of an address.
+    #[returns(ReferralStatsResponse)]
+    ReferralStats { address: String },
+
+    /// Returns the referral addresses sorted by amount earned, from the highest.
+    #[returns(TopReferrersResponse)]
+    TopReferrers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -79,6 +111,8 @@ pub struct PriceResponse {
     pub royalties: Uint128,
     pub referral: Uint128,
     pub protocol_fee: Uint128,
+    /// Fees of the other addresses of the fee split.
+    pub addresses: Vec<AddressFee>,
     pub total_price: Uint128,
 }
 
@@ -88,6 +122,22 @@ pub struct MaxPerAddressResponse {
     pub remaining: Option<u32>,
 }
 
+#[cw_serde]
+pub struct ReferralStatsResponse {
+    pub address: Addr,
+    /// Number of tokens minted with the address as referral.
+    pub mints: u32,
+    /// Total referral fees earned on mints and burns.
+    pub earned: Coin,
+    /// Accrued referral fees that can be claimed with `ClaimReferral`.
+    pub claimable: Coin,
+}
+
+#[cw_serde]
+pub struct TopReferrersResponse {
+    pub referrers: Vec<ReferralStatsResponse>,
+}
+
 impl InstantiateMsg {
     const MAX_FEE_BPS: u16 = 10_000;
 
@@ -96,7 +146,9 @@ impl InstantiateMsg {
     /// # Validation Checks:
     ///
     /// - start time must be in the future.
-    /// - maximum bps allowed for both seller and referral.
+    /// - maximum bps allowed for the seller and the protocol, and for their sum.
+    /// - the protocol fee is not part of the fee split.
+    /// - content hash.
     /// - end condition of the launchparty.
     pub fn validate(&self, _env: Env) -> Result<(), ContractError> {
         // validate seller_fee_bps
@@ -105,13 +157,26 @@ impl InstantiateMsg {
                 profile: String::from("seller"),
             });
         }
-
-        // validate referral_fee_bps
-        if self.referral_fee_bps > Self::MAX_FEE_BPS {
+        if self.protocol_fee_bps > Self::MAX_FEE_BPS {
             return Err(ContractError::FeeBps {
-                profile: String::from("referral"),
+                profile: String::from("protocol"),
             });
         }
+        // the fees are subtracted from the base price when burning.
+        if self.seller_fee_bps as u32 + self.protocol_fee_bps as u32 > Self::MAX_FEE_BPS as u32 {
+            return Err(ContractError::TotalFeeBps {});
+        }
+        if self
+            .fee_split
+            .iter()
+            .any(|share| share.recipient == FeeRecipient::Protocol)
+        {
+            return Err(ContractError::ProtocolShareInFeeSplit {});
+        }
+
+        if let Some(content_hash) = &self.content_hash {
+            validate_content_hash("content_hash", content_hash)?;
+        }
 
         // validate denom
         if self.payment_denom.is_empty() {
@@ -1,6 +1,7 @@
+use bs_std::fees::FeeShare;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Timestamp};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 /// Smart contract configuration structure.
 #[cw_serde]
@@ -13,6 +14,8 @@ pub struct Config {
     pub name: String,
     /// URI of the NFT contract
     pub uri: String,
+    /// Hash of the content shared by every edition.
+    pub content_hash: Option<String>,
     /// Denom used to pay for the NFTs
     pub payment_denom: String,
     /// Maximum amount of token an address can mint.
@@ -20,8 +23,12 @@ pub struct Config {
     /// ID of the next NFT that will be minted. The first NFT will be minted with ID == 1.
     pub next_token_id: u32,
     pub seller_fee_bps: u16,
-    pub referral_fee_bps: u16,
+    /// Split of the seller fee among the royalties, the referral and any other address.
+    pub fee_split: Vec<FeeShare>,
+    /// Basis per point of the base price sent to the community pool during mint or burn.
     pub protocol_fee_bps: u16,
+    /// If true, referral fees are accrued in the contract and claimed by the referrals.
+    pub accrue_referrals: bool,
     /// Start time of the launchparty.
     pub start_time: Timestamp,
     /// Max edition of the collection launchparty.
@@ -38,13 +45,44 @@ pub struct Config {
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const ADDRESS_TOKENS: Map<&Addr, u32> = Map::new("address_tokens");
 
+/// Activity of a referral address, amounts are in the payment denom.
 #[cw_serde]
-pub struct Trait {
-    pub display_type: Option<String>,
-    pub trait_type: String,
-    pub value: String,
+#[derive(Default)]
+pub struct ReferralStats {
+    /// Number of tokens minted with the address as referral.
+    pub mints: u32,
+    /// Total referral fees earned on mints and burns.
+    pub earned: Uint128,
+    /// Referral fees accrued and not yet claimed.
+    pub claimable: Uint128,
 }
 
+pub struct ReferralIndexes<'a> {
+    pub earned: MultiIndex<'a, u128, ReferralStats, Addr>,
+}
+
+impl<'a> IndexList<ReferralStats> for ReferralIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<ReferralStats>> + '_> {
+        let v: Vec<&dyn Index<ReferralStats>> = vec![&self.earned];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Stats of each referral address, indexed by amount earned for the leaderboard.
+pub fn referrals<'a>() -> IndexedMap<'a, &'a Addr, ReferralStats, ReferralIndexes<'a>> {
+    let indexes = ReferralIndexes {
+        earned: MultiIndex::new(
+            |_pk: &[u8], d: &ReferralStats| d.earned.u128(),
+            "referrals",
+            "referrals__earned",
+        ),
+    };
+    IndexedMap::new("referrals", indexes)
+}
+
+pub use bs721::Trait;
+
+/// Token extension, a subset of [`bs721::Metadata`] so that the collection stores and exports it.
 #[cw_serde]
 pub struct EditionMetadata {
     pub name: String,
@@ -4,6 +4,7 @@ use crate::{
     state::{REVERSE_MAP, SUDO_PARAMS, VERIFIER},
     SudoParams,
 };
+use bs721::validate_content_hash;
 use bs721_base::msg::MintMsg;
 use cosmwasm_std::{
     ensure, to_json_binary, Addr, Binary, ContractInfoResponse, Deps, DepsMut, Empty, Env, Event,
@@ -105,30 +106,39 @@ pub fn execute_mint(
 ) -> Result<Response, ContractError> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
 
-    let (token_id, owner, _token_uri, extension, _seller_fee_bps, _payment_addr) = match msg {
-        Bs721ExecuteMsg::Mint(MintMsg {
-            token_id,
-            owner,
-            token_uri,
-            extension,
-            seller_fee_bps,
-            payment_addr,
-        }) => (
-            token_id,
-            owner,
-            token_uri,
-            extension,
-            seller_fee_bps,
-            payment_addr,
-        ),
-        _ => return Err(ContractError::NotImplemented {}),
-    };
+    let (token_id, owner, _token_uri, content_hash, extension, _seller_fee_bps, _payment_addr) =
+        match msg {
+            Bs721ExecuteMsg::Mint(MintMsg {
+                token_id,
+                owner,
+                token_uri,
+                content_hash,
+                extension,
+                seller_fee_bps,
+                payment_addr,
+            }) => (
+                token_id,
+                owner,
+                token_uri,
+                content_hash,
+                extension,
+                seller_fee_bps,
+                payment_addr,
+            ),
+            _ => return Err(ContractError::NotImplemented {}),
+        };
+
+    if let Some(ref content_hash) = content_hash {
+        validate_content_hash("content_hash", content_hash)
+            .map_err(bs721_base::ContractError::from)?;
+    }
 
     // create the token
     let token = TokenInfo {
         owner: deps.api.addr_validate(&owner)?,
         approvals: vec![],
         token_uri: None,
+        content_hash,
         extension: extension.clone(),
         seller_fee_bps: None,
         payment_addr: None,
@@ -499,7 +509,7 @@ fn only_owner(deps: Deps, sender: &Addr, token_id: &str) -> Result<Addr, Contrac
         .load(deps.storage, token_id)?
         .owner;
 
-    if &owner != sender {
+    if owner != sender {
         return Err(ContractError::Base(Unauthorized {}));
     }
 
@@ -591,7 +601,7 @@ pub fn transcode(address: &str) -> StdResult<String> {
 
 fn validate_address(deps: Deps, sender: &Addr, addr: Addr) -> Result<Addr, ContractError> {
     // we have an EOA registration
-    if sender == &addr {
+    if sender == addr {
         return Ok(addr);
     }
 
@@ -602,7 +612,7 @@ fn validate_address(deps: Deps, sender: &Addr, addr: Addr) -> Result<Addr, Contr
         let collection_info: MinterResponse = deps
             .querier
             .query_wasm_smart(&addr, &bs721_base::msg::QueryMsg::<Empty>::Minter {})?;
-        if collection_info.minter == sender.to_string() {
+        if collection_info.minter == *sender {
             return Ok(addr);
         }
     }
@@ -619,7 +629,7 @@ fn validate_address(deps: Deps, sender: &Addr, addr: Addr) -> Result<Addr, Contr
         } else {
             // If there is no admin and the creator is not the sender, check creator's admin
             let creator_info = deps.querier.query_wasm_contract_info(&creator)?;
-            if creator_info.admin.map_or(true, |a| &a != sender) {
+            if creator_info.admin.is_none_or(|a| &a != sender) {
                 return Err(ContractError::UnauthorizedCreatorOrAdmin {});
             }
         }
@@ -99,6 +99,7 @@ pub fn execute(
                 token_id: msg.token_id,
                 owner: msg.owner,
                 token_uri: None,
+                content_hash: msg.content_hash,
                 extension: msg.extension,
                 seller_fee_bps: msg.seller_fee_bps,
                 payment_addr: msg.payment_addr,
@@ -1,7 +1,10 @@
-use crate::QueryMsg;
-use bs_profile::{TextRecord, NFT};
+use crate::{ExecuteMsg, QueryMsg, SudoParams};
+use bs_profile::{Metadata, TextRecord, NFT};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Addr, QuerierWrapper, QueryRequest, StdResult, WasmQuery};
+use cosmwasm_std::{
+    to_json_binary, Addr, CosmosMsg, QuerierWrapper, QueryRequest, StdResult, WasmMsg, WasmQuery,
+};
+use serde::de::DeserializeOwned;
 
 /// NameCollectionContract is a wrapper around Addr that provides a lot of helpers
 #[cw_serde]
@@ -12,6 +15,93 @@ impl NameCollectionContract {
         self.0.clone()
     }
 
+    pub fn call<T: Into<ExecuteMsg<Metadata>>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&msg.into())?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        req: QueryMsg,
+    ) -> StdResult<T> {
+        querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&req)?,
+        }))
+    }
+
+    pub fn associate_address(&self, name: &str, address: Option<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::AssociateAddress {
+            name: name.to_string(),
+            address,
+        })
+    }
+
+    pub fn update_image_nft(&self, name: &str, nft: Option<NFT>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdateImageNft {
+            name: name.to_string(),
+            nft,
+        })
+    }
+
+    pub fn add_text_record(&self, name: &str, record: TextRecord) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::AddTextRecord {
+            name: name.to_string(),
+            record,
+        })
+    }
+
+    pub fn update_text_record(&self, name: &str, record: TextRecord) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdateTextRecord {
+            name: name.to_string(),
+            record,
+        })
+    }
+
+    pub fn remove_text_record(&self, name: &str, record_name: &str) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RemoveTextRecord {
+            name: name.to_string(),
+            record_name: record_name.to_string(),
+        })
+    }
+
+    /// Only callable by the verification oracle
+    pub fn verify_text_record(
+        &self,
+        name: &str,
+        record_name: &str,
+        result: bool,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::VerifyTextRecord {
+            name: name.to_string(),
+            record_name: record_name.to_string(),
+            result,
+        })
+    }
+
+    pub fn transfer_nft(&self, recipient: &str, token_id: &str) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.to_string(),
+        })
+    }
+
+    pub fn burn(&self, token_id: &str) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Burn {
+            token_id: token_id.to_string(),
+        })
+    }
+
+    pub fn params(&self, querier: &QuerierWrapper) -> StdResult<SudoParams> {
+        self.query(querier, QueryMsg::Params {})
+    }
+
     pub fn name(&self, querier: &QuerierWrapper, address: &str) -> StdResult<String> {
         let res: String = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: self.addr().into(),
@@ -23,6 +113,17 @@ impl NameCollectionContract {
         Ok(res)
     }
 
+    pub fn profile_marketplace(&self, querier: &QuerierWrapper) -> StdResult<Addr> {
+        self.query(querier, QueryMsg::ProfileMarketplace {})
+    }
+
+    pub fn associated_address(&self, querier: &QuerierWrapper, name: &str) -> StdResult<Addr> {
+        let req = QueryMsg::AssociatedAddress {
+            name: name.to_string(),
+        };
+        self.query(querier, req)
+    }
+
     pub fn image_nft(&self, querier: &QuerierWrapper, name: &str) -> StdResult<Option<NFT>> {
         let res: Option<NFT> = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: self.addr().into(),
@@ -45,6 +146,13 @@ impl NameCollectionContract {
         Ok(res)
     }
 
+    pub fn current_manager(&self, querier: &QuerierWrapper, name: &str) -> StdResult<String> {
+        let req = QueryMsg::CurrentManager {
+            name: name.to_string(),
+        };
+        self.query(querier, req)
+    }
+
     pub fn is_twitter_verified(&self, querier: &QuerierWrapper, name: &str) -> StdResult<bool> {
         let res: bool = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: self.addr().into(),
@@ -55,4 +163,8 @@ impl NameCollectionContract {
 
         Ok(res)
     }
+
+    pub fn verifier(&self, querier: &QuerierWrapper) -> StdResult<Option<String>> {
+        self.query(querier, QueryMsg::Verifier {})
+    }
 }
@@ -52,7 +52,6 @@ pub struct InstantiateMsg {
 }
 
 #[cosmwasm_schema::cw_serde]
-#[cfg_attr(feature = "interface", derive(cw_orch::ExecuteFns))]
 pub enum ExecuteMsg<T> {
     /// Set name marketplace contract address
     SetProfileMarketplace { address: String },
@@ -161,7 +160,7 @@ impl<T> From<ExecuteMsg<T>> for Bs721ExecuteMsg<T, Empty> {
             //     Bs721ExecuteMsg::UpdateStartTradingTime(start_time)
             // }
             // ExecuteMsg::FreezeCollectionInfo {} => Bs721ExecuteMsg::FreezeCollectionInfo {},
-            ExecuteMsg::Mint(msg) => Bs721ExecuteMsg::Mint(MintMsg::from(msg)),
+            ExecuteMsg::Mint(msg) => Bs721ExecuteMsg::Mint(msg),
             _ => unreachable!("Invalid ExecuteMsg"),
         }
     }
@@ -171,7 +170,6 @@ pub struct MigrateMsg {}
 
 #[cosmwasm_schema::cw_serde]
 #[derive(QueryResponses)]
-#[cfg_attr(feature = "interface", derive(cw_orch::QueryFns))]
 pub enum QueryMsg {
     /// Contains the enabled modules
     /// Returns sudo params
@@ -39,6 +39,7 @@ where
         let info = self.tokens.load(deps.storage, &token_id)?;
         Ok(NftInfoResponse {
             token_uri: info.token_uri,
+            content_hash: info.content_hash,
             seller_fee_bps: info.seller_fee_bps,
             payment_addr: info.payment_addr,
             extension: info.extension,
@@ -205,6 +206,7 @@ where
             },
             info: NftInfoResponse {
                 token_uri: info.token_uri,
+                content_hash: info.content_hash,
                 seller_fee_bps: info.seller_fee_bps,
                 payment_addr: info.payment_addr,
                 extension: info.extension,
@@ -68,6 +68,7 @@ fn init_msg() -> InstantiateMsg {
     let collection_info = CollectionInfo {
         creator: "bobo".to_string(),
         description: "bobo name da best".to_string(),
+        image_hash: None,
         image: "ipfs://something".to_string(),
         external_link: None,
         explicit_content: None,
@@ -108,7 +109,7 @@ fn mint_and_update() {
 
     // retrieve max record count
     let params: SudoParams =
-        from_json(&query(deps.as_ref(), mock_env(), QueryMsg::Params {}).unwrap()).unwrap();
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Params {}).unwrap()).unwrap();
     let max_record_count = params.max_record_count;
 
     // mint token
@@ -123,6 +124,7 @@ fn mint_and_update() {
                 token_id: token_id.to_string(),
                 owner: info.sender.to_string(),
                 token_uri: None,
+                content_hash: None,
                 seller_fee_bps: None,
                 payment_addr: None,
                 extension: Metadata::default(),
@@ -312,6 +314,42 @@ fn mint_and_update() {
     assert_eq!(res.extension.records.len(), 1);
 }
 
+#[test]
+fn mint_with_content_hash() {
+    let contract = Bs721ProfileContract::default();
+    let mut deps = mock_deps();
+    let info = mock_info(CREATOR, &[]);
+
+    instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg()).unwrap();
+
+    let mint_msg = |content_hash: &str| {
+        ExecuteMsg::Mint(MintMsg {
+            token_id: "Enterprise".to_string(),
+            owner: info.sender.to_string(),
+            token_uri: None,
+            content_hash: Some(content_hash.to_string()),
+            seller_fee_bps: None,
+            payment_addr: None,
+            extension: Metadata::default(),
+        })
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        mint_msg("not a hash"),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Base(_)));
+
+    let content_hash = "a".repeat(64);
+    execute(deps.as_mut(), mock_env(), info.clone(), mint_msg(&content_hash)).unwrap();
+
+    let token = contract.tokens.load(&deps.storage, "Enterprise").unwrap();
+    assert_eq!(token.content_hash, Some(content_hash));
+}
+
 #[test]
 fn query_names() {
     let deps = mock_deps();
@@ -0,0 +1,66 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg, WasmQuery,
+};
+use serde::de::DeserializeOwned;
+
+use crate::msg::{ContributorListResponse, ExecuteMsg, QueryMsg};
+
+/// RoyaltiesContract is a wrapper around Addr that provides a lot of helpers
+#[cw_serde]
+pub struct RoyaltiesContract(pub Addr);
+
+impl RoyaltiesContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&msg.into())?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        req: QueryMsg,
+    ) -> StdResult<T> {
+        let query = WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&req)?,
+        }
+        .into();
+        querier.query(&query)
+    }
+
+    pub fn distribute(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Distribute {})
+    }
+
+    pub fn withdraw(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Withdraw {})
+    }
+
+    pub fn list_contributors(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ContributorListResponse> {
+        let req = QueryMsg::ListContributors { start_after, limit };
+        self.query(querier, req)
+    }
+
+    pub fn withdrawable_amount(&self, querier: &QuerierWrapper) -> StdResult<Uint128> {
+        self.query(querier, QueryMsg::WithdrawableAmount {})
+    }
+
+    pub fn distributable_amount(&self, querier: &QuerierWrapper) -> StdResult<Uint128> {
+        self.query(querier, QueryMsg::DistributableAmount {})
+    }
+}
@@ -1,9 +1,11 @@
 pub mod contract;
 pub mod error;
+pub mod helpers;
 pub mod msg;
 pub mod state;
 
 pub use crate::error::ContractError;
+pub use crate::helpers::RoyaltiesContract;
 
 #[cfg(test)]
 mod tests;
@@ -7,6 +7,8 @@ use cosmos_sdk_proto::{cosmos::distribution::v1beta1::MsgFundCommunityPool, trai
 use bs721_curve::msg::InstantiateMsg as Bs721CurveMsgInstantiate;
 
 use bs721_launchparty::msg::InstantiateMsg as LaunchpartyFixedMsgInstantiate;
+
+use bs_std::fees::{fee_share_bps, FeeRecipient, FeeShare};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
@@ -81,6 +83,7 @@ pub fn execute(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -159,6 +162,16 @@ fn execute_create_royalties_group(
         .add_attribute("action", "create_royalties_group"))
 }
 
+/// Returns an error if the protocol share of the fee split is lower than the protocol fee.
+fn check_protocol_fee_share(config: &Config, fee_split: &[FeeShare]) -> Result<(), ContractError> {
+    if (fee_share_bps(fee_split, &FeeRecipient::Protocol) as u32) < config.protocol_fee_bps {
+        return Err(ContractError::ProtocolFeeShare {
+            min_bps: config.protocol_fee_bps,
+        });
+    }
+    Ok(())
+}
+
 fn execute_create_curve(
     deps: DepsMut,
     env: Env,
@@ -196,15 +209,18 @@ fn execute_create_curve(
             payment_denom: msg.payment_denom,
             max_per_address: msg.max_per_address,
             uri: msg.uri,
+            content_hash: msg.content_hash,
             payment_address: msg.payment_address,
             seller_fee_bps: msg.seller_fee_bps,
-            referral_fee_bps: msg.referral_fee_bps,
+            fee_split: msg.fee_split,
             protocol_fee_bps: config.protocol_fee_bps as u16,
+            accrue_referrals: msg.accrue_referrals,
             start_time: msg.start_time,
             max_edition: msg.max_edition,
             ratio: msg.ratio,
             bs721_code_id: config.bs721_code_id,
             bs721_admin: config.owner.to_string(),
+            creator: Some(info.sender.to_string()),
         })?,
         funds: vec![],
         label: format!(
@@ -225,6 +241,7 @@ fn execute_create_launchparty(
     msg: MsgCreateLaunchparty,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    check_protocol_fee_share(&config, &msg.fee_split)?;
 
     let sent_amount = must_pay(&info, &config.create_nft_sale_fee.denom)?;
 
@@ -253,16 +270,33 @@ fn execute_create_launchparty(
             symbol: msg.symbol,
             name: msg.name,
             uri: msg.uri,
+            content_hash: msg.content_hash,
             price: msg.price,
             max_per_address: msg.max_per_address,
             seller_fee_bps: msg.seller_fee_bps,
-            referral_fee_bps: msg.referral_fee_bps,
-            protocol_fee_bps: config.protocol_fee_bps as u16,
+            fee_split: msg.fee_split,
+            accrue_referrals: msg.accrue_referrals,
             start_time: msg.start_time,
             party_type: msg.party_type,
+            randomized: msg.randomized,
+            min_raise: msg.min_raise,
+            limit_target: msg.limit_target,
+            mint_limits: msg.mint_limits,
+            reserved_supply: msg.reserved_supply,
+            dutch_auction: msg.dutch_auction,
+            burn_to_mint: msg.burn_to_mint,
+            raffle: msg.raffle,
+            token_gate: msg.token_gate,
+            tiers: msg.tiers,
+            reveal: msg.reveal,
+            minter_handover: msg.minter_handover,
             bs721_code_id: config.bs721_code_id,
             payment_address: msg.payment_address,
             bs721_admin: config.owner.to_string(),
+            creator: Some(info.sender.to_string()),
+            cw20_address: msg.cw20_address,
+            allowlist_phases: msg.allowlist_phases,
+            stages: msg.stages,
         })?,
         funds: vec![],
         label: format!(
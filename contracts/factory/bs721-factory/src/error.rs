@@ -52,6 +52,9 @@ pub enum ContractError {
     #[error("{profile} fee bps must be less than 10000")]
     FeeBps { profile: String },
 
+    #[error("protocol fee share must be at least {min_bps} bps")]
+    ProtocolFeeShare { min_bps: u32 },
+
     #[error("max number of pre-generated metadata reached")]
     MaxMetadataReached {},
 
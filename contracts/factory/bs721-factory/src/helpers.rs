@@ -0,0 +1,79 @@
+use bs721_royalties::msg::ContributorMsg;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, QuerierWrapper, StdResult, WasmMsg, WasmQuery,
+};
+use serde::de::DeserializeOwned;
+
+use crate::msg::{ExecuteMsg, MsgCreateCurve, MsgCreateLaunchparty, QueryMsg};
+use crate::state::Config;
+
+/// FactoryContract is a wrapper around Addr that provides a lot of helpers
+#[cw_serde]
+pub struct FactoryContract(pub Addr);
+
+impl FactoryContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    pub fn call_with_funds<T: Into<ExecuteMsg>>(
+        &self,
+        msg: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&msg.into())?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds,
+        }
+        .into())
+    }
+
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        req: QueryMsg,
+    ) -> StdResult<T> {
+        let query = WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&req)?,
+        }
+        .into();
+        querier.query(&query)
+    }
+
+    /// Creates a launchparty, `funds` must cover the `create_nft_sale_fee` of the config.
+    pub fn create_launchparty(
+        &self,
+        msg: MsgCreateLaunchparty,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(ExecuteMsg::CreateLaunchaparty(msg), funds)
+    }
+
+    /// Creates a curve, `funds` must cover the `create_nft_sale_fee` of the config.
+    pub fn create_curve(&self, msg: MsgCreateCurve, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        self.call_with_funds(ExecuteMsg::CreateCurve(msg), funds)
+    }
+
+    pub fn create_royalties_group(
+        &self,
+        denom: String,
+        contributors: Vec<ContributorMsg>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::CreateRoyaltiesGroup {
+            denom,
+            contributors,
+        })
+    }
+
+    pub fn config(&self, querier: &QuerierWrapper) -> StdResult<Config> {
+        self.query(querier, QueryMsg::Config {})
+    }
+}
@@ -1,6 +1,8 @@
 pub mod contract;
 mod error;
+pub mod helpers;
 pub mod msg;
 pub mod state;
 
 pub use crate::error::ContractError;
+pub use crate::helpers::FactoryContract;
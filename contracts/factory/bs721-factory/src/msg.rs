@@ -1,8 +1,12 @@
 use bs721_royalties::msg::ContributorMsg;
+use bs_std::fees::FeeShare;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Coin, Env, Timestamp};
+use cosmwasm_std::{Coin, Env, Timestamp, Uint128};
 
-use bs721_launchparty::msg::PartyType;
+use bs721_launchparty::msg::{
+    AllowlistPhase, BurnToMint, DelayedReveal, DutchAuction, EditionTier, MintLimitTarget,
+    MintLimits, MintStage, MinterHandover, PartyType, Raffle, TokenGate,
+};
 
 use crate::{state::Config, ContractError};
 
@@ -33,6 +37,8 @@ pub struct MsgCreateLaunchparty {
     pub name: String,
     /// BS721 Uri
     pub uri: String,
+    /// Hash of the content shared by every edition.
+    pub content_hash: Option<String>,
     /// Price of single nft minting.
     pub price: Coin,
     /// Maximum amount of tokens an address can mint.
@@ -40,9 +46,11 @@ pub struct MsgCreateLaunchparty {
     /// Basis per point of the `price` sent to the referred address during mint. This payment is sent
     /// one-off.
     pub seller_fee_bps: u16,
-    /// Basis per point of the `price` sent to the referred address during mint. This payment is sent
-    /// one-off.
-    pub referral_fee_bps: u16,
+    /// Split of the mint price among its recipients. The protocol share must be at least the
+    /// protocol fee of the factory.
+    pub fee_split: Vec<FeeShare>,
+    /// If true, referral fees are accrued in the launchparty and claimed by each referral.
+    pub accrue_referrals: bool,
     /// Contributors to the collection.
     pub contributors: Vec<ContributorMsg>,
     /// Start time of the launchparty.
@@ -51,6 +59,40 @@ pub struct MsgCreateLaunchparty {
     pub party_type: PartyType,
 
     pub payment_address: String,
+    /// Allowlist phases of the launchparty, usually held before the public `start_time`.
+    pub allowlist_phases: Vec<AllowlistPhase>,
+    /// Ordered stages of the public mint.
+    pub stages: Vec<MintStage>,
+    /// Address of the cw20 token used to pay for mints. If not provided, mints are paid in native
+    /// coins.
+    pub cw20_address: Option<String>,
+    /// If true, minters receive a random not yet minted index of the collection as token ID.
+    pub randomized: bool,
+    /// If provided the launchparty is a crowdfund, releasing the mint funds only if this amount
+    /// is raised.
+    pub min_raise: Option<Uint128>,
+    /// Address the per address limits are applied to when minting to a recipient.
+    pub limit_target: MintLimitTarget,
+    /// If provided, limits minting per block, per transaction and per address over time.
+    pub mint_limits: Option<MintLimits>,
+    /// Editions reserved for the creator airdrops.
+    pub reserved_supply: u32,
+    /// If provided, the price of the public mint is given by the dutch auction.
+    pub dutch_auction: Option<DutchAuction>,
+    /// If provided, editions can only be minted by redeeming tokens of another collection.
+    pub burn_to_mint: Option<BurnToMint>,
+    /// If provided, editions can only be won in the raffle.
+    pub raffle: Option<Raffle>,
+    /// If provided, the public mint is restricted or discounted to the holders of tokens of other
+    /// collections.
+    pub token_gate: Option<TokenGate>,
+    /// Tiers of the editions, with their own URI and attributes.
+    pub tiers: Vec<EditionTier>,
+    /// If provided, editions are minted with a placeholder URI until the creator reveals the
+    /// final metadata.
+    pub reveal: Option<DelayedReveal>,
+    /// What happens to the minter role of the collection when the launchparty is finalized.
+    pub minter_handover: MinterHandover,
 }
 
 #[cw_serde]
@@ -61,6 +103,8 @@ pub struct MsgCreateCurve {
     pub name: String,
     /// BS721 Uri
     pub uri: String,
+    /// Hash of the content shared by every edition.
+    pub content_hash: Option<String>,
     /// Denom used to pay for the NFTs
     pub payment_denom: String,
     pub payment_address: String,
@@ -69,9 +113,11 @@ pub struct MsgCreateCurve {
     /// Basis per point of the `price` sent to the referred address during mint. This payment is sent
     /// one-off.
     pub seller_fee_bps: u16,
-    /// Basis per point of the `price` sent to the referred address during mint. This payment is sent
-    /// one-off.
-    pub referral_fee_bps: u16,
+    /// Split of the seller fee of mints and burns among its recipients. The protocol fee of the
+    /// factory is charged on top of it, so the split cannot have a protocol share.
+    pub fee_split: Vec<FeeShare>,
+    /// If true, referral fees are accrued in the curve and claimed by each referral.
+    pub accrue_referrals: bool,
     /// Start time of the launchparty.
     pub start_time: Timestamp,
     /// Max edition of the collection launchparty.
@@ -81,6 +127,7 @@ pub struct MsgCreateCurve {
 }
 
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     UpdateConfig {
         /// Address allowed to update contract parameters
@@ -2,14 +2,16 @@ use std::cmp::max;
 
 use bs_profile::{
     common::{charge_fees, SECONDS_PER_YEAR},
-    market::{state::*, ExecuteMsg, QueryMsg},
+    market::{state::*, BidOffset, ConfigResponse, ExecuteMsg, QueryMsg},
 };
+use bs_controllers::HooksResponse;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_json_binary, Addr, CosmosMsg, Deps, DepsMut, Env, Event, Order, QuerierWrapper,
+    to_json_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, Env, Event, Order, QuerierWrapper,
     QueryRequest, Response, StdError, StdResult, Timestamp, Uint128, WasmMsg, WasmQuery,
 };
 use cw_storage_plus::Bound;
+use serde::de::DeserializeOwned;
 
 use crate::{commands::store_ask, ContractError};
 
@@ -23,15 +25,50 @@ impl ProfileMarketplaceContract {
     }
 
     pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    pub fn call_with_funds<T: Into<ExecuteMsg>>(
+        &self,
+        msg: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
         let msg = to_json_binary(&msg.into())?;
         Ok(WasmMsg::Execute {
             contract_addr: self.addr().into(),
             msg,
-            funds: vec![],
+            funds,
         }
         .into())
     }
 
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        req: QueryMsg,
+    ) -> StdResult<T> {
+        querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&req)?,
+        }))
+    }
+
+    /// Places a bid on a name, the bid amount is given by `funds`.
+    pub fn set_bid(&self, token_id: &str, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::SetBid {
+                token_id: token_id.to_string(),
+            },
+            funds,
+        )
+    }
+
+    pub fn remove_bid(&self, token_id: &str) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RemoveBid {
+            token_id: token_id.to_string(),
+        })
+    }
+
     pub fn remove_ask(&self, token_id: &str) -> StdResult<CosmosMsg> {
         self.call(ExecuteMsg::RemoveAsk {
             token_id: token_id.to_string(),
@@ -49,6 +86,128 @@ impl ProfileMarketplaceContract {
         Ok(res)
     }
 
+    pub fn ask(&self, querier: &QuerierWrapper, token_id: &str) -> StdResult<Option<Ask>> {
+        let req = QueryMsg::Ask {
+            token_id: token_id.to_string(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn asks(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Ask>> {
+        self.query(querier, QueryMsg::Asks { start_after, limit })
+    }
+
+    pub fn ask_count(&self, querier: &QuerierWrapper) -> StdResult<u64> {
+        self.query(querier, QueryMsg::AskCount {})
+    }
+
+    pub fn asks_by_seller(
+        &self,
+        querier: &QuerierWrapper,
+        seller: &str,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Ask>> {
+        let req = QueryMsg::AsksBySeller {
+            seller: seller.to_string(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn bid(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: &str,
+        bidder: &str,
+    ) -> StdResult<Option<Bid>> {
+        let req = QueryMsg::Bid {
+            token_id: token_id.to_string(),
+            bidder: bidder.to_string(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn bids(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: &str,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Bid>> {
+        let req = QueryMsg::Bids {
+            token_id: token_id.to_string(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn bids_by_bidder(
+        &self,
+        querier: &QuerierWrapper,
+        bidder: &str,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Bid>> {
+        let req = QueryMsg::BidsByBidder {
+            bidder: bidder.to_string(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn bids_sorted_by_price(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<BidOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Bid>> {
+        self.query(querier, QueryMsg::BidsSortedByPrice { start_after, limit })
+    }
+
+    pub fn bids_for_seller(
+        &self,
+        querier: &QuerierWrapper,
+        seller: &str,
+        start_after: Option<BidOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Bid>> {
+        let req = QueryMsg::BidsForSeller {
+            seller: seller.to_string(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn ask_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::AskHooks {})
+    }
+
+    pub fn bid_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::BidHooks {})
+    }
+
+    pub fn sale_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::SaleHooks {})
+    }
+
+    pub fn params(&self, querier: &QuerierWrapper) -> StdResult<SudoParams> {
+        self.query(querier, QueryMsg::Params {})
+    }
+
+    pub fn config(&self, querier: &QuerierWrapper) -> StdResult<ConfigResponse> {
+        self.query(querier, QueryMsg::Config {})
+    }
+
     // contract needs approval from nft owner before accepting bid
     pub fn accept_bid(
         &self,
@@ -1,7 +1,7 @@
 use crate::commands::{query_asks_by_seller, query_bids_by_bidder};
 use crate::contract::{execute, instantiate};
 #[cfg(test)]
-use bs_profile::market::{state::*, *};
+use bs_profile::market::state::*;
 use bs_profile::market::{ExecuteMsg, InstantiateMsg};
 use bs_std::NATIVE_DENOM;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
@@ -0,0 +1,137 @@
+use cosmwasm_std::Addr;
+use sha2::{Digest, Sha256};
+
+use crate::ContractError;
+
+/// Maximum number of hashes accepted in a Merkle proof.
+const MAX_PROOF_LENGTH: usize = 32;
+
+/// Checks that the Merkle root is a hex encoded sha256 hash.
+pub fn validate_merkle_root(merkle_root: &str) -> Result<(), ContractError> {
+    decode_hash(merkle_root).map_err(|_| ContractError::InvalidMerkleRoot {})?;
+    Ok(())
+}
+
+/// Verifies that the leaf built from `address` and `allocation` belongs to the Merkle tree
+/// identified by `merkle_root`.
+///
+/// The leaf is `sha256(address ++ allocation)`, and each pair of nodes is sorted before being
+/// hashed together so that the proof does not need to carry the position of the nodes.
+pub fn verify_allowlist_proof(
+    merkle_root: &str,
+    address: &Addr,
+    allocation: u32,
+    proof: &[String],
+) -> Result<(), ContractError> {
+    if proof.len() > MAX_PROOF_LENGTH {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+
+    let root = decode_hash(merkle_root).map_err(|_| ContractError::InvalidMerkleRoot {})?;
+
+    let leaf: [u8; 32] = Sha256::digest(format!("{address}{allocation}").as_bytes()).into();
+    let computed = proof.iter().try_fold(leaf, |node, sibling| {
+        let sibling = decode_hash(sibling)?;
+        let (first, second) = if node <= sibling {
+            (node, sibling)
+        } else {
+            (sibling, node)
+        };
+        Ok::<_, ContractError>(
+            Sha256::new()
+                .chain_update(first)
+                .chain_update(second)
+                .finalize()
+                .into(),
+        )
+    })?;
+
+    if computed != root {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+
+    Ok(())
+}
+
+fn decode_hash(hash: &str) -> Result<[u8; 32], ContractError> {
+    let bytes = hex::decode(hash).map_err(|_| ContractError::InvalidMerkleProof {})?;
+    bytes
+        .try_into()
+        .map_err(|_| ContractError::InvalidMerkleProof {})
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit tests
+// -------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hash_leaf(address: &str, allocation: u32) -> [u8; 32] {
+        Sha256::digest(format!("{address}{allocation}").as_bytes()).into()
+    }
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        Sha256::new()
+            .chain_update(first)
+            .chain_update(second)
+            .finalize()
+            .into()
+    }
+
+    #[test]
+    fn validate_merkle_root_works() {
+        let root = hex::encode(hash_leaf("alice", 1));
+        validate_merkle_root(&root).unwrap();
+
+        let err = validate_merkle_root("not_hex").unwrap_err();
+        assert_eq!(err, ContractError::InvalidMerkleRoot {});
+
+        let err = validate_merkle_root(&root[..62]).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidMerkleRoot {},
+            "expected to fail since root is not 32 bytes long"
+        );
+    }
+
+    #[test]
+    fn verify_allowlist_proof_works() {
+        let alice = hash_leaf("alice", 2);
+        let bob = hash_leaf("bob", 5);
+        let root = hex::encode(hash_pair(alice, bob));
+
+        verify_allowlist_proof(&root, &Addr::unchecked("alice"), 2, &[hex::encode(bob)]).unwrap();
+        verify_allowlist_proof(&root, &Addr::unchecked("bob"), 5, &[hex::encode(alice)]).unwrap();
+
+        {
+            let err =
+                verify_allowlist_proof(&root, &Addr::unchecked("alice"), 5, &[hex::encode(bob)])
+                    .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidMerkleProof {},
+                "expected to fail since the allocation is not the one in the tree"
+            );
+        }
+
+        {
+            let err =
+                verify_allowlist_proof(&root, &Addr::unchecked("carol"), 2, &[hex::encode(bob)])
+                    .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidMerkleProof {},
+                "expected to fail since the address is not in the tree"
+            );
+        }
+
+        {
+            let err =
+                verify_allowlist_proof(&root, &Addr::unchecked("alice"), 2, &["zz".to_string()])
+                    .unwrap_err();
+            assert_eq!(err, ContractError::InvalidMerkleProof {});
+        }
+    }
+}
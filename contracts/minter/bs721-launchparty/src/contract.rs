@@ -1,23 +1,53 @@
+use crate::allowlist::{validate_merkle_root, verify_allowlist_proof};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MaxPerAddressResponse, PartyType, QueryMsg};
-use crate::state::{Config, EditionMetadata, Trait, ADDRESS_TOKENS, CONFIG};
+use crate::msg::{
+    AllowlistMintedResponse, AllowlistPhaseResponse, AllowlistPhasesResponse, AllowlistProof,
+    CrowdfundResponse, CurrentStageResponse, DutchAuction, DutchAuctionResponse, EditionTier,
+    ExecuteMsg, GateClaimResponse, GateToken, InstantiateMsg, MaxPerAddressResponse,
+    MintLimitTarget, MintStage, MinterHandover, NftReceiveMsg, PartyType, QueryMsg,
+    RaffleEntryResponse, RaffleResponse, ReceiveMsg, RedeemAction, RedeemPendingResponse,
+    ReferralStatsResponse, RevealResponse, StageResponse, StatusResponse, TierAssignment,
+    TierResponse, TiersResponse, TokenTierResponse, TopReferrersResponse,
+};
+use crate::raffle::{chain_entry, draw_winners, initial_entries_hash, verify_beacon, won};
+use crate::shuffle::{draw_index, draw_weighted, mix_seed, seed_offset};
+use crate::state::{
+    referrals, BlockMints, Config, EditionMetadata, Escrow, MintActivity, RaffleState,
+    ReferralStats, RevealState, TokenPayment, Trait, ADDRESS_TOKENS, ALLOWLIST_MINTED,
+    ALLOWLIST_PHASES, AUCTION_PAYMENTS, AUCTION_REFERRALS, AUCTION_SALES, BLOCK_MINTS, CONFIG,
+    ESCROW, GATE_CLAIMS, MINT_ACTIVITY, RAFFLE, RAFFLE_ENTRIES, RAISED, REDEEM_PENDING,
+    REFERRAL_ESCROW, REVEAL, STAGES, STAGE_ADDRESS_TOKENS, STAGE_MINTED, TIERS, TOKEN_PAYMENTS,
+    TOKEN_TIERS,
+};
 
-use bs721::{CollectionInfo, RoyaltyInfoResponse};
+use bs721::{
+    Bs721QueryMsg, Bs721ReceiveMsg, CollectionInfo, DisplayType, NumTokensResponse,
+    OwnerOfResponse, RoyaltyInfoResponse, TokensResponse,
+};
 use bs721_base::{
     ExecuteMsg as Bs721BaseExecuteMsg, InstantiateMsg as Bs721BaseInstantiateMsg, MintMsg,
 };
+use bs_std::fees::{split_fees, validate_fee_split, AddressFee, FeeAmounts, MAX_BPS};
+
+use bs_mint_hooks::{
+    handle_reply,
+    post::{add_postmint_hook, prepare_postmint_hooks, query_postmint_hooks, remove_postmint_hook},
+    pre::{add_premint_hook, prepare_premint_hooks, query_premint_hooks, remove_premint_hook},
+};
 
 use cosmos_sdk_proto::{cosmos::distribution::v1beta1::MsgFundCommunityPool, traits::Message};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coin, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut,
-    Empty, Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Timestamp,
-    Uint128, WasmMsg,
+    attr, coin, from_json, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Deps,
+    DepsMut, Empty, Env, MessageInfo, Order, Reply, ReplyOn, Response, StdError, StdResult,
+    Storage, SubMsg, Timestamp, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
-use cw_utils::{may_pay, parse_reply_instantiate_data};
+use cw_storage_plus::Bound;
+use cw_utils::{may_pay, nonpayable, parse_reply_instantiate_data};
 
 const CONTRACT_NAME: &str = "crates.io:bs721-launchparty";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -27,6 +57,19 @@ const INSTANTIATE_TOKEN_REPLY_ID: u64 = 1;
 /// Maximum tokens that can be minted in both cases of the `PartyType`.
 // TODO: investigate how this can be removed by adding metadata to NFTs.
 const OVERAL_MAXIMUM_MINTABLE: u32 = 10_000;
+/// Default and maximum number of items returned by paginated queries.
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+/// Number of editions updated by each reveal batch.
+const REVEAL_BATCH_SIZE: u32 = 50;
+/// Seconds after the end of the raffle entries within which the beacon must be submitted,
+/// otherwise every ticket is refunded.
+const RAFFLE_DRAW_TIMEOUT: u64 = 7 * 24 * 60 * 60;
+/// Seconds after the draw timeout within which the raffle winners can claim their editions
+/// before the launchparty can be finalized.
+const RAFFLE_CLAIM_WINDOW: u64 = 30 * 24 * 60 * 60;
+/// Number of winning raffle tickets drawn by each draw batch.
+const RAFFLE_DRAW_BATCH_SIZE: u32 = 100;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -38,6 +81,7 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     msg.validate(env.clone())?;
+    validate_fee_split(deps.api, &msg.fee_split)?;
 
     let start_time = if msg.start_time < env.block.time {
         env.block.time
@@ -54,24 +98,120 @@ pub fn instantiate(
 
     let bs721_admin = deps.api.addr_validate(msg.bs721_admin.as_str())?;
 
+    let cw20_address = msg
+        .cw20_address
+        .map(|address| deps.api.addr_validate(address.as_str()))
+        .transpose()?;
+    if let Some(cw20_address) = &cw20_address {
+        if msg.price.denom != cw20_address.as_str() {
+            return Err(ContractError::InvalidCw20Denom {});
+        }
+    }
+
+    // cw20 protocol fees cannot be sent to the community pool, so they are paid to the admin
+    // picked by the factory. An admin picked by any other instantiator is not trusted.
+    let protocol_address = match &cw20_address {
+        Some(_) if deps.querier.query_wasm_contract_info(&info.sender).is_ok() => {
+            Some(bs721_admin.clone())
+        }
+        Some(_) => return Err(ContractError::Cw20RequiresFactory {}),
+        None => None,
+    };
+
+    if let Some(burn_to_mint) = &msg.burn_to_mint {
+        deps.api.addr_validate(&burn_to_mint.collection)?;
+    }
+    if let Some(token_gate) = &msg.token_gate {
+        for collection in &token_gate.collections {
+            deps.api.addr_validate(collection)?;
+        }
+    }
+
+    let creator = msg
+        .creator
+        .map(|address| deps.api.addr_validate(address.as_str()))
+        .transpose()?
+        .unwrap_or(info.sender);
+
     let config = Config {
-        creator: info.sender,
+        creator,
         symbol: msg.symbol.clone(),
         name: msg.name.clone(),
         uri: msg.uri.clone(),
-        price: msg.price,
+        content_hash: msg.content_hash.clone(),
+        price: msg.price.clone(),
         max_per_address: msg.max_per_address,
+        limit_target: msg.limit_target,
+        mint_limits: msg.mint_limits,
+        reserved_supply: msg.reserved_supply,
+        dutch_auction: msg.dutch_auction,
+        burn_to_mint: msg.burn_to_mint,
+        raffle: msg.raffle,
+        token_gate: msg.token_gate,
+        reveal: msg.reveal,
+        minter_handover: msg.minter_handover,
+        final_supply: None,
         bs721_address: None,
         next_token_id: 1, // first token ID is 1
         payment_address,
         seller_fee_bps: msg.seller_fee_bps,
-        referral_fee_bps: msg.referral_fee_bps,
+        fee_split: msg.fee_split,
+        accrue_referrals: msg.accrue_referrals,
         start_time,
         party_type: msg.party_type,
-        protocol_fee_bps: msg.protocol_fee_bps,
+        paused: false,
+        ended: false,
+        randomized: msg.randomized,
+        min_raise: msg.min_raise,
+        cw20_address,
+        protocol_address,
     };
 
     CONFIG.save(deps.storage, &config)?;
+    ESCROW.save(deps.storage, &Escrow::default())?;
+    RAISED.save(deps.storage, &Uint128::zero())?;
+
+    // every edition not reserved is won in the raffle.
+    if config.raffle.is_some() {
+        let editions = config.party_type.max_editions().unwrap() - config.reserved_supply;
+        RAFFLE.save(
+            deps.storage,
+            &RaffleState {
+                tickets: 0,
+                editions,
+                entries_hash: hex::encode(initial_entries_hash(&env.contract.address)),
+                beacon: None,
+                seed: None,
+                drawn: 0,
+                claimed: 0,
+            },
+        )?;
+    }
+
+    for (phase_id, phase) in msg.allowlist_phases.iter().enumerate() {
+        ALLOWLIST_PHASES.save(deps.storage, phase_id as u32, phase)?;
+    }
+
+    // without explicit stages the public mint is a single open ended stage.
+    let stages = if msg.stages.is_empty() {
+        vec![MintStage {
+            name: "public".to_string(),
+            start_time,
+            end_time: None,
+            price: msg.price,
+            max_per_address: None,
+            max_supply: None,
+        }]
+    } else {
+        msg.stages
+    };
+    for (stage_id, stage) in stages.iter().enumerate() {
+        STAGES.save(deps.storage, stage_id as u32, stage)?;
+    }
+
+    for (tier_id, tier) in msg.tiers.iter().enumerate() {
+        TIERS.save(deps.storage, tier_id as u32, tier)?;
+    }
 
     // create submessages to instantiate nft
     let sub_msgs: Vec<SubMsg> = vec![SubMsg {
@@ -99,6 +239,9 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    // mint hooks reply only on error, reverting the mint.
+    handle_reply(reply.id)?;
+
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     let mut res = Response::new();
@@ -135,45 +278,242 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Mint { amount, referral } => {
-            // check if referral address is valid
+        ExecuteMsg::Mint {
+            amount,
+            referral,
+            allowlist,
+            recipient,
+            gate_tokens,
+        } => {
+            // check if referral and recipient addresses are valid
             let referral = referral
                 .map(|address| deps.api.addr_validate(address.as_str()))
                 .transpose()?;
-            execute_mint(deps, env, info, amount, referral)
+            let recipient = recipient
+                .map(|address| deps.api.addr_validate(address.as_str()))
+                .transpose()?;
+
+            // when the price is in a cw20 token native coins are not accepted, and only free
+            // mints can be executed without the `Receive` entry point.
+            let config: Config = CONFIG.load(deps.storage)?;
+            let sent_amount = if config.cw20_address.is_some() {
+                nonpayable(&info)?;
+                Uint128::zero()
+            } else {
+                may_pay(&info, &config.price.denom)?
+            };
+
+            execute_mint(
+                deps,
+                env,
+                info.sender,
+                sent_amount,
+                amount,
+                referral,
+                recipient,
+                allowlist,
+                gate_tokens,
+            )
+        }
+        ExecuteMsg::AirdropMint { recipients } => execute_airdrop_mint(deps, env, info, recipients),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::UpdateMerkleRoot {
+            phase_id,
+            merkle_root,
+        } => execute_update_merkle_root(deps, env, info, phase_id, merkle_root),
+        ExecuteMsg::Pause {} => execute_set_paused(deps, info, true),
+        ExecuteMsg::Resume {} => execute_set_paused(deps, info, false),
+        ExecuteMsg::UpdatePrice { price, stage_id } => {
+            execute_update_price(deps, env, info, price, stage_id)
+        }
+        ExecuteMsg::UpdateMaxPerAddress { max_per_address } => {
+            execute_update_max_per_address(deps, info, max_per_address)
+        }
+        ExecuteMsg::UpdateStartTime { start_time } => {
+            execute_update_start_time(deps, env, info, start_time)
+        }
+        ExecuteMsg::EndParty {} => execute_end_party(deps, env, info),
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Release {} => execute_release(deps, env),
+        ExecuteMsg::ClaimReferral {} => execute_claim_referral(deps, info),
+        ExecuteMsg::ClaimRebate {} => execute_claim_rebate(deps, env, info),
+        ExecuteMsg::SettleAuction {} => execute_settle_auction(deps, env),
+        ExecuteMsg::CancelRedeem {} => execute_cancel_redeem(deps, info),
+        ExecuteMsg::Reveal { base_uri } => execute_reveal(deps, env, info, base_uri),
+        ExecuteMsg::RevealBatch {} => execute_reveal_batch(deps),
+        ExecuteMsg::Finalize {} => execute_finalize(deps, env, info),
+        ExecuteMsg::EnterRaffle { tickets } => {
+            let config: Config = CONFIG.load(deps.storage)?;
+            let sent_amount = if config.cw20_address.is_some() {
+                nonpayable(&info)?;
+                Uint128::zero()
+            } else {
+                may_pay(&info, &config.price.denom)?
+            };
+            execute_enter_raffle(deps, env, info.sender, sent_amount, tickets)
+        }
+        ExecuteMsg::SubmitBeacon { proof } => execute_submit_beacon(deps, env, info, proof),
+        ExecuteMsg::DrawRaffle {} => execute_draw_raffle(deps, info),
+        ExecuteMsg::ClaimRaffle {} => execute_claim_raffle(deps, env, info),
+        ExecuteMsg::AddPreMintHook { hook } => {
+            only_creator(&CONFIG.load(deps.storage)?, &info.sender)?;
+            Ok(add_premint_hook(deps, hook)?)
+        }
+        ExecuteMsg::AddPostMintHook { hook } => {
+            only_creator(&CONFIG.load(deps.storage)?, &info.sender)?;
+            Ok(add_postmint_hook(deps, hook)?)
+        }
+        ExecuteMsg::RemovePreMintHook { hook } => {
+            only_creator(&CONFIG.load(deps.storage)?, &info.sender)?;
+            Ok(remove_premint_hook(deps, hook)?)
+        }
+        ExecuteMsg::RemovePostMintHook { hook } => {
+            only_creator(&CONFIG.load(deps.storage)?, &info.sender)?;
+            Ok(remove_postmint_hook(deps, hook)?)
         }
     }
 }
 
-fn execute_mint(
+/// Handles the cw20 tokens sent to the launchparty. Only the cw20 token defined in the `Config`
+/// is accepted.
+fn execute_receive(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.cw20_address != Some(info.sender) {
+        return Err(ContractError::InvalidCw20Token {});
+    }
+
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Mint {
+            amount,
+            referral,
+            allowlist,
+            recipient,
+            gate_tokens,
+        } => {
+            let referral = referral
+                .map(|address| deps.api.addr_validate(address.as_str()))
+                .transpose()?;
+            let recipient = recipient
+                .map(|address| deps.api.addr_validate(address.as_str()))
+                .transpose()?;
+            execute_mint(
+                deps,
+                env,
+                sender,
+                wrapper.amount,
+                amount,
+                referral,
+                recipient,
+                allowlist,
+                gate_tokens,
+            )
+        }
+        ReceiveMsg::EnterRaffle { tickets } => {
+            execute_enter_raffle(deps, env, sender, wrapper.amount, tickets)
+        }
+    }
+}
+
+/// Mints `amount` tokens to `recipient`, or to `sender` if not provided. The `sender` paid
+/// `sent_amount` of the price denom.
+#[allow(clippy::too_many_arguments)]
+fn execute_mint(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    sent_amount: Uint128,
     amount: u32,
     referral: Option<Addr>,
+    recipient: Option<Addr>,
+    allowlist: Option<AllowlistProof>,
+    gate_tokens: Option<Vec<GateToken>>,
 ) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
+    if config.burn_to_mint.is_some() {
+        return Err(ContractError::BurnToMintOnly {});
+    }
+    if config.raffle.is_some() {
+        return Err(ContractError::RaffleOnly {});
+    }
     let accepted_denom = config.price.denom.clone();
 
-    before_mint_checks(&env, &config, amount)?;
+    let mut res = Response::new();
+
+    // a dutch auction gives the price of the public mint only.
+    let auction = match allowlist {
+        Some(_) => None,
+        None => config.dutch_auction.clone(),
+    };
 
-    let already_minted = (ADDRESS_TOKENS.key(&info.sender).may_load(deps.storage)?).unwrap_or(0);
-    let new_total_mint = already_minted.checked_add(amount).unwrap_or(1);
+    // the per address limits, allowlist included, are applied to the payer or to the recipient
+    // as defined at instantiation.
+    let owner = recipient.unwrap_or_else(|| sender.clone());
+    let limited = match config.limit_target {
+        MintLimitTarget::Payer => sender.clone(),
+        MintLimitTarget::Recipient => owner.clone(),
+    };
+    if owner != sender {
+        res = res.add_attribute("payer", sender.to_string());
+    }
 
-    if let Some(max_per_address) = config.max_per_address {
-        if new_total_mint > max_per_address {
-            return Err(ContractError::MaxPerAddressExceeded {
-                remaining: max_per_address.checked_sub(already_minted).unwrap_or(0),
-            });
+    mint_limits_checks(deps.storage, &env, &config, &limited, amount)?;
+
+    // tokens minted during an allowlist phase are not counted in the public maximum per address.
+    let unit_price = match allowlist {
+        Some(proof) => {
+            if gate_tokens.is_some() {
+                return Err(ContractError::GateWithAllowlist {});
+            }
+            res = res.add_attribute("allowlist_phase", proof.phase_id.to_string());
+            allowlist_mint_checks(deps.storage, &env, &limited, &config, amount, proof)?
         }
-    }
+        None => {
+            before_mint_checks(&env, &config, amount)?;
+
+            let already_minted =
+                (ADDRESS_TOKENS.key(&limited).may_load(deps.storage)?).unwrap_or(0);
+            let new_total_mint = already_minted.checked_add(amount).unwrap_or(1);
+
+            if let Some(max_per_address) = config.max_per_address {
+                if new_total_mint > max_per_address {
+                    return Err(ContractError::MaxPerAddressExceeded {
+                        remaining: max_per_address.saturating_sub(already_minted),
+                    });
+                }
+            }
+
+            ADDRESS_TOKENS.save(deps.storage, &limited, &new_total_mint)?;
+
+            let (stage_id, stage) = current_stage(deps.storage, env.block.time)?
+                .ok_or(ContractError::NoActiveStage {})?;
+            res = res.add_attribute("stage", stage.name.clone());
+            let stage_price = stage_mint_checks(deps.storage, &limited, stage_id, &stage, amount)?;
+            let price = match &auction {
+                Some(auction) => auction.price_at(env.block.time),
+                None => stage_price,
+            };
+
+            // editions minted with a gate token are discounted.
+            if gate_mint_checks(&mut deps, &config, &sender, amount, gate_tokens)? {
+                res = res.add_attribute("gated", "true");
+                let discount_bps = config.token_gate.as_ref().unwrap().discount_bps;
+                price.multiply_ratio(MAX_BPS - discount_bps, MAX_BPS)
+            } else {
+                price
+            }
+        }
+    };
 
     // check that the user has sent exactly the required amount. The amount is given by the price of
     // a single token times the number of tokens to mint.
-    let sent_amount = may_pay(&info, &accepted_denom)?;
-    let required_amount = config
-        .price
-        .amount
+    let required_amount = unit_price
         .checked_mul(Uint128::from(amount))
         .map_err(StdError::overflow)?;
     if sent_amount != required_amount {
@@ -183,213 +523,1957 @@ fn execute_mint(
         ));
     }
 
-    let mut res = Response::new();
-
     // create minting message
+    let mut token_ids: Vec<u32> = Vec::with_capacity(amount as usize);
     for _ in 0..amount {
-        let token_id = config.next_token_id;
-
-        let mut attributes: Vec<Trait> = vec![Trait {
-            trait_type: "Edition".to_string(),
-            value: token_id.to_string(),
-            display_type: Some("number".to_string()),
-        }];
-
-        if let PartyType::MaxEdition(number) = config.party_type {
-            attributes.push(Trait {
-                trait_type: "Max Editions".to_string(),
-                value: number.to_string(),
-                display_type: Some("number".to_string()),
-            });
-            attributes.push(Trait {
-                trait_type: "Edition Type".to_string(),
-                value: "Limited Edition".to_string(),
-                display_type: None,
-            });
-        } else {
-            attributes.push(Trait {
-                trait_type: "Edition Type".to_string(),
-                value: "Open Edition".to_string(),
-                display_type: None,
-            });
-        }
-
-        let mint_msg =
-            Bs721BaseExecuteMsg::<EditionMetadata, Empty>::Mint(MintMsg::<EditionMetadata> {
-                owner: info.sender.to_string(),
-                token_id: token_id.to_string(),
-                token_uri: Some(config.uri.clone()),
-                extension: EditionMetadata {
-                    name: format!("{} #{}", config.name, token_id),
-                    attributes: Some(attributes),
-                },
-                payment_addr: Some(config.payment_address.clone().to_string()),
-                seller_fee_bps: Some(config.seller_fee_bps),
-            });
-
-        let msg = WasmMsg::Execute {
-            contract_addr: config.bs721_address.clone().unwrap().to_string(),
-            msg: to_json_binary(&mint_msg)?,
-            funds: vec![],
-        };
+        let (token_id, msgs) = hooked_mint_msgs(&mut deps, &env, &config, &sender, &owner)?;
 
         res = res
-            .add_message(msg)
+            .add_submessages(msgs)
             .add_attribute("token_id", token_id.to_string());
+        token_ids.push(token_id);
 
         config.next_token_id += 1;
         CONFIG.save(deps.storage, &config)?;
     }
 
-    // create  royalties and optionally referral messages
+    RAISED.update(deps.storage, |raised| -> StdResult<_> {
+        Ok(raised.checked_add(required_amount)?)
+    })?;
 
-    // if token price is not zero we have to send:
-    // - referral bps * price to referred address.
-    // - price - (referral bps * price) to royalties address
-    if !config.price.amount.is_zero() {
-        let (referral_amount, royalties_amount, protocol_amount) =
-            compute_referral_and_royalties_amounts(&config, &referral, required_amount)?;
+    // with a rebate, the amount paid over the floor price is held until the party is over.
+    let held_amount = match &auction {
+        Some(auction) => record_auction_sale(
+            deps.storage,
+            auction,
+            &sender,
+            &referral,
+            unit_price,
+            amount,
+        )?,
+        None => Uint128::zero(),
+    };
+    let payout_amount = required_amount - held_amount;
+
+    // create  royalties and optionally referral messages
 
-        let mut bank_msgs: Vec<BankMsg> = vec![];
+    // if token price is not zero the price is split among the recipients of the fee split. Without
+    // a referral its share is sent to the royalties address.
+    // during a crowdfund the same amounts are escrowed until the end of the party.
+    let mut accrued_amount = Uint128::zero();
+    let referral_amount = if !required_amount.is_zero() && config.min_raise.is_some() {
+        let referral_amount =
+            escrow_payments(deps.storage, &config, &referral, unit_price, &token_ids)?;
+        res = res.add_attribute("escrowed", required_amount);
+        referral_amount
+    } else if !payout_amount.is_zero() {
+        let fees = compute_referral_and_royalties_amounts(&config, &referral, payout_amount)?;
+        let referral_amount = fees.referral;
+
+        let mut payment_msgs: Vec<CosmosMsg> = address_fee_msgs(&config, &fees.addresses)?;
         let mut attributes: Vec<Attribute> = vec![];
 
         if !referral_amount.is_zero() {
-            bank_msgs.push(BankMsg::Send {
-                to_address: referral.clone().unwrap().to_string(),
-                amount: vec![coin(referral_amount.u128(), accepted_denom.clone())],
-            });
+            // accrued fees are kept in the contract until claimed.
+            if config.accrue_referrals {
+                accrued_amount = referral_amount;
+            } else {
+                payment_msgs.push(payment_msg(
+                    &config,
+                    referral.as_ref().unwrap(),
+                    referral_amount,
+                )?);
+            }
 
-            attributes.push(attr("referral", referral.unwrap().to_string()));
+            attributes.push(attr("referral", referral.as_ref().unwrap().to_string()));
             attributes.push(attr(
                 "amount",
                 coin(referral_amount.u128(), accepted_denom.clone()).to_string(),
             ));
         }
 
-        if protocol_amount > Uint128::zero() {
-            res = res.add_submessage(fund_community_pool_msg(
-                env,
-                coin(protocol_amount.u128(), accepted_denom.clone()),
-            ));
+        if fees.protocol > Uint128::zero() {
+            res = res.add_submessage(protocol_fee_msg(env, &config, fees.protocol)?);
+        }
+
+        attributes.push(attr("protocol_fee", fees.protocol.u128().to_string()));
+
+        payment_msgs.push(payment_msg(
+            &config,
+            &config.payment_address,
+            fees.royalties,
+        )?);
+
+        attributes.push(attr("royalties", fees.royalties.u128().to_string()));
+
+        res = res.add_messages(payment_msgs).add_attributes(attributes);
+        referral_amount
+    } else {
+        Uint128::zero()
+    };
+
+    if let Some(referral) = &referral {
+        record_referral(
+            deps.storage,
+            referral,
+            token_ids.len() as u32,
+            referral_amount,
+            accrued_amount,
+        )?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(res
+        .add_attribute("action", "mint_launchparty_nft")
+        .add_attribute("price", unit_price)
+        .add_attribute("creator", config.creator.to_string())
+        .add_attribute("recipient", owner.to_string()))
+}
+
+/// Builds the message minting the next token of the collection to `owner`. With randomized
+/// assignment the drawn index, and the tier drawn by weight, depend also on the `sender` of the
+/// mint.
+fn mint_token_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    sender: &Addr,
+    owner: &Addr,
+) -> Result<(u32, WasmMsg), ContractError> {
+    // with randomized assignment the token ID is a random index of the collection, each one
+    // with its own metadata file.
+    let (token_id, token_uri) = match config.party_type.max_editions() {
+        Some(number) if config.randomized => {
+            let index = draw_index(storage, env, sender, number, config.next_token_id - 1)?;
+            let base = config.uri.trim_end_matches('/');
+            (index, format!("{base}/{index}.json"))
         }
+        _ => (config.next_token_id, config.uri.clone()),
+    };
+
+    let tier = assign_tier(storage, env, sender, token_id)?;
+    let token_uri = tier
+        .as_ref()
+        .and_then(|tier| tier.uri.clone())
+        .unwrap_or(token_uri);
+
+    // with a delayed reveal editions get the placeholder until revealed, and their final URI
+    // when minted after the reveal, e.g. with an airdrop.
+    let token_uri = match (&config.reveal, REVEAL.may_load(storage)?) {
+        (_, Some(reveal)) => reveal.token_uri(token_id),
+        (Some(delayed), None) => delayed.placeholder_uri.clone(),
+        (None, None) => token_uri,
+    };
+
+    let mut attributes: Vec<Trait> = vec![Trait {
+        trait_type: "Edition".to_string(),
+        value: token_id.to_string(),
+        display_type: Some(DisplayType::Number),
+    }];
+
+    if let Some(number) = config.party_type.max_editions() {
+        attributes.push(Trait {
+            trait_type: "Max Editions".to_string(),
+            value: number.to_string(),
+            display_type: Some(DisplayType::Number),
+        });
+        attributes.push(Trait {
+            trait_type: "Edition Type".to_string(),
+            value: "Limited Edition".to_string(),
+            display_type: None,
+        });
+    } else {
+        attributes.push(Trait {
+            trait_type: "Edition Type".to_string(),
+            value: "Open Edition".to_string(),
+            display_type: None,
+        });
+    }
+
+    if let Some(end_time) = config.party_type.end_time(config.start_time) {
+        attributes.push(Trait {
+            trait_type: "Mint End".to_string(),
+            value: end_time.seconds().to_string(),
+            display_type: Some(DisplayType::Date),
+        });
+    }
 
-        attributes.push(attr("protocol_fee", protocol_amount.u128().to_string()));
+    if let Some(tier) = tier {
+        attributes.push(Trait {
+            trait_type: "Tier".to_string(),
+            value: tier.name,
+            display_type: None,
+        });
+        attributes.extend(tier.attributes);
+    }
 
-        bank_msgs.push(BankMsg::Send {
-            to_address: config.payment_address.clone().to_string(),
-            amount: vec![coin(royalties_amount.u128(), accepted_denom.clone())],
+    let mint_msg =
+        Bs721BaseExecuteMsg::<EditionMetadata, Empty>::Mint(MintMsg::<EditionMetadata> {
+            owner: owner.to_string(),
+            token_id: token_id.to_string(),
+            token_uri: Some(token_uri),
+            content_hash: config.content_hash.clone(),
+            extension: EditionMetadata {
+                name: format!("{} #{}", config.name, token_id),
+                attributes: Some(attributes),
+            },
+            payment_addr: Some(config.payment_address.clone().to_string()),
+            seller_fee_bps: Some(config.seller_fee_bps),
         });
 
-        attributes.push(attr("royalties", royalties_amount.u128().to_string()));
+    Ok((
+        token_id,
+        WasmMsg::Execute {
+            contract_addr: config.bs721_address.clone().unwrap().to_string(),
+            msg: to_json_binary(&mint_msg)?,
+            funds: vec![],
+        },
+    ))
+}
+
+/// Assigns a tier to the edition `token_id`: the tier whose range contains it, otherwise one of
+/// the weighted tiers drawn at random. Returns None if the edition belongs to no tier.
+fn assign_tier(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    token_id: u32,
+) -> StdResult<Option<EditionTier>> {
+    let tiers = TIERS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let tier_id = match tiers.iter().find(|(_, tier)| tier.contains(token_id)) {
+        Some((tier_id, _)) => *tier_id,
+        None => {
+            let weighted: Vec<(u32, u32)> = tiers
+                .iter()
+                .filter_map(|(tier_id, tier)| match tier.assignment {
+                    TierAssignment::Weight(weight) => Some((*tier_id, weight)),
+                    TierAssignment::Range { .. } => None,
+                })
+                .collect();
+            if weighted.is_empty() {
+                return Ok(None);
+            }
+
+            let weights: Vec<u32> = weighted.iter().map(|(_, weight)| *weight).collect();
+            weighted[draw_weighted(storage, env, sender, &weights)?].0
+        }
+    };
+
+    TOKEN_TIERS.save(storage, token_id, &tier_id)?;
+    Ok(tiers
+        .into_iter()
+        .nth(tier_id as usize)
+        .map(|(_, tier)| tier))
+}
+
+/// Builds the messages minting the next token of the collection to `owner`, wrapped by the mint
+/// hooks. A failing pre-mint hook reverts the whole mint.
+fn hooked_mint_msgs(
+    deps: &mut DepsMut,
+    env: &Env,
+    config: &Config,
+    sender: &Addr,
+    owner: &Addr,
+) -> Result<(u32, Vec<SubMsg>), ContractError> {
+    ensure_random_draw_sender(deps.as_ref(), config, sender)?;
+    // the random offset of the reveal is derived from the seed left by the last mint.
+    if config
+        .reveal
+        .as_ref()
+        .is_some_and(|reveal| reveal.random_offset)
+    {
+        mix_seed(deps.storage, env, sender)?;
+    }
+    let (token_id, msg) = mint_token_msg(deps.storage, env, config, sender, owner)?;
+
+    let collection = config.bs721_address.clone().unwrap();
+    let mut msgs = prepare_premint_hooks(
+        deps.as_ref(),
+        collection.clone(),
+        Some(token_id.to_string()),
+        sender.to_string(),
+    )?;
+    msgs.push(SubMsg::new(msg));
+    msgs.extend(prepare_postmint_hooks(
+        deps.as_ref(),
+        collection,
+        Some(token_id.to_string()),
+        sender.to_string(),
+    )?);
+
+    Ok((token_id, msgs))
+}
+
+/// Rejects contract senders when minted editions are drawn at random, by a randomized party or by
+/// weighted tiers. The draw is known once the mint is executed, so a contract could inspect the
+/// edition it got and revert the transaction until the draw is favourable.
+fn ensure_random_draw_sender(
+    deps: Deps,
+    config: &Config,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    let mut random = config.randomized;
+    for tier in TIERS.range(deps.storage, None, None, Order::Ascending) {
+        random |= matches!(tier?.1.assignment, TierAssignment::Weight(_));
+    }
+
+    if random && deps.querier.query_wasm_contract_info(sender).is_ok() {
+        return Err(ContractError::ContractRandomMint {});
+    }
+
+    Ok(())
+}
+
+/// Records the tokens sold during a dutch auction at `unit_price`, returning the amount held for
+/// the rebate of the buyer. The `referral` of the sale earns its fees on the held amount once the
+/// auction is settled.
+fn record_auction_sale(
+    storage: &mut dyn Storage,
+    auction: &DutchAuction,
+    buyer: &Addr,
+    referral: &Option<Addr>,
+    unit_price: Uint128,
+    amount: u32,
+) -> StdResult<Uint128> {
+    let mut sales = AUCTION_SALES.may_load(storage)?.unwrap_or_default();
+    sales.clearing_price = Some(unit_price);
+    sales.sold += amount;
+    if auction.rebate && referral.is_some() {
+        sales.referred += amount;
+    }
+    AUCTION_SALES.save(storage, &sales)?;
+
+    if !auction.rebate {
+        return Ok(Uint128::zero());
+    }
+
+    if let Some(referral) = referral {
+        AUCTION_REFERRALS.update(storage, referral, |tokens| -> StdResult<_> {
+            Ok(tokens.unwrap_or_default() + amount)
+        })?;
+    }
+
+    let excess = (unit_price - auction.floor_price) * Uint128::from(amount);
+    AUCTION_PAYMENTS.update(storage, buyer, |payment| -> StdResult<_> {
+        let mut payment = payment.unwrap_or_default();
+        payment.minted += amount;
+        payment.excess += excess;
+        Ok(payment)
+    })?;
+
+    Ok(excess)
+}
+
+/// Escrows the payment of each token minted during a crowdfund, split as it will be released.
+fn escrow_payments(
+    storage: &mut dyn Storage,
+    config: &Config,
+    referral: &Option<Addr>,
+    unit_price: Uint128,
+    token_ids: &[u32],
+) -> Result<Uint128, ContractError> {
+    let fees = compute_referral_and_royalties_amounts(config, referral, unit_price)?;
+    let referral_amount = fees.referral;
+    let minted = Uint128::from(token_ids.len() as u128);
+
+    let mut escrow = ESCROW.load(storage)?;
+    escrow.raised += unit_price * minted;
+    escrow.royalties += fees.royalties * minted;
+    escrow.protocol += fees.protocol * minted;
+    for fee in &fees.addresses {
+        match escrow
+            .addresses
+            .iter_mut()
+            .find(|escrowed| escrowed.address == fee.address)
+        {
+            Some(escrowed) => escrowed.amount += fee.amount * minted,
+            None => escrow.addresses.push(AddressFee {
+                address: fee.address.clone(),
+                amount: fee.amount * minted,
+            }),
+        }
+    }
+    ESCROW.save(storage, &escrow)?;
+
+    if let Some(referral) = referral {
+        if !referral_amount.is_zero() {
+            REFERRAL_ESCROW.update(storage, referral, |escrowed| -> StdResult<_> {
+                Ok(escrowed.unwrap_or_default() + referral_amount * minted)
+            })?;
+        }
+    }
+
+    let payment = TokenPayment {
+        referral: referral.clone(),
+        referral_amount,
+        royalties_amount: fees.royalties,
+        protocol_amount: fees.protocol,
+        addresses: fees.addresses,
+    };
+    for token_id in token_ids {
+        TOKEN_PAYMENTS.save(storage, *token_id, &payment)?;
+    }
+
+    Ok(referral_amount * minted)
+}
+
+/// Adds a referred mint of `mints` tokens to the stats of the referral. The `accrued` amount is
+/// added to the fees the referral can claim.
+fn record_referral(
+    storage: &mut dyn Storage,
+    referral: &Addr,
+    mints: u32,
+    earned: Uint128,
+    accrued: Uint128,
+) -> StdResult<()> {
+    referrals().update(storage, referral, |stats| -> StdResult<_> {
+        let mut stats = stats.unwrap_or_default();
+        stats.mints += mints;
+        stats.earned += earned;
+        stats.claimable += accrued;
+        Ok(stats)
+    })?;
+    Ok(())
+}
+
+/// Handles the bs721 tokens sent to the launchparty. Only tokens of the launchparty collection
+/// can be refunded, and only tokens of the burn-to-mint collection can be redeemed.
+fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Bs721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let msg: NftReceiveMsg = from_json(&wrapper.msg)?;
+    let accepted = match &msg {
+        NftReceiveMsg::Refund {} => config.bs721_address.as_ref() == Some(&info.sender),
+        NftReceiveMsg::Redeem {} => config
+            .burn_to_mint
+            .as_ref()
+            .is_some_and(|burn_to_mint| burn_to_mint.collection == info.sender.as_str()),
+    };
+    if !accepted {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+
+    match msg {
+        NftReceiveMsg::Refund {} => execute_refund(deps, env, config, sender, wrapper.token_id),
+        NftReceiveMsg::Redeem {} => execute_redeem(deps, env, config, sender, wrapper.token_id),
+    }
+}
+
+/// Records a token received from `sender` for a burn-to-mint redemption. Once the sender has
+/// sent the number of tokens required by the ratio, the tokens are burned or held and an edition
+/// is minted to the sender.
+fn execute_redeem(
+    mut deps: DepsMut,
+    env: Env,
+    mut config: Config,
+    sender: Addr,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let burn_to_mint = config.burn_to_mint.clone().unwrap();
+    if !burn_to_mint.is_eligible(&token_id) {
+        return Err(ContractError::TokenNotEligible { token_id });
+    }
+
+    if config.bs721_address.is_none() {
+        return Err(ContractError::Bs721NotLinked {});
+    }
+    before_mint_checks(&env, &config, 1)?;
+
+    let mut pending = REDEEM_PENDING
+        .may_load(deps.storage, &sender)?
+        .unwrap_or_default();
+    pending.push(token_id.clone());
 
-        res = res.add_messages(bank_msgs).add_attributes(attributes)
+    let res = Response::new()
+        .add_attribute("action", "redeem")
+        .add_attribute("redeemed_token_id", token_id)
+        .add_attribute("sender", sender.to_string());
+
+    if (pending.len() as u32) < burn_to_mint.ratio {
+        REDEEM_PENDING.save(deps.storage, &sender, &pending)?;
+        return Ok(res.add_attribute("pending", pending.len().to_string()));
     }
+    REDEEM_PENDING.remove(deps.storage, &sender);
+
+    // held tokens stay in the launchparty.
+    let burn_msgs = match burn_to_mint.action {
+        RedeemAction::Burn => pending
+            .iter()
+            .map(|token_id| {
+                Ok(WasmMsg::Execute {
+                    contract_addr: burn_to_mint.collection.clone(),
+                    msg: to_json_binary(&Bs721BaseExecuteMsg::<Empty, Empty>::Burn {
+                        token_id: token_id.clone(),
+                    })?,
+                    funds: vec![],
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?,
+        RedeemAction::Hold => vec![],
+    };
 
+    let (minted_token_id, msgs) = hooked_mint_msgs(&mut deps, &env, &config, &sender, &sender)?;
+    config.next_token_id += 1;
     CONFIG.save(deps.storage, &config)?;
-    ADDRESS_TOKENS.save(deps.storage, &info.sender, &new_total_mint)?;
 
-    Ok(res
-        .add_attribute("action", "mint_launchparty_nft")
-        .add_attribute("price", config.price.amount)
-        .add_attribute("creator", config.creator.to_string())
-        .add_attribute("recipient", info.sender.to_string()))
-}
+    Ok(res
+        .add_messages(burn_msgs)
+        .add_submessages(msgs)
+        .add_attribute("token_id", minted_token_id.to_string())
+        .add_attribute("recipient", sender.to_string()))
+}
+
+/// Returns to the sender the tokens sent for a burn-to-mint redemption not yet completed.
+fn execute_cancel_redeem(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let burn_to_mint = config.burn_to_mint.ok_or(ContractError::NotBurnToMint {})?;
+
+    let pending = REDEEM_PENDING
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NoPendingTokens {})?;
+    REDEEM_PENDING.remove(deps.storage, &info.sender);
+
+    let transfer_msgs = pending
+        .iter()
+        .map(|token_id| {
+            Ok(WasmMsg::Execute {
+                contract_addr: burn_to_mint.collection.clone(),
+                msg: to_json_binary(&Bs721BaseExecuteMsg::<Empty, Empty>::TransferNft {
+                    recipient: info.sender.to_string(),
+                    token_id: token_id.clone(),
+                })?,
+                funds: vec![],
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(transfer_msgs)
+        .add_attribute("action", "cancel_redeem")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_ids", pending.join(",")))
+}
+
+/// Burns a token received from `sender` refunding the full amount paid for it. Allowed only when
+/// a crowdfund ended without reaching its minimum raise.
+fn execute_refund(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: Addr,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let min_raise = config.min_raise.ok_or(ContractError::NotCrowdfund {})?;
+    if !party_ended(&env, &config) {
+        return Err(ContractError::CrowdfundNotEnded {});
+    }
+
+    let mut escrow = ESCROW.load(deps.storage)?;
+    if escrow.raised >= min_raise {
+        return Err(ContractError::MinRaiseReached {});
+    }
+
+    let id = token_id
+        .parse::<u32>()
+        .map_err(|_| ContractError::NotRefundable {
+            token_id: token_id.clone(),
+        })?;
+    let payment =
+        TOKEN_PAYMENTS
+            .may_load(deps.storage, id)?
+            .ok_or(ContractError::NotRefundable {
+                token_id: token_id.clone(),
+            })?;
+
+    RAISED.update(deps.storage, |raised| -> StdResult<_> {
+        Ok(raised.checked_sub(payment.total())?)
+    })?;
+    escrow.raised -= payment.total();
+    escrow.royalties -= payment.royalties_amount;
+    escrow.protocol -= payment.protocol_amount;
+    for fee in &payment.addresses {
+        if let Some(escrowed) = escrow
+            .addresses
+            .iter_mut()
+            .find(|escrowed| escrowed.address == fee.address)
+        {
+            escrowed.amount -= fee.amount;
+        }
+    }
+    ESCROW.save(deps.storage, &escrow)?;
+
+    if let Some(referral) = &payment.referral {
+        if !payment.referral_amount.is_zero() {
+            REFERRAL_ESCROW.update(deps.storage, referral, |escrowed| -> StdResult<_> {
+                Ok(escrowed.unwrap_or_default() - payment.referral_amount)
+            })?;
+        }
+        referrals().update(deps.storage, referral, |stats| -> StdResult<_> {
+            let mut stats = stats.unwrap_or_default();
+            stats.mints -= 1;
+            stats.earned -= payment.referral_amount;
+            Ok(stats)
+        })?;
+    }
+    TOKEN_PAYMENTS.remove(deps.storage, id);
+
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: config.bs721_address.clone().unwrap().to_string(),
+        msg: to_json_binary(&Bs721BaseExecuteMsg::<EditionMetadata, Empty>::Burn {
+            token_id: token_id.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(burn_msg)
+        .add_message(payment_msg(&config, &sender, payment.total())?)
+        .add_attribute("action", "refund")
+        .add_attribute("token_id", token_id)
+        .add_attribute("recipient", sender.to_string())
+        .add_attribute("amount", payment.total()))
+}
+
+/// Releases the funds escrowed during a crowdfund that reached its minimum raise. Referral fees
+/// are claimed by each referral address with `ClaimReferral`.
+fn execute_release(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let min_raise = config.min_raise.ok_or(ContractError::NotCrowdfund {})?;
+    if !party_ended(&env, &config) {
+        return Err(ContractError::CrowdfundNotEnded {});
+    }
+
+    let mut escrow = ESCROW.load(deps.storage)?;
+    if escrow.released {
+        return Err(ContractError::AlreadyReleased {});
+    }
+    if escrow.raised < min_raise {
+        return Err(ContractError::MinRaiseNotReached {});
+    }
+
+    escrow.released = true;
+    ESCROW.save(deps.storage, &escrow)?;
+
+    let mut res = Response::new().add_messages(address_fee_msgs(&config, &escrow.addresses)?);
+    if !escrow.protocol.is_zero() {
+        res = res.add_submessage(protocol_fee_msg(env, &config, escrow.protocol)?);
+    }
+    if !escrow.royalties.is_zero() {
+        res = res.add_message(payment_msg(
+            &config,
+            &config.payment_address,
+            escrow.royalties,
+        )?);
+    }
+
+    Ok(res
+        .add_attribute("action", "release")
+        .add_attribute("raised", escrow.raised)
+        .add_attribute("royalties", escrow.royalties)
+        .add_attribute("protocol_fee", escrow.protocol))
+}
+
+/// Sends to the sender the accrued referral fees, the referral fees escrowed during a released
+/// crowdfund and the referral fees on the amount held by a settled dutch auction.
+fn execute_claim_referral(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let escrow = ESCROW.may_load(deps.storage)?.unwrap_or_default();
+    let escrowed = if escrow.released {
+        REFERRAL_ESCROW
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default()
+    } else {
+        Uint128::zero()
+    };
+    let settled = settled_auction_referral(deps.storage, &config, &info.sender)?;
+    let mut stats = referrals()
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+
+    let amount = stats.claimable + escrowed + settled;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    if !escrowed.is_zero() {
+        REFERRAL_ESCROW.remove(deps.storage, &info.sender);
+    }
+    if !settled.is_zero() {
+        AUCTION_REFERRALS.remove(deps.storage, &info.sender);
+        stats.earned += settled;
+    }
+    if !stats.claimable.is_zero() || !settled.is_zero() {
+        stats.claimable = Uint128::zero();
+        referrals().save(deps.storage, &info.sender, &stats)?;
+    }
+
+    Ok(Response::new()
+        .add_message(payment_msg(&config, &info.sender, amount)?)
+        .add_attribute("action", "claim_referral")
+        .add_attribute("referral", info.sender.to_string())
+        .add_attribute("amount", amount))
+}
+
+/// Returns the referral fees of `referral` on the amount held for the rebate of a settled dutch
+/// auction, at the clearing price.
+fn settled_auction_referral(
+    storage: &dyn Storage,
+    config: &Config,
+    referral: &Addr,
+) -> StdResult<Uint128> {
+    let sales = AUCTION_SALES.may_load(storage)?.unwrap_or_default();
+    let Some(auction) = config.dutch_auction.as_ref().filter(|_| sales.settled) else {
+        return Ok(Uint128::zero());
+    };
+    let tokens = AUCTION_REFERRALS
+        .may_load(storage, referral)?
+        .unwrap_or_default();
+
+    let clearing_excess = sales.clearing_price.unwrap_or(auction.floor_price) - auction.floor_price;
+    let amount = clearing_excess * Uint128::from(tokens);
+    if amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    Ok(split_fees(&config.fee_split, amount, true)?.referral)
+}
+
+/// Returns true if the party has been ended by the creator, is expired or is sold out.
+fn party_ended(env: &Env, config: &Config) -> bool {
+    config.ended
+        || !party_is_active(
+            env,
+            &config.party_type,
+            config.next_token_id + config.reserved_supply,
+            config.start_time,
+        )
+}
+
+/// Returns the dutch auction of a launchparty with rebate, once the party is over.
+fn ended_auction_rebate(env: &Env, config: &Config) -> Result<DutchAuction, ContractError> {
+    let auction = config
+        .dutch_auction
+        .clone()
+        .filter(|auction| auction.rebate)
+        .ok_or(ContractError::NotAuctionRebate {})?;
+    if !party_ended(env, config) {
+        return Err(ContractError::PartyNotEnded {});
+    }
+    Ok(auction)
+}
+
+/// Refunds to the sender the amount paid over the clearing price of the dutch auction.
+fn execute_claim_rebate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let auction = ended_auction_rebate(&env, &config)?;
+
+    let payment = AUCTION_PAYMENTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NothingToClaim {})?;
+    AUCTION_PAYMENTS.remove(deps.storage, &info.sender);
+
+    let sales = AUCTION_SALES.load(deps.storage)?;
+    let clearing_excess = sales.clearing_price.unwrap_or(auction.floor_price) - auction.floor_price;
+    let rebate = payment.excess - clearing_excess * Uint128::from(payment.minted);
+    if rebate.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    RAISED.update(deps.storage, |raised| -> StdResult<_> {
+        Ok(raised.checked_sub(rebate)?)
+    })?;
+
+    Ok(Response::new()
+        .add_message(payment_msg(&config, &info.sender, rebate)?)
+        .add_attribute("action", "claim_rebate")
+        .add_attribute("recipient", info.sender.to_string())
+        .add_attribute("amount", rebate))
+}
+
+/// Pays royalties and protocol fee on the amount held for the rebate and owed at the clearing
+/// price of the dutch auction. The referral fees on the tokens sold with a referral stay in the
+/// contract until claimed.
+fn execute_settle_auction(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let auction = ended_auction_rebate(&env, &config)?;
+
+    let mut sales = AUCTION_SALES.may_load(deps.storage)?.unwrap_or_default();
+    if sales.settled {
+        return Err(ContractError::AlreadySettled {});
+    }
+    sales.settled = true;
+    AUCTION_SALES.save(deps.storage, &sales)?;
+
+    let clearing_excess = sales.clearing_price.unwrap_or(auction.floor_price) - auction.floor_price;
+    let amount = clearing_excess * Uint128::from(sales.sold);
+    let referred_amount = clearing_excess * Uint128::from(sales.referred);
+
+    let mut res = Response::new();
+    let mut royalties = Uint128::zero();
+    let mut protocol_fee = Uint128::zero();
+    let mut referral_fees = Uint128::zero();
+    for (amount, referred) in [(amount - referred_amount, false), (referred_amount, true)] {
+        if amount.is_zero() {
+            continue;
+        }
+        let fees = split_fees(&config.fee_split, amount, referred)?;
+        if fees.royalties.is_zero() {
+            return Err(StdError::generic_err("royalties amount is zero or negative").into());
+        }
+        if !fees.protocol.is_zero() {
+            res = res.add_submessage(protocol_fee_msg(env.clone(), &config, fees.protocol)?);
+        }
+        res = res
+            .add_messages(address_fee_msgs(&config, &fees.addresses)?)
+            .add_message(payment_msg(
+                &config,
+                &config.payment_address,
+                fees.royalties,
+            )?);
+        royalties += fees.royalties;
+        protocol_fee += fees.protocol;
+        referral_fees += fees.referral;
+    }
+
+    Ok(res
+        .add_attribute("action", "settle_auction")
+        .add_attribute("amount", amount)
+        .add_attribute("royalties", royalties)
+        .add_attribute("protocol_fee", protocol_fee)
+        .add_attribute("referral_fees", referral_fees))
+}
+
+/// Enters `tickets` raffle tickets for `sender`, who paid `sent_amount` of the price denom. The
+/// tickets are numbered by their entry order and chained in the hash evaluated by the beacon.
+fn execute_enter_raffle(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    sent_amount: Uint128,
+    tickets: u32,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let raffle = config.raffle.clone().ok_or(ContractError::NotRaffle {})?;
+
+    if tickets == 0 {
+        return Err(ContractError::ZeroRaffleTickets {});
+    }
+    if config.paused {
+        return Err(ContractError::Paused {});
+    }
+    if config.start_time > env.block.time {
+        return Err(ContractError::NotStarted {});
+    }
+    if config.ended || raffle.entry_end_time <= env.block.time {
+        return Err(ContractError::RaffleEntriesClosed {});
+    }
+    ensure_random_draw_sender(deps.as_ref(), &config, &sender)?;
+
+    let mut entry = RAFFLE_ENTRIES
+        .may_load(deps.storage, &sender)?
+        .unwrap_or_default();
+    let entered = entry.tickets.len() as u32;
+    if entered + tickets > raffle.max_tickets_per_address {
+        return Err(ContractError::MaxTicketsExceeded {
+            remaining: raffle.max_tickets_per_address.saturating_sub(entered),
+        });
+    }
+
+    let required_amount = config
+        .price
+        .amount
+        .checked_mul(Uint128::from(tickets))
+        .map_err(StdError::overflow)?;
+    if sent_amount != required_amount {
+        return Err(ContractError::InvalidPaymentAmount(
+            sent_amount,
+            required_amount,
+        ));
+    }
+
+    let mut state = RAFFLE.load(deps.storage)?;
+    let previous =
+        hex::decode(&state.entries_hash).map_err(|err| StdError::generic_err(err.to_string()))?;
+    state.entries_hash = hex::encode(chain_entry(&previous, &sender, tickets));
+    entry.tickets.extend(state.tickets..state.tickets + tickets);
+    entry.deposit += required_amount;
+    state.tickets += tickets;
+
+    RAFFLE.save(deps.storage, &state)?;
+    RAFFLE_ENTRIES.save(deps.storage, &sender, &entry)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "enter_raffle")
+        .add_attribute("sender", sender.to_string())
+        .add_attribute("tickets", tickets.to_string())
+        .add_attribute("deposit", required_amount))
+}
+
+/// Returns true if the beacon has not been submitted within the draw timeout after the end of
+/// the entries.
+fn raffle_draw_expired(env: &Env, entry_end_time: Timestamp) -> bool {
+    entry_end_time.plus_seconds(RAFFLE_DRAW_TIMEOUT) <= env.block.time
+}
+
+/// Verifies the beacon `proof`, the VRF proof of the hash chained on the entries by the beacon
+/// public key, and draws the first batch of winning tickets from its output.
+fn execute_submit_beacon(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proof: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let raffle = config.raffle.ok_or(ContractError::NotRaffle {})?;
+    if env.block.time < raffle.entry_end_time {
+        return Err(ContractError::RaffleEntriesOpen {});
+    }
+
+    let mut state = RAFFLE.load(deps.storage)?;
+    if state.seed.is_some() {
+        return Err(ContractError::RaffleAlreadyDrawn {});
+    }
+    if state.tickets <= state.editions {
+        return Err(ContractError::RaffleNotOversubscribed {});
+    }
+    if raffle_draw_expired(&env, raffle.entry_end_time) {
+        return Err(ContractError::RaffleDrawExpired {});
+    }
+
+    // the public key is validated at instantiation.
+    let public_key = hex::decode(&raffle.beacon_public_key)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let message =
+        hex::decode(&state.entries_hash).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let beacon = hex::decode(&proof).map_err(|_| ContractError::InvalidBeacon {})?;
+    let seed = verify_beacon(&public_key, &message, &beacon)?;
+
+    let drawn = draw_winners(deps.storage, &mut state, &seed, RAFFLE_DRAW_BATCH_SIZE)?;
+    state.beacon = Some(proof.to_lowercase());
+    state.seed = Some(hex::encode(seed));
+    RAFFLE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_beacon")
+        .add_attribute("beacon", proof.to_lowercase())
+        .add_attribute("seed", hex::encode(seed))
+        .add_attribute("drawn", drawn.to_string()))
+}
+
+/// Draws the next batch of winning tickets from the beacon output.
+fn execute_draw_raffle(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut state = RAFFLE
+        .load(deps.storage)
+        .map_err(|_| ContractError::NotRaffle {})?;
+    let seed = state
+        .seed
+        .as_ref()
+        .map(hex::decode)
+        .transpose()
+        .map_err(|err| StdError::generic_err(err.to_string()))?
+        .ok_or(ContractError::RaffleBeaconMissing {})?;
+    if state.winners_drawn() {
+        return Err(ContractError::RaffleAlreadyDrawn {});
+    }
+
+    let drawn = draw_winners(deps.storage, &mut state, &seed, RAFFLE_DRAW_BATCH_SIZE)?;
+    RAFFLE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "draw_raffle")
+        .add_attribute("drawn", drawn.to_string())
+        .add_attribute("completed", state.winners_drawn().to_string()))
+}
+
+/// Returns true if the raffle has winning tickets not yet claimed, or tickets still to be drawn,
+/// and the claim window after the draw timeout is not over.
+fn raffle_claims_pending(deps: Deps, env: &Env, config: &Config) -> StdResult<bool> {
+    let (Some(raffle), Some(state)) = (&config.raffle, RAFFLE.may_load(deps.storage)?) else {
+        return Ok(false);
+    };
+
+    let claim_end = raffle
+        .entry_end_time
+        .plus_seconds(RAFFLE_DRAW_TIMEOUT + RAFFLE_CLAIM_WINDOW);
+    if claim_end <= env.block.time || state.unclaimed() == 0 {
+        return Ok(false);
+    }
+
+    // once the draw expired every ticket is refunded, there are no winners left to wait for.
+    let drawn = state.tickets <= state.editions || state.seed.is_some();
+    Ok(drawn || !raffle_draw_expired(env, raffle.entry_end_time))
+}
+
+/// Mints to the sender an edition for each winning ticket, paying its price to the fee split,
+/// and refunds the other tickets. Every ticket is refunded if the beacon was not submitted in
+/// time, or if the launchparty is finalized and can no longer mint.
+fn execute_claim_raffle(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    let raffle = config.raffle.clone().ok_or(ContractError::NotRaffle {})?;
+    if env.block.time < raffle.entry_end_time {
+        return Err(ContractError::RaffleEntriesOpen {});
+    }
+
+    let mut entry = RAFFLE_ENTRIES
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NoRaffleTickets {})?;
+    if entry.claimed {
+        return Err(ContractError::RaffleAlreadyClaimed {});
+    }
+
+    let mut state = RAFFLE.load(deps.storage)?;
+    // once finalized the launchparty can no longer mint, the winning tickets are refunded too.
+    let won = match won(deps.storage, &state, &entry.tickets) {
+        _ if config.final_supply.is_some() => 0,
+        Some(won) => won,
+        None if state.seed.is_none() && raffle_draw_expired(&env, raffle.entry_end_time) => 0,
+        None => return Err(ContractError::RaffleNotDrawn {}),
+    };
+
+    let tickets = entry.tickets.len() as u32;
+    let refund = entry.deposit.multiply_ratio(tickets - won, tickets);
+    let paid = entry.deposit - refund;
+
+    entry.claimed = true;
+    RAFFLE_ENTRIES.save(deps.storage, &info.sender, &entry)?;
+    state.claimed += won;
+    RAFFLE.save(deps.storage, &state)?;
+
+    let mut res = Response::new();
+    if won > 0 && config.bs721_address.is_none() {
+        return Err(ContractError::Bs721NotLinked {});
+    }
+    for _ in 0..won {
+        let (token_id, msgs) =
+            hooked_mint_msgs(&mut deps, &env, &config, &info.sender, &info.sender)?;
+
+        res = res
+            .add_submessages(msgs)
+            .add_attribute("token_id", token_id.to_string());
+
+        config.next_token_id += 1;
+        CONFIG.save(deps.storage, &config)?;
+    }
+
+    if !paid.is_zero() {
+        RAISED.update(deps.storage, |raised| -> StdResult<_> {
+            Ok(raised.checked_add(paid)?)
+        })?;
+
+        let fees = compute_referral_and_royalties_amounts(&config, &None, paid)?;
+        res = res.add_messages(address_fee_msgs(&config, &fees.addresses)?);
+        if !fees.protocol.is_zero() {
+            res = res.add_submessage(protocol_fee_msg(env, &config, fees.protocol)?);
+        }
+        res = res
+            .add_message(payment_msg(
+                &config,
+                &config.payment_address,
+                fees.royalties,
+            )?)
+            .add_attribute("protocol_fee", fees.protocol)
+            .add_attribute("royalties", fees.royalties);
+    }
+
+    if !refund.is_zero() {
+        res = res.add_message(payment_msg(&config, &info.sender, refund)?);
+    }
+
+    Ok(res
+        .add_attribute("action", "claim_raffle")
+        .add_attribute("recipient", info.sender.to_string())
+        .add_attribute("won", won.to_string())
+        .add_attribute("refund", refund))
+}
+
+/// Returns the message paying the protocol fee. The community pool accepts only native coins, so
+/// cw20 fees are sent to the protocol address.
+fn protocol_fee_msg(env: Env, config: &Config, amount: Uint128) -> StdResult<SubMsg> {
+    if let Some(protocol_address) = &config.protocol_address {
+        return Ok(SubMsg::new(payment_msg(config, protocol_address, amount)?));
+    }
+
+    Ok(fund_community_pool_msg(
+        env,
+        coin(amount.u128(), config.price.denom.clone()),
+    ))
+}
+
+/// Returns the messages paying the addresses of the fee split.
+fn address_fee_msgs(config: &Config, fees: &[AddressFee]) -> StdResult<Vec<CosmosMsg>> {
+    fees.iter()
+        .filter(|fee| !fee.amount.is_zero())
+        .map(|fee| payment_msg(config, &fee.address, fee.amount))
+        .collect()
+}
+
+/// Returns the message sending `amount` of the price denom to `recipient`, using a cw20 transfer
+/// if the launchparty accepts a cw20 token.
+fn payment_msg(config: &Config, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    match &config.cw20_address {
+        Some(cw20_address) => Ok(WasmMsg::Execute {
+            contract_addr: cw20_address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into()),
+        None => Ok(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![coin(amount.u128(), config.price.denom.clone())],
+        }
+        .into()),
+    }
+}
+
+/// Replaces the Merkle root of an allowlist phase. Only the creator can update the root, and only
+/// before the phase starts.
+fn execute_update_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    phase_id: u32,
+    merkle_root: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    only_creator(&config, &info.sender)?;
+
+    let mut phase = ALLOWLIST_PHASES
+        .may_load(deps.storage, phase_id)?
+        .ok_or(ContractError::AllowlistPhaseNotFound { phase_id })?;
+
+    if phase.start_time <= env.block.time {
+        return Err(ContractError::AllowlistPhaseStarted { phase_id });
+    }
+
+    validate_merkle_root(&merkle_root)?;
+
+    phase.merkle_root = merkle_root.clone();
+    ALLOWLIST_PHASES.save(deps.storage, phase_id, &phase)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_merkle_root")
+        .add_attribute("phase_id", phase_id.to_string())
+        .add_attribute("merkle_root", merkle_root))
+}
+
+/// Pauses or resumes the minting.
+fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    only_creator(&config, &info.sender)?;
+
+    match (config.paused, paused) {
+        (true, true) => return Err(ContractError::Paused {}),
+        (false, false) => return Err(ContractError::NotPaused {}),
+        _ => {}
+    }
+
+    config.paused = paused;
+    CONFIG.save(deps.storage, &config)?;
+
+    let action = if paused { "pause" } else { "resume" };
+    Ok(Response::new().add_attribute("action", action))
+}
+
+/// Updates the price of a stage or, if `stage_id` is None, the public price and the price of all
+/// the stages not yet ended.
+fn execute_update_price(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    price: Coin,
+    stage_id: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    only_creator(&config, &info.sender)?;
+
+    if price.denom != config.price.denom {
+        return Err(ContractError::InvalidPriceDenom {});
+    }
+
+    let is_ended = |stage: &MintStage| {
+        stage
+            .end_time
+            .is_some_and(|end_time| end_time <= env.block.time)
+    };
+
+    let mut res = Response::new().add_attribute("action", "update_price");
+
+    match stage_id {
+        Some(stage_id) => {
+            let mut stage = STAGES
+                .may_load(deps.storage, stage_id)?
+                .ok_or(ContractError::StageNotFound { stage_id })?;
+            if is_ended(&stage) {
+                return Err(ContractError::StageEnded { stage_id });
+            }
+
+            stage.price = price.clone();
+            STAGES.save(deps.storage, stage_id, &stage)?;
+            res = res.add_attribute("stage_id", stage_id.to_string());
+        }
+        None => {
+            let stages = STAGES
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (stage_id, mut stage) in stages {
+                if !is_ended(&stage) {
+                    stage.price = price.clone();
+                    STAGES.save(deps.storage, stage_id, &stage)?;
+                }
+            }
+
+            config.price = price.clone();
+            CONFIG.save(deps.storage, &config)?;
+        }
+    }
+
+    Ok(res.add_attribute("price", price.to_string()))
+}
+
+/// Updates the maximum amount of tokens an address can mint.
+fn execute_update_max_per_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_per_address: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    only_creator(&config, &info.sender)?;
+
+    if max_per_address == Some(0) {
+        return Err(ContractError::ZeroMaxPerAddress {});
+    }
+
+    config.max_per_address = max_per_address;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_max_per_address")
+        .add_attribute(
+            "max_per_address",
+            max_per_address.map_or("none".to_string(), |max| max.to_string()),
+        ))
+}
+
+/// Moves the start time of the launchparty before it is reached. Stages, the dutch auction and the
+/// raffle entries are moved by the same offset to keep the schedule unchanged.
+fn execute_update_start_time(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    start_time: Timestamp,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    only_creator(&config, &info.sender)?;
+
+    if config.start_time <= env.block.time {
+        return Err(ContractError::AlreadyStarted {});
+    }
+    if start_time <= env.block.time {
+        return Err(ContractError::InvalidStartTime {});
+    }
+
+    let shift = |time: Timestamp| {
+        if start_time >= config.start_time {
+            time.plus_nanos(start_time.nanos() - config.start_time.nanos())
+        } else {
+            time.minus_nanos(config.start_time.nanos() - start_time.nanos())
+        }
+    };
+
+    let stages = STAGES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (stage_id, mut stage) in stages {
+        stage.start_time = shift(stage.start_time);
+        stage.end_time = stage.end_time.map(shift);
+        STAGES.save(deps.storage, stage_id, &stage)?;
+    }
+
+    // the dutch auction keeps its price curve relative to the start of the party.
+    if let Some(auction) = &mut config.dutch_auction {
+        auction.start_time = shift(auction.start_time);
+        auction.end_time = shift(auction.end_time);
+    }
+
+    // raffle entries stay open for as long as scheduled.
+    if let Some(raffle) = &mut config.raffle {
+        raffle.entry_end_time = shift(raffle.entry_end_time);
+    }
+
+    // an explicit end time is moved as well, keeping the length of the party.
+    match &mut config.party_type {
+        PartyType::EndTime(end_time) | PartyType::MaxEditionUntil { end_time, .. } => {
+            *end_time = shift(*end_time);
+        }
+        PartyType::MaxEdition(_) | PartyType::Duration(_) => {}
+    }
+
+    config.start_time = start_time;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_start_time")
+        .add_attribute("start_time", start_time.to_string()))
+}
+
+/// Reveals the final metadata of the editions, checked against the provenance hash committed at
+/// instantiation, and updates the URI of the first batch of editions.
+fn execute_reveal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    base_uri: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    only_creator(&config, &info.sender)?;
+
+    let delayed = config
+        .reveal
+        .as_ref()
+        .ok_or(ContractError::NotDelayedReveal {})?;
+    if REVEAL.exists(deps.storage) {
+        return Err(ContractError::AlreadyRevealed {});
+    }
+    if !party_ended(&env, &config) {
+        return Err(ContractError::PartyNotEnded {});
+    }
+    delayed.verify(&base_uri)?;
+
+    // editions reserved for airdrops can still be minted after the reveal.
+    let size = config
+        .party_type
+        .max_editions()
+        .unwrap_or(config.next_token_id - 1 + config.reserved_supply);
+    // the offset is fixed by the mints, the creator cannot change it by timing the reveal.
+    let offset = if delayed.random_offset && size > 0 {
+        seed_offset(deps.storage, size)?
+    } else {
+        0
+    };
+
+    let reveal = RevealState {
+        base_uri: base_uri.trim_end_matches('/').to_string(),
+        offset,
+        size,
+        last_token_id: None,
+        completed: false,
+    };
+    let res = reveal_batch(deps, &config, reveal)?;
+
+    Ok(res
+        .add_attribute("action", "reveal")
+        .add_attribute("base_uri", base_uri)
+        .add_attribute("offset", offset.to_string()))
+}
+
+/// Updates the URI of the next batch of editions not yet revealed.
+fn execute_reveal_batch(deps: DepsMut) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let reveal = REVEAL
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotRevealed {})?;
+    if reveal.completed {
+        return Err(ContractError::RevealCompleted {});
+    }
+
+    let res = reveal_batch(deps, &config, reveal)?;
+    Ok(res.add_attribute("action", "reveal_batch"))
+}
+
+/// Builds the messages updating the URI of the editions following the last one revealed. The
+/// reveal is completed when the collection has no more editions to update.
+fn reveal_batch(
+    deps: DepsMut,
+    config: &Config,
+    mut reveal: RevealState,
+) -> Result<Response, ContractError> {
+    let collection = config
+        .bs721_address
+        .clone()
+        .ok_or(ContractError::Bs721NotLinked {})?;
+
+    let tokens: TokensResponse = deps.querier.query_wasm_smart(
+        &collection,
+        &Bs721QueryMsg::AllTokens {
+            start_after: reveal.last_token_id.clone(),
+            limit: Some(REVEAL_BATCH_SIZE),
+        },
+    )?;
+
+    let msgs = tokens
+        .tokens
+        .iter()
+        .map(|token_id| -> StdResult<_> {
+            let id: u32 = token_id
+                .parse()
+                .map_err(|_| StdError::parse_err("u32", token_id))?;
+            Ok(WasmMsg::Execute {
+                contract_addr: collection.to_string(),
+                msg: to_json_binary(
+                    &Bs721BaseExecuteMsg::<EditionMetadata, Empty>::UpdateTokenUri {
+                        token_id: token_id.clone(),
+                        token_uri: Some(reveal.token_uri(id)),
+                        content_hash: None,
+                    },
+                )?,
+                funds: vec![],
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    reveal.completed = tokens.tokens.len() < REVEAL_BATCH_SIZE as usize;
+    if let Some(last) = tokens.tokens.last() {
+        reveal.last_token_id = Some(last.clone());
+    }
+    REVEAL.save(deps.storage, &reveal)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("revealed", tokens.tokens.len().to_string())
+        .add_attribute("completed", reveal.completed.to_string()))
+}
+
+/// Finalizes the launchparty once it is over: the minter role of the collection is handed over
+/// or renounced, so that no more editions can be minted by the launchparty, and the trading of
+/// the collection starts.
+fn execute_finalize(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    if config.final_supply.is_some() {
+        return Err(ContractError::AlreadyFinalized {});
+    }
+    if !party_ended(&env, &config) {
+        return Err(ContractError::PartyNotEnded {});
+    }
+    if config.reveal.is_some()
+        && !REVEAL
+            .may_load(deps.storage)?
+            .is_some_and(|reveal| reveal.completed)
+    {
+        return Err(ContractError::RevealNotCompleted {});
+    }
+    // winners must have the time to claim, otherwise their tickets would be refunded.
+    if raffle_claims_pending(deps.as_ref(), &env, &config)? {
+        return Err(ContractError::RaffleClaimsPending {});
+    }
+    // only the creator can forfeit the editions reserved for airdrops.
+    if config.reserved_supply > 0 && info.sender != config.creator {
+        return Err(ContractError::ReservedSupplyPending {});
+    }
+
+    let collection = config
+        .bs721_address
+        .clone()
+        .ok_or(ContractError::Bs721NotLinked {})?;
+    // burned editions, such as the refunds of a crowdfund, are not part of the supply.
+    let supply: NumTokensResponse = deps
+        .querier
+        .query_wasm_smart(&collection, &Bs721QueryMsg::NumTokens {})?;
+    let final_supply = supply.count as u32;
+
+    let (handover_msg, minter) = match config.minter_handover {
+        MinterHandover::Creator => (
+            Bs721BaseExecuteMsg::<EditionMetadata, Empty>::SetMinter {
+                new_minter: config.creator.to_string(),
+            },
+            config.creator.to_string(),
+        ),
+        MinterHandover::Renounce => (
+            Bs721BaseExecuteMsg::RenounceMinter {},
+            "renounced".to_string(),
+        ),
+    };
+    let msgs = [
+        Bs721BaseExecuteMsg::UpdateStartTradingTime {
+            start_trading_time: Some(env.block.time),
+        },
+        handover_msg,
+    ]
+    .iter()
+    .map(|msg| -> StdResult<_> {
+        Ok(WasmMsg::Execute {
+            contract_addr: collection.to_string(),
+            msg: to_json_binary(msg)?,
+            funds: vec![],
+        })
+    })
+    .collect::<StdResult<Vec<_>>>()?;
+
+    config.ended = true;
+    config.final_supply = Some(final_supply);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "finalize")
+        .add_attribute("final_supply", final_supply.to_string())
+        .add_attribute("minter", minter)
+        .add_attribute("start_trading_time", env.block.time.to_string()))
+}
+
+/// Ends the launchparty before its end condition is reached.
+fn execute_end_party(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    only_creator(&config, &info.sender)?;
+
+    if config.ended {
+        return Err(ContractError::PartyEnded {});
+    }
+
+    config.ended = true;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "end_party")
+        .add_attribute("end_time", env.block.time.to_string())
+        .add_attribute("minted", (config.next_token_id - 1).to_string()))
+}
+
+/// Mints for free one of the editions reserved at instantiation to each recipient.
+fn execute_airdrop_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipients: Vec<String>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    only_creator(&config, &info.sender)?;
+
+    if config.bs721_address.is_none() {
+        return Err(ContractError::Bs721NotLinked {});
+    }
+
+    if config.final_supply.is_some() {
+        return Err(ContractError::AlreadyFinalized {});
+    }
+
+    if recipients.is_empty() {
+        return Err(ContractError::NoRecipients {});
+    }
+
+    let amount = recipients.len() as u32;
+    if amount > config.reserved_supply {
+        return Err(ContractError::ReservedSupplyExceeded {
+            remaining: config.reserved_supply,
+        });
+    }
+
+    // TODO: remove this check
+    if (config.next_token_id - 1) + amount > OVERAL_MAXIMUM_MINTABLE {
+        return Err(ContractError::MaxMetadataReached {});
+    }
+
+    let mut res = Response::new();
+    for recipient in recipients {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        let (token_id, msg) =
+            mint_token_msg(deps.storage, &env, &config, &info.sender, &recipient)?;
+
+        res = res
+            .add_message(msg)
+            .add_attribute("token_id", token_id.to_string())
+            .add_attribute("recipient", recipient.to_string());
+
+        config.next_token_id += 1;
+    }
+
+    config.reserved_supply -= amount;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(res.add_attribute("action", "airdrop_mint"))
+}
+
+/// Returns an error if `sender` is not the creator of the launchparty.
+fn only_creator(config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    if sender != config.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+fn fund_community_pool_msg(env: Env, amount: Coin) -> SubMsg {
+    let mut buffer = vec![];
+
+    MsgFundCommunityPool {
+        amount: vec![cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+            denom: amount.denom,
+            amount: amount.amount.to_string(),
+        }],
+        depositor: env.contract.address.to_string(),
+    }
+    .encode(&mut buffer)
+    .unwrap();
+
+    SubMsg::new(CosmosMsg::Stargate {
+        type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_string(),
+        value: Binary::from(buffer),
+    })
+}
+
+/// Splits `total_amount` among the recipients of the fee split. If `referral` is None, the
+/// referral share is associated with the royalties contract.
+///
+/// # Arguments
+///
+/// * `config` - Configuration parameters for the computation.
+/// * `referral` - Optional referral address.
+/// * `total_amount` - Total amount of tokens.
+///
+/// # Returns
+///
+/// Returns the amounts of the referral, royalties, protocol and of each address of the split.
+///
+/// # Errors
+///
+/// Returns an error if the amount associated with the royalties contract is zero.
+pub fn compute_referral_and_royalties_amounts(
+    config: &Config,
+    referral: &Option<Addr>,
+    total_amount: Uint128,
+) -> StdResult<FeeAmounts> {
+    let fees = split_fees(&config.fee_split, total_amount, referral.is_some())?;
+    if fees.royalties.is_zero() {
+        return Err(StdError::generic_err(
+            "royalties amount is zero or negative",
+        ));
+    }
+
+    Ok(fees)
+}
+
+/// Basic checks performed before minting a token
+///
+/// ## Validation Checks
+///
+/// - start time older than current time.
+/// - bs721 base address is stored in the contract.
+/// - royalties address is stored in the contract.
+/// - minting is not paused.
+/// - checks if party is active and not ended by the creator.
+/// - check that maximum number of pre-generated metadata is not reched.
+pub fn before_mint_checks(
+    env: &Env,
+    config: &Config,
+    edition_to_mint: u32,
+) -> Result<(), ContractError> {
+    if config.start_time > env.block.time {
+        return Err(ContractError::NotStarted {});
+    }
+
+    check_mintable(env, config, edition_to_mint)
+}
+
+/// Checks the anti-bot limits of a mint of `edition_to_mint` tokens, recording it in the editions
+/// minted in the block and by the `limited` address.
+///
+/// ## Validation Checks
+///
+/// - the editions do not exceed the maximum per transaction.
+/// - the editions minted in the block do not exceed the maximum per block.
+/// - the cooldown since the last mint of the address is elapsed.
+/// - the editions minted by the address in its window do not exceed the maximum per window.
+fn mint_limits_checks(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    limited: &Addr,
+    edition_to_mint: u32,
+) -> Result<(), ContractError> {
+    let Some(limits) = &config.mint_limits else {
+        return Ok(());
+    };
+
+    if let Some(max) = limits.max_per_tx {
+        if edition_to_mint > max {
+            return Err(ContractError::MaxPerTxExceeded { max });
+        }
+    }
+
+    if let Some(max) = limits.max_per_block {
+        let minted = BLOCK_MINTS
+            .may_load(storage)?
+            .filter(|block| block.height == env.block.height)
+            .map_or(0, |block| block.minted);
+        if minted + edition_to_mint > max {
+            return Err(ContractError::MaxPerBlockExceeded {
+                remaining: max.saturating_sub(minted),
+            });
+        }
+        BLOCK_MINTS.save(
+            storage,
+            &BlockMints {
+                height: env.block.height,
+                minted: minted + edition_to_mint,
+            },
+        )?;
+    }
+
+    if limits.cooldown.is_none() && limits.max_per_window.is_none() {
+        return Ok(());
+    }
+
+    let activity = MINT_ACTIVITY.may_load(storage, limited)?;
+    if let (Some(cooldown), Some(activity)) = (limits.cooldown, &activity) {
+        let next_mint = activity.last_mint.plus_seconds(cooldown);
+        if env.block.time < next_mint {
+            return Err(ContractError::MintCooldown { next_mint });
+        }
+    }
+
+    // a new window starts at the first mint after the previous one is elapsed.
+    let (window_start, window_minted) = match (&limits.max_per_window, activity) {
+        (Some(window), Some(activity))
+            if env.block.time < activity.window_start.plus_seconds(window.duration) =>
+        {
+            (activity.window_start, activity.window_minted)
+        }
+        _ => (env.block.time, 0),
+    };
+    if let Some(window) = &limits.max_per_window {
+        if window_minted + edition_to_mint > window.max_editions {
+            return Err(ContractError::MaxPerWindowExceeded {
+                remaining: window.max_editions.saturating_sub(window_minted),
+            });
+        }
+    }
+
+    MINT_ACTIVITY.save(
+        storage,
+        limited,
+        &MintActivity {
+            last_mint: env.block.time,
+            window_start,
+            window_minted: window_minted + edition_to_mint,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Checks the gate tokens used to mint `edition_to_mint` tokens during the public mint, recording
+/// them as claimed by `sender`. Returns true if the mint opened the gate.
+///
+/// ## Validation Checks
+///
+/// - gate tokens are used only if the launchparty is token gated.
+/// - a gate token is required for each edition if minting is restricted to holders.
+/// - each gate token belongs to a gate collection, is owned by the sender and is not already
+///   claimed.
+fn gate_mint_checks(
+    deps: &mut DepsMut,
+    config: &Config,
+    sender: &Addr,
+    edition_to_mint: u32,
+    gate_tokens: Option<Vec<GateToken>>,
+) -> Result<bool, ContractError> {
+    let Some(token_gate) = &config.token_gate else {
+        return match gate_tokens {
+            Some(_) => Err(ContractError::NotTokenGated {}),
+            None => Ok(false),
+        };
+    };
+
+    let gate_tokens = match gate_tokens {
+        Some(gate_tokens) => gate_tokens,
+        None if token_gate.holders_only => return Err(ContractError::GateTokenRequired {}),
+        None => return Ok(false),
+    };
+    if gate_tokens.len() != edition_to_mint as usize {
+        return Err(ContractError::GateTokenRequired {});
+    }
+
+    for token in gate_tokens {
+        if !token_gate.collections.contains(&token.collection) {
+            return Err(ContractError::InvalidGateCollection {
+                collection: token.collection,
+            });
+        }
 
-fn fund_community_pool_msg(env: Env, amount: Coin) -> SubMsg {
-    let mut buffer = vec![];
+        let owner: OwnerOfResponse = deps.querier.query_wasm_smart(
+            &token.collection,
+            &Bs721QueryMsg::OwnerOf {
+                token_id: token.token_id.clone(),
+                include_expired: None,
+            },
+        )?;
+        if owner.owner != sender.as_str() {
+            return Err(ContractError::NotGateTokenOwner {
+                token_id: token.token_id,
+            });
+        }
 
-    MsgFundCommunityPool {
-        amount: vec![cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
-            denom: amount.denom,
-            amount: amount.amount.to_string(),
-        }],
-        depositor: env.contract.address.to_string(),
+        let key = (token.collection.as_str(), token.token_id.as_str());
+        if GATE_CLAIMS.has(deps.storage, key) {
+            return Err(ContractError::GateTokenClaimed {
+                token_id: token.token_id,
+            });
+        }
+        GATE_CLAIMS.save(deps.storage, key, sender)?;
     }
-    .encode(&mut buffer)
-    .unwrap();
 
-    SubMsg::new(CosmosMsg::Stargate {
-        type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_string(),
-        value: Binary::from(buffer),
-    })
+    Ok(true)
 }
 
-/// Computes the amount of `total_amount` associated with the referral address, if any, and the amount
-/// associated with the royalties contract. If `referral` is None, zero tokens are associated with the referrer.
-///
-/// # Arguments
-///
-/// * `config` - Configuration parameters for the computation.
-/// * `referral` - Optional referral address.
-/// * `total_amount` - Total amount of tokens.
-///
-/// # Returns
+/// Checks performed before minting a token during an allowlist phase. On success the amount
+/// minted by the sender in the phase is updated and the phase price is returned.
 ///
-/// Returns a tuple containing the referral amount and royalties amount as `Uint128`.
-///
-/// # Errors
+/// ## Validation Checks
 ///
-/// Returns an error if an overflow occurs during the computation.
-pub fn compute_referral_and_royalties_amounts(
+/// - the phase exists and is active.
+/// - the sender, with its allocation, is included in the phase Merkle tree.
+/// - tokens can still be minted, as in `before_mint_checks`.
+/// - the allocation of the sender is not exceeded.
+fn allowlist_mint_checks(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
     config: &Config,
-    referral: &Option<Addr>,
-    total_amount: Uint128,
-) -> StdResult<(Uint128, Uint128, Uint128)> {
-    let referral_amount = referral.as_ref().map_or_else(
-        || Ok(Uint128::zero()),
-        |_address| -> Result<Uint128, _> {
-            total_amount
-                .checked_mul(Uint128::from(config.referral_fee_bps))
-                .map_err(StdError::overflow)?
-                .checked_div(Uint128::new(10_000))
-                .map_err(StdError::divide_by_zero)
-        },
-    )?;
+    edition_to_mint: u32,
+    proof: AllowlistProof,
+) -> Result<Uint128, ContractError> {
+    let phase_id = proof.phase_id;
+    let phase = ALLOWLIST_PHASES
+        .may_load(storage, phase_id)?
+        .ok_or(ContractError::AllowlistPhaseNotFound { phase_id })?;
+
+    if !phase.is_active(env.block.time) {
+        return Err(ContractError::AllowlistPhaseNotActive { phase_id });
+    }
 
-    let protocol_amount = total_amount
-        .checked_mul(Uint128::from(config.protocol_fee_bps))
-        .map_err(StdError::overflow)?
-        .checked_div(Uint128::new(10_000))
-        .map_err(StdError::divide_by_zero)?;
+    verify_allowlist_proof(&phase.merkle_root, sender, proof.allocation, &proof.proof)?;
 
-    let royalties_amount = total_amount - referral_amount - protocol_amount;
-    if royalties_amount.u128() <= 0u128 {
-        return Err(StdError::generic_err(
-            "royalties amount is zero or negative",
-        ));
+    check_mintable(env, config, edition_to_mint)?;
+
+    let already_minted = ALLOWLIST_MINTED
+        .may_load(storage, (phase_id, sender))?
+        .unwrap_or(0);
+    let remaining = proof.allocation.saturating_sub(already_minted);
+    if edition_to_mint > remaining {
+        return Err(ContractError::AllowlistAllocationExceeded { remaining });
     }
 
-    Ok((referral_amount, royalties_amount, protocol_amount))
+    ALLOWLIST_MINTED.save(
+        storage,
+        (phase_id, sender),
+        &(already_minted + edition_to_mint),
+    )?;
+
+    Ok(phase.price.amount)
 }
 
-/// Basic checks performed before minting a token
+/// Checks performed before minting a token during a stage of the public mint. On success the
+/// amounts minted in the stage are updated and the stage price is returned.
 ///
 /// ## Validation Checks
 ///
-/// - start time older than current time.
-/// - bs721 base address is stored in the contract.
-/// - royalties address is stored in the contract.
-/// - checks if party is active.
-/// - check that maximum number of pre-generated metadata is not reched.
-pub fn before_mint_checks(
-    env: &Env,
-    config: &Config,
+/// - the maximum amount of tokens an address can mint in the stage is not exceeded.
+/// - the stage supply is not exceeded.
+fn stage_mint_checks(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    stage_id: u32,
+    stage: &MintStage,
     edition_to_mint: u32,
-) -> Result<(), ContractError> {
-    if config.start_time > env.block.time {
-        return Err(ContractError::NotStarted {});
+) -> Result<Uint128, ContractError> {
+    let address_minted = STAGE_ADDRESS_TOKENS
+        .may_load(storage, (stage_id, sender))?
+        .unwrap_or(0);
+    if let Some(max_per_address) = stage.max_per_address {
+        let remaining = max_per_address.saturating_sub(address_minted);
+        if edition_to_mint > remaining {
+            return Err(ContractError::MaxPerAddressExceeded { remaining });
+        }
+    }
+
+    let stage_minted = STAGE_MINTED.may_load(storage, stage_id)?.unwrap_or(0);
+    if let Some(max_supply) = stage.max_supply {
+        let remaining = max_supply.saturating_sub(stage_minted);
+        if edition_to_mint > remaining {
+            return Err(ContractError::StageSupplyExceeded { remaining });
+        }
     }
 
+    STAGE_ADDRESS_TOKENS.save(
+        storage,
+        (stage_id, sender),
+        &(address_minted + edition_to_mint),
+    )?;
+    STAGE_MINTED.save(storage, stage_id, &(stage_minted + edition_to_mint))?;
+
+    Ok(stage.price.amount)
+}
+
+/// Returns the stage of the public mint active at the given time, if any.
+fn current_stage(storage: &dyn Storage, time: Timestamp) -> StdResult<Option<(u32, MintStage)>> {
+    for item in STAGES.range(storage, None, None, Order::Ascending) {
+        let (stage_id, stage) = item?;
+        if stage.is_active(time) {
+            return Ok(Some((stage_id, stage)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Checks that the bs721 contract is linked and that the party allows to mint `edition_to_mint`
+/// new tokens.
+fn check_mintable(env: &Env, config: &Config, edition_to_mint: u32) -> Result<(), ContractError> {
     if config.bs721_address.is_none() {
         return Err(ContractError::Bs721NotLinked {});
     }
 
-    if !party_is_active(
-        env,
-        &config.party_type,
-        (config.next_token_id - 1) + edition_to_mint,
-        config.start_time,
-    ) {
+    if config.paused {
+        return Err(ContractError::Paused {});
+    }
+
+    if config.ended
+        || !party_is_active(
+            env,
+            &config.party_type,
+            // editions reserved for airdrops cannot be minted by the public.
+            (config.next_token_id - 1) + edition_to_mint + config.reserved_supply,
+            config.start_time,
+        )
+    {
         return Err(ContractError::PartyEnded {});
     }
 
@@ -406,51 +2490,373 @@ pub fn before_mint_checks(
 /// A party is active if:
 ///
 /// - maxmimum number of editions have been not already minted.
-/// - current time is less than starting time plus party duration.
+/// - current time is not after the end time of the party.
 pub fn party_is_active(
     env: &Env,
     party_type: &PartyType,
     token_id: u32,
     start_time: Timestamp,
 ) -> bool {
-    match party_type {
-        PartyType::MaxEdition(number) => {
-            if token_id > *number {
-                return false;
-            }
-        }
-        PartyType::Duration(duration) => {
-            if start_time.plus_seconds(*duration as u64) < env.block.time {
-                return false;
-            }
-        }
+    if party_type
+        .max_editions()
+        .is_some_and(|number| token_id > number)
+    {
+        return false;
+    }
+    if party_type
+        .end_time(start_time)
+        .is_some_and(|end_time| end_time < env.block.time)
+    {
+        return false;
     }
     true
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetConfig {} => to_json_binary(&query_config(deps)?),
         QueryMsg::MaxPerAddress { address } => {
-            to_json_binary(&query_max_per_address(deps, address)?)
+            to_json_binary(&query_max_per_address(deps, env, address)?)
+        }
+        QueryMsg::AllowlistPhases {} => to_json_binary(&query_allowlist_phases(deps)?),
+        QueryMsg::AllowlistMinted { phase_id, address } => {
+            to_json_binary(&query_allowlist_minted(deps, phase_id, address)?)
+        }
+        QueryMsg::CurrentStage {} => to_json_binary(&query_current_stage(deps, env)?),
+        QueryMsg::Crowdfund {} => to_json_binary(&query_crowdfund(deps)?),
+        QueryMsg::Status {} => to_json_binary(&query_status(deps, env)?),
+        QueryMsg::DutchAuction {} => to_json_binary(&query_dutch_auction(deps, env)?),
+        QueryMsg::ReferralStats { address } => {
+            to_json_binary(&query_referral_stats(deps, address)?)
+        }
+        QueryMsg::TopReferrers { start_after, limit } => {
+            to_json_binary(&query_top_referrers(deps, start_after, limit)?)
         }
+        QueryMsg::Tiers {} => to_json_binary(&query_tiers(deps)?),
+        QueryMsg::TokenTier { token_id } => to_json_binary(&query_token_tier(deps, token_id)?),
+        QueryMsg::GateClaim {
+            collection,
+            token_id,
+        } => to_json_binary(&query_gate_claim(deps, collection, token_id)?),
+        QueryMsg::RedeemPending { address } => {
+            to_json_binary(&query_redeem_pending(deps, address)?)
+        }
+        QueryMsg::Reveal {} => to_json_binary(&query_reveal(deps)?),
+        QueryMsg::Raffle {} => to_json_binary(&query_raffle(deps, env)?),
+        QueryMsg::RaffleEntry { address } => {
+            to_json_binary(&query_raffle_entry(deps, env, address)?)
+        }
+        QueryMsg::PreMintHooks {} => query_premint_hooks(deps),
+        QueryMsg::PostMintHooks {} => query_postmint_hooks(deps),
+    }
+}
+
+fn query_status(deps: Deps, env: Env) -> StdResult<StatusResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let raised = RAISED.may_load(deps.storage)?.unwrap_or_default();
+    let minted = config.next_token_id - 1;
+
+    let active = config.start_time <= env.block.time
+        && !config.paused
+        && !config.ended
+        && party_is_active(
+            &env,
+            &config.party_type,
+            config.next_token_id + config.reserved_supply,
+            config.start_time,
+        );
+
+    let remaining = config
+        .party_type
+        .max_editions()
+        .map(|number| number.saturating_sub(minted + config.reserved_supply));
+    let end_time = config.party_type.end_time(config.start_time);
+
+    Ok(StatusResponse {
+        active,
+        paused: config.paused,
+        minted,
+        remaining,
+        reserved: config.reserved_supply,
+        end_time,
+        raised: coin(raised.u128(), config.price.denom),
+        bs721_address: config.bs721_address,
+        final_supply: config.final_supply,
+    })
+}
+
+fn query_dutch_auction(deps: Deps, env: Env) -> StdResult<DutchAuctionResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let sales = AUCTION_SALES.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(DutchAuctionResponse {
+        curve: config
+            .dutch_auction
+            .as_ref()
+            .map(|auction| auction.curve())
+            .unwrap_or_default(),
+        current_price: config
+            .dutch_auction
+            .as_ref()
+            .map(|auction| auction.price_at(env.block.time)),
+        auction: config.dutch_auction,
+        clearing_price: sales.clearing_price,
+        sold: sales.sold,
+    })
+}
+
+fn query_referral_stats(deps: Deps, address: String) -> StdResult<ReferralStatsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let stats = referrals()
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+
+    referral_stats_response(deps.storage, &config, address, stats)
+}
+
+fn query_top_referrers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TopReferrersResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    // referrers are iterated from the highest earned, so the cursor is the upper bound.
+    let max = start_after
+        .map(|address| -> StdResult<_> {
+            let address = deps.api.addr_validate(&address)?;
+            let stats = referrals().load(deps.storage, &address)?;
+            Ok(Bound::exclusive((stats.earned.u128(), address)))
+        })
+        .transpose()?;
+
+    let referrers = referrals()
+        .idx
+        .earned
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            let (address, stats) = item?;
+            referral_stats_response(deps.storage, &config, address, stats)
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(TopReferrersResponse { referrers })
+}
+
+/// Returns the stats of a referral, with the claimable amount including the released crowdfund
+/// escrow and the fees on the amount held by a settled dutch auction.
+fn referral_stats_response(
+    storage: &dyn Storage,
+    config: &Config,
+    address: Addr,
+    stats: ReferralStats,
+) -> StdResult<ReferralStatsResponse> {
+    let settled = settled_auction_referral(storage, config, &address)?;
+    let mut claimable = stats.claimable + settled;
+    if ESCROW.may_load(storage)?.unwrap_or_default().released {
+        claimable += REFERRAL_ESCROW
+            .may_load(storage, &address)?
+            .unwrap_or_default();
     }
+
+    Ok(ReferralStatsResponse {
+        address,
+        mints: stats.mints,
+        earned: coin((stats.earned + settled).u128(), &config.price.denom),
+        claimable: coin(claimable.u128(), &config.price.denom),
+    })
+}
+
+fn query_tiers(deps: Deps) -> StdResult<TiersResponse> {
+    let tiers = TIERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(tier_id, tier)| TierResponse { tier_id, tier }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TiersResponse { tiers })
+}
+
+fn query_token_tier(deps: Deps, token_id: u32) -> StdResult<TokenTierResponse> {
+    let tier = TOKEN_TIERS
+        .may_load(deps.storage, token_id)?
+        .map(|tier_id| -> StdResult<_> {
+            Ok(TierResponse {
+                tier_id,
+                tier: TIERS.load(deps.storage, tier_id)?,
+            })
+        })
+        .transpose()?;
+
+    Ok(TokenTierResponse { tier })
+}
+
+fn query_gate_claim(
+    deps: Deps,
+    collection: String,
+    token_id: String,
+) -> StdResult<GateClaimResponse> {
+    let claimed_by = GATE_CLAIMS.may_load(deps.storage, (&collection, &token_id))?;
+    Ok(GateClaimResponse { claimed_by })
+}
+
+fn query_redeem_pending(deps: Deps, address: String) -> StdResult<RedeemPendingResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let token_ids = REDEEM_PENDING
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    let missing = config.burn_to_mint.map_or(0, |burn_to_mint| {
+        burn_to_mint.ratio.saturating_sub(token_ids.len() as u32)
+    });
+
+    Ok(RedeemPendingResponse { token_ids, missing })
+}
+
+fn query_reveal(deps: Deps) -> StdResult<RevealResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let reveal = REVEAL.may_load(deps.storage)?;
+
+    Ok(RevealResponse {
+        reveal: config.reveal,
+        base_uri: reveal.as_ref().map(|reveal| reveal.base_uri.clone()),
+        offset: reveal.as_ref().map(|reveal| reveal.offset),
+        completed: reveal.is_some_and(|reveal| reveal.completed),
+    })
+}
+
+fn query_raffle(deps: Deps, env: Env) -> StdResult<RaffleResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let Some(state) = RAFFLE.may_load(deps.storage)? else {
+        return Ok(RaffleResponse {
+            raffle: None,
+            tickets: 0,
+            editions: 0,
+            beacon_message: None,
+            beacon: None,
+            seed: None,
+            drawn: false,
+            claimed: 0,
+        });
+    };
+
+    let closed = config
+        .raffle
+        .as_ref()
+        .is_some_and(|raffle| raffle.entry_end_time <= env.block.time);
+
+    Ok(RaffleResponse {
+        raffle: config.raffle,
+        tickets: state.tickets,
+        editions: state.editions,
+        beacon_message: closed.then(|| state.entries_hash.clone()),
+        beacon: state.beacon.clone(),
+        seed: state.seed.clone(),
+        drawn: closed && state.winners_drawn(),
+        claimed: state.claimed,
+    })
+}
+
+fn query_raffle_entry(deps: Deps, env: Env, address: String) -> StdResult<RaffleEntryResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    let entry = RAFFLE_ENTRIES
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+
+    let closed = config
+        .raffle
+        .is_some_and(|raffle| raffle.entry_end_time <= env.block.time);
+    let won = match RAFFLE.may_load(deps.storage)? {
+        Some(state) if closed => won(deps.storage, &state, &entry.tickets),
+        _ => None,
+    };
+
+    Ok(RaffleEntryResponse {
+        tickets: entry.tickets,
+        deposit: entry.deposit,
+        won,
+        claimed: entry.claimed,
+    })
+}
+
+fn query_crowdfund(deps: Deps) -> StdResult<CrowdfundResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let escrow = ESCROW.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(CrowdfundResponse {
+        min_raise: config.min_raise,
+        raised: escrow.raised,
+        released: escrow.released,
+    })
+}
+
+fn query_current_stage(deps: Deps, env: Env) -> StdResult<CurrentStageResponse> {
+    let stage = current_stage(deps.storage, env.block.time)?
+        .map(|(stage_id, stage)| -> StdResult<_> {
+            let minted = STAGE_MINTED.may_load(deps.storage, stage_id)?.unwrap_or(0);
+            Ok(StageResponse {
+                stage_id,
+                stage,
+                minted,
+            })
+        })
+        .transpose()?;
+
+    Ok(CurrentStageResponse { stage })
+}
+
+fn query_allowlist_phases(deps: Deps) -> StdResult<AllowlistPhasesResponse> {
+    let phases = ALLOWLIST_PHASES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(phase_id, phase)| AllowlistPhaseResponse { phase_id, phase }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AllowlistPhasesResponse { phases })
+}
+
+fn query_allowlist_minted(
+    deps: Deps,
+    phase_id: u32,
+    address: String,
+) -> StdResult<AllowlistMintedResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let minted = ALLOWLIST_MINTED
+        .may_load(deps.storage, (phase_id, &addr))?
+        .unwrap_or(0);
+
+    Ok(AllowlistMintedResponse { minted })
 }
 
-fn query_max_per_address(deps: Deps, address: String) -> StdResult<MaxPerAddressResponse> {
+/// Returns the amount of tokens the address can still mint, given by the lowest between the
+/// remaining of the launchparty and the remaining of the current stage.
+fn query_max_per_address(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<MaxPerAddressResponse> {
     let addr = deps.api.addr_validate(&address)?;
     let already_minted = (ADDRESS_TOKENS.key(&addr).may_load(deps.storage)?).unwrap_or(0);
 
     let config: Config = CONFIG.load(deps.storage)?;
 
-    if let Some(max_per_address) = config.max_per_address {
-        return Ok(MaxPerAddressResponse {
-            remaining: Some(max_per_address.checked_sub(already_minted).unwrap_or(0)),
-        });
+    let mut remaining = config
+        .max_per_address
+        .map(|max_per_address| max_per_address.saturating_sub(already_minted));
+
+    if let Some((stage_id, stage)) = current_stage(deps.storage, env.block.time)? {
+        if let Some(max_per_address) = stage.max_per_address {
+            let stage_minted = STAGE_ADDRESS_TOKENS
+                .may_load(deps.storage, (stage_id, &addr))?
+                .unwrap_or(0);
+            let stage_remaining = max_per_address.saturating_sub(stage_minted);
+            remaining = Some(remaining.map_or(stage_remaining, |r| r.min(stage_remaining)));
+        }
     }
 
-    Ok(MaxPerAddressResponse { remaining: None })
+    Ok(MaxPerAddressResponse { remaining })
 }
 
 fn query_config(deps: Deps) -> StdResult<Config> {
@@ -466,6 +2872,8 @@ fn query_config(deps: Deps) -> StdResult<Config> {
 mod tests {
 
     use super::*;
+    use crate::msg::default_instantiate_msg;
+    use bs_std::fees::{royalties_fee_split, FeeRecipient, FeeShare, FeeSplitError};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
     use cosmwasm_std::{from_json, to_json_binary, SubMsgResponse, SubMsgResult, Timestamp};
     use prost::Message;
@@ -501,6 +2909,26 @@ mod tests {
                 "expected false since current time 1s less then start time + party duration"
             )
         }
+
+        {
+            let party_type = PartyType::MaxEditionUntil {
+                max_editions: 2,
+                end_time: env.block.time,
+            };
+            let start_time = env.block.time.minus_seconds(10);
+            assert!(party_is_active(&env, &party_type, 2, start_time));
+            assert!(
+                !party_is_active(&env, &party_type, 3, start_time),
+                "expected false since the max editions are minted before the end time"
+            );
+
+            let mut env = env.clone();
+            env.block.time = env.block.time.plus_seconds(1);
+            assert!(
+                !party_is_active(&env, &party_type, 1, start_time),
+                "expected false since the end time is passed before the max editions are minted"
+            );
+        }
     }
 
     #[test]
@@ -512,16 +2940,33 @@ mod tests {
             symbol: String::from(""),
             name: String::from(""),
             uri: String::from(""),
+            content_hash: None,
             price: coin(1, "ubtsg"),
             max_per_address: None,
             next_token_id: 1,
             payment_address: Addr::unchecked("payment_address"),
             seller_fee_bps: 1_000,
-            referral_fee_bps: 1_000,
-            protocol_fee_bps: 1_000,
+            accrue_referrals: false,
+            fee_split: royalties_fee_split(1_000, 1_000),
             start_time: Timestamp::from_seconds(1),
             party_type: PartyType::MaxEdition(2),
+            paused: false,
+            ended: false,
+            randomized: false,
+            min_raise: None,
+            limit_target: MintLimitTarget::Payer,
+            mint_limits: None,
+            reserved_supply: 0,
+            dutch_auction: None,
+            burn_to_mint: None,
+            raffle: None,
+            token_gate: None,
+            reveal: None,
+            minter_handover: MinterHandover::Creator,
+            final_supply: None,
             bs721_address: Some(Addr::unchecked("contract1")),
+            cw20_address: None,
+            protocol_address: None,
         };
 
         {
@@ -578,54 +3023,69 @@ mod tests {
             symbol: String::from(""),
             name: String::from(""),
             uri: String::from(""),
+            content_hash: None,
             price: coin(1, "ubtsg"),
             max_per_address: None,
             next_token_id: 1,
             seller_fee_bps: 1_000,
-            referral_fee_bps: 1_000,
-            protocol_fee_bps: 1_000,
+            accrue_referrals: false,
+            fee_split: royalties_fee_split(1_000, 1_000),
             start_time: Timestamp::from_seconds(1),
             party_type: PartyType::MaxEdition(2),
+            paused: false,
+            ended: false,
+            randomized: false,
+            min_raise: None,
+            limit_target: MintLimitTarget::Payer,
+            mint_limits: None,
+            reserved_supply: 0,
+            dutch_auction: None,
+            burn_to_mint: None,
+            raffle: None,
+            token_gate: None,
+            reveal: None,
+            minter_handover: MinterHandover::Creator,
+            final_supply: None,
             bs721_address: Some(Addr::unchecked("contract1")),
             payment_address: Addr::unchecked("contract2"),
+            cw20_address: None,
+            protocol_address: None,
         };
 
         {
-            let (referral_amt, royalties_amt, protocol_amt) =
-                compute_referral_and_royalties_amounts(&config, &None, Uint128::new(1_000))
-                    .unwrap();
+            let fees = compute_referral_and_royalties_amounts(&config, &None, Uint128::new(1_000))
+                .unwrap();
             assert_eq!(
                 Uint128::zero(),
-                referral_amt,
+                fees.referral,
                 "expected zero referral amount since no referral address"
             );
             assert_eq!(
                 Uint128::new(900),
-                royalties_amt,
+                fees.royalties,
                 "expected royalties amount equal to total amount - protocol_fee"
             );
-            assert_eq!(Uint128::new(100), protocol_amt, "expected protocol fee")
+            assert_eq!(Uint128::new(100), fees.protocol, "expected protocol fee")
         }
 
         {
-            let (referral_amt, royalties_amt, protocol_amt) =
-                compute_referral_and_royalties_amounts(
-                    &config,
-                    &Some(Addr::unchecked("referrral".to_string())),
-                    Uint128::new(1_000),
-                )
-                .unwrap();
+            let fees = compute_referral_and_royalties_amounts(
+                &config,
+                &Some(Addr::unchecked("referrral".to_string())),
+                Uint128::new(1_000),
+            )
+            .unwrap();
             assert_eq!(
                 Uint128::new(100),
-                referral_amt,
+                fees.referral,
                 "expected 10% as referral amount"
             );
             assert_eq!(
                 Uint128::new(800),
-                royalties_amt,
+                fees.royalties,
                 "expected 80% as royalties amount"
             );
-            assert_eq!(Uint128::new(100), protocol_amt, "expected protocol fee")
+            assert_eq!(Uint128::new(100), fees.protocol, "expected protocol fee")
         }
 
         {
@@ -646,62 +3106,97 @@ mod tests {
         }
 
         {
-            let (referral_amt, royalties_amt, protocol_amt) =
-                compute_referral_and_royalties_amounts(
-                    &config,
-                    &Some(Addr::unchecked("referrral".to_string())),
-                    Uint128::new(1),
-                )
-                .unwrap();
+            let fees = compute_referral_and_royalties_amounts(
+                &config,
+                &Some(Addr::unchecked("referrral".to_string())),
+                Uint128::new(1),
+            )
+            .unwrap();
             assert_eq!(
                 Uint128::zero(),
-                referral_amt,
+                fees.referral,
                 "expected zero 10% of 1 is rounded zero"
             );
             assert_eq!(
                 Uint128::new(1),
-                royalties_amt,
+                fees.royalties,
                 "expected 1 since royalties is 1 minus referral amount"
             );
-            assert_eq!(Uint128::new(0), protocol_amt, "expected zero protocol fee")
+            assert_eq!(Uint128::new(0), fees.protocol, "expected zero protocol fee")
         }
 
         {
-            let (referral_amt, royalties_amt, protocol_amt) =
-                compute_referral_and_royalties_amounts(
-                    &config,
-                    &Some(Addr::unchecked("referrral".to_string())),
-                    Uint128::new(9),
-                )
-                .unwrap();
+            let fees = compute_referral_and_royalties_amounts(
+                &config,
+                &Some(Addr::unchecked("referrral".to_string())),
+                Uint128::new(9),
+            )
+            .unwrap();
             assert_eq!(
                 Uint128::zero(),
-                referral_amt,
+                fees.referral,
                 "expected zero since 10% of 9 is rounded zero"
             );
             assert_eq!(
                 Uint128::new(9),
-                royalties_amt,
+                fees.royalties,
                 "expected 9 since royalties is 9 minus referral amount"
             );
-            assert_eq!(Uint128::new(0), protocol_amt, "expected zero protocol fee")
+            assert_eq!(Uint128::new(0), fees.protocol, "expected zero protocol fee")
         }
 
         {
-            let (referral_amt, royalties_amt, protocol_amt) =
-                compute_referral_and_royalties_amounts(
-                    &config,
-                    &Some(Addr::unchecked("referrral".to_string())),
-                    Uint128::new(10),
-                )
-                .unwrap();
-            assert_eq!(Uint128::new(1), referral_amt, "expected 1 since 10% of 10");
+            let fees = compute_referral_and_royalties_amounts(
+                &config,
+                &Some(Addr::unchecked("referrral".to_string())),
+                Uint128::new(10),
+            )
+            .unwrap();
+            assert_eq!(Uint128::new(1), fees.referral, "expected 1 since 10% of 10");
             assert_eq!(
                 Uint128::new(8),
-                royalties_amt,
+                fees.royalties,
                 "expected 8 since royalties is 10 minus referral amount minus protocol fee"
             );
-            assert_eq!(Uint128::new(1), protocol_amt, "expected 1 protocol fee")
+            assert_eq!(Uint128::new(1), fees.protocol, "expected 1 protocol fee")
+        }
+
+        {
+            let mut config = config.clone();
+            config.fee_split = vec![
+                FeeShare {
+                    recipient: FeeRecipient::Royalties,
+                    bps: 8_000,
+                },
+                FeeShare {
+                    recipient: FeeRecipient::Address("label".to_string()),
+                    bps: 1_500,
+                },
+                FeeShare {
+                    recipient: FeeRecipient::Protocol,
+                    bps: 500,
+                },
+            ];
+            let fees = compute_referral_and_royalties_amounts(
+                &config,
+                &Some(Addr::unchecked("referrral".to_string())),
+                Uint128::new(1_000),
+            )
+            .unwrap();
+            assert_eq!(
+                Uint128::zero(),
+                fees.referral,
+                "expected zero since the split has no referral share"
+            );
+            assert_eq!(
+                fees.addresses,
+                vec![AddressFee {
+                    address: Addr::unchecked("label"),
+                    amount: Uint128::new(150),
+                }]
+            );
+            assert_eq!(Uint128::new(800), fees.royalties);
+            assert_eq!(Uint128::new(50), fees.protocol);
         }
     }
 
@@ -710,24 +3205,47 @@ mod tests {
         let mut deps = mock_dependencies();
         let env = mock_env();
 
-        let msg = InstantiateMsg {
-            price: coin(1, "ubtsg"),
-            max_per_address: Some(1),
-            // creator: Some(String::from("creator")),
+        let mut msg = InstantiateMsg {
             payment_address: String::from(ROYALTIES_CONTRACT_ADDR),
             symbol: String::from(""),
             name: String::from(""),
             uri: String::from(""),
-            seller_fee_bps: 100,
-            referral_fee_bps: 1,
-            protocol_fee_bps: 3,
-            start_time: env.block.time,
-            party_type: PartyType::MaxEdition(1),
+            fee_split: royalties_fee_split(1, 3),
             bs721_code_id: BS721_CODE_ID,
-            bs721_admin: String::from("bs721_admin"),
+            ..default_instantiate_msg(&env)
         };
 
         let info = mock_info("creator", &[]);
+
+        {
+            msg.fee_split.push(FeeShare {
+                recipient: FeeRecipient::Address("label".to_string()),
+                bps: 100,
+            });
+            let err =
+                instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::FeeSplit(FeeSplitError::InvalidTotal { total: 10_100 }),
+                "expected to fail since the fee shares sum over 10000 bps"
+            );
+            msg.fee_split.pop();
+        }
+
+        {
+            msg.cw20_address = Some(String::from("cw20"));
+            msg.price = coin(1, "cw20");
+            let err =
+                instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Cw20RequiresFactory {},
+                "expected to fail since the protocol fee address is picked by the creator"
+            );
+            msg.cw20_address = None;
+            msg.price = default_instantiate_msg(&env).price;
+        }
+
         instantiate(deps.as_mut(), env, info, msg).unwrap();
     }
 
@@ -737,20 +3255,13 @@ mod tests {
         let env = mock_env();
 
         let msg = InstantiateMsg {
-            price: coin(1, "ubtsg"),
-            max_per_address: Some(1),
-            // creator: Some(String::from("creator")),
             symbol: String::from(""),
             name: String::from(""),
             uri: String::from(""),
-            seller_fee_bps: 100,
-            referral_fee_bps: 1,
-            protocol_fee_bps: 3,
-            start_time: env.block.time,
-            party_type: PartyType::MaxEdition(1),
+            fee_split: royalties_fee_split(1, 3),
             bs721_code_id: BS721_CODE_ID,
             payment_address: String::from(ROYALTIES_CONTRACT_ADDR),
-            bs721_admin: String::from("bs721_admin"),
+            ..default_instantiate_msg(&env)
         };
 
         let info = mock_info("creator", &[]);
@@ -814,16 +3325,33 @@ mod tests {
                 symbol: String::from(""),
                 name: String::from(""),
                 uri: String::from(""),
+                content_hash: None,
                 price: coin(1, "ubtsg"),
                 max_per_address: Some(1),
                 next_token_id: 1,
                 seller_fee_bps: 100,
-                referral_fee_bps: 1,
-                protocol_fee_bps: 3,
+                accrue_referrals: false,
+                fee_split: royalties_fee_split(1, 3),
                 start_time: env.block.time,
                 party_type: PartyType::MaxEdition(1),
+                paused: false,
+                ended: false,
+                randomized: false,
+                min_raise: None,
+                limit_target: MintLimitTarget::Payer,
+                mint_limits: None,
+                reserved_supply: 0,
+                dutch_auction: None,
+                burn_to_mint: None,
+                raffle: None,
+                token_gate: None,
+                reveal: None,
+                minter_handover: MinterHandover::Creator,
+                final_supply: None,
                 bs721_address: Some(Addr::unchecked(NFT_CONTRACT_ADDR)),
                 payment_address: Addr::unchecked(ROYALTIES_CONTRACT_ADDR),
+                cw20_address: None,
+                protocol_address: None,
             }
         );
     }
@@ -833,19 +3361,10 @@ mod tests {
         let mut deps = mock_dependencies();
         let env = mock_env();
         let msg = InstantiateMsg {
-            price: coin(1, "ubtsg"),
-            max_per_address: Some(1),
-            symbol: "LP".to_string(),
-            name: "Launchparty".to_string(),
             uri: String::from(""),
-            seller_fee_bps: 100,
-            referral_fee_bps: 100,
-            start_time: env.block.time,
-            party_type: PartyType::MaxEdition(1),
             bs721_code_id: 2,
-            protocol_fee_bps: 3,
             payment_address: String::from(ROYALTIES_CONTRACT_ADDR),
-            bs721_admin: String::from("bs721_admin"),
+            ..default_instantiate_msg(&env)
         };
 
         let info = mock_info("creator", &[coin(1, "ubtsg")]);
@@ -875,6 +3394,9 @@ mod tests {
         let msg = ExecuteMsg::Mint {
             referral: None,
             amount: 1,
+            allowlist: None,
+            recipient: None,
+            gate_tokens: None,
         };
         let info = mock_info(MOCK_CONTRACT_ADDR, &[coin(1, "ubtsg")]);
 
@@ -884,17 +3406,17 @@ mod tests {
             Bs721BaseExecuteMsg::<EditionMetadata, Empty>::Mint(MintMsg::<EditionMetadata> {
                 token_id: "1".to_string(),
                 extension: EditionMetadata {
-                    name: format!("{} #{}", "Launchparty".to_string(), "1".to_string()),
+                    name: format!("{} #{}", "Launchparty", "1"),
                     attributes: Some(vec![
                         Trait {
                             trait_type: "Edition".to_string(),
                             value: "1".to_string(),
-                            display_type: Some("number".to_string()),
+                            display_type: Some(DisplayType::Number),
                         },
                         Trait {
                             trait_type: "Max Editions".to_string(),
                             value: "1".to_string(),
-                            display_type: Some("number".to_string()),
+                            display_type: Some(DisplayType::Number),
                         },
                         Trait {
                             trait_type: "Edition Type".to_string(),
@@ -907,6 +3429,7 @@ mod tests {
                 payment_addr: Some(ROYALTIES_CONTRACT_ADDR.to_string()),
                 seller_fee_bps: Some(100),
                 token_uri: Some(String::from("")),
+                content_hash: None,
             });
 
         assert_eq!(
@@ -930,19 +3453,12 @@ mod tests {
         let mut deps = mock_dependencies();
         let env = mock_env();
         let msg = InstantiateMsg {
-            price: coin(1, "ubtsg"),
             max_per_address: Some(3),
-            symbol: "LP".to_string(),
-            name: "Launchparty".to_string(),
             uri: String::from(""),
-            seller_fee_bps: 100,
-            referral_fee_bps: 100,
-            start_time: env.block.time,
             party_type: PartyType::MaxEdition(3),
             bs721_code_id: 2,
-            protocol_fee_bps: 3,
             payment_address: String::from(ROYALTIES_CONTRACT_ADDR),
-            bs721_admin: String::from("bs721_admin"),
+            ..default_instantiate_msg(&env)
         };
 
         let info = mock_info("creator", &[coin(3, "ubtsg")]);
@@ -972,6 +3488,9 @@ mod tests {
         let msg = ExecuteMsg::Mint {
             referral: None,
             amount: 3,
+            allowlist: None,
+            recipient: None,
+            gate_tokens: None,
         };
         let info = mock_info(MOCK_CONTRACT_ADDR, &[coin(3, "ubtsg")]);
 
@@ -981,17 +3500,17 @@ mod tests {
             Bs721BaseExecuteMsg::<EditionMetadata, Empty>::Mint(MintMsg::<EditionMetadata> {
                 token_id: "1".to_string(),
                 extension: EditionMetadata {
-                    name: format!("{} #{}", "Launchparty".to_string(), "1".to_string()),
+                    name: format!("{} #{}", "Launchparty", "1"),
                     attributes: Some(vec![
                         Trait {
                             trait_type: "Edition".to_string(),
                             value: "1".to_string(),
-                            display_type: Some("number".to_string()),
+                            display_type: Some(DisplayType::Number),
                         },
                         Trait {
                             trait_type: "Max Editions".to_string(),
                             value: "3".to_string(),
-                            display_type: Some("number".to_string()),
+                            display_type: Some(DisplayType::Number),
                         },
                         Trait {
                             trait_type: "Edition Type".to_string(),
@@ -1004,6 +3523,7 @@ mod tests {
                 payment_addr: Some(ROYALTIES_CONTRACT_ADDR.to_string()),
                 seller_fee_bps: Some(100),
                 token_uri: Some(String::from("")),
+                content_hash: None,
             });
 
         assert_eq!(
@@ -1025,17 +3545,17 @@ mod tests {
             Bs721BaseExecuteMsg::<EditionMetadata, Empty>::Mint(MintMsg::<EditionMetadata> {
                 token_id: "2".to_string(),
                 extension: EditionMetadata {
-                    name: format!("{} #{}", "Launchparty".to_string(), "2".to_string()),
+                    name: format!("{} #{}", "Launchparty", "2"),
                     attributes: Some(vec![
                         Trait {
                             trait_type: "Edition".to_string(),
                             value: "2".to_string(),
-                            display_type: Some("number".to_string()),
+                            display_type: Some(DisplayType::Number),
                         },
                         Trait {
                             trait_type: "Max Editions".to_string(),
                             value: "3".to_string(),
-                            display_type: Some("number".to_string()),
+                            display_type: Some(DisplayType::Number),
                         },
                         Trait {
                             trait_type: "Edition Type".to_string(),
@@ -1048,6 +3568,7 @@ mod tests {
                 payment_addr: Some(ROYALTIES_CONTRACT_ADDR.to_string()),
                 seller_fee_bps: Some(100),
                 token_uri: Some(String::from("")),
+                content_hash: None,
             });
 
         assert_eq!(
@@ -1069,17 +3590,17 @@ mod tests {
             Bs721BaseExecuteMsg::<EditionMetadata, Empty>::Mint(MintMsg::<EditionMetadata> {
                 token_id: "3".to_string(),
                 extension: EditionMetadata {
-                    name: format!("{} #{}", "Launchparty".to_string(), "3".to_string()),
+                    name: format!("{} #{}", "Launchparty", "3"),
                     attributes: Some(vec![
                         Trait {
                             trait_type: "Edition".to_string(),
                             value: "3".to_string(),
-                            display_type: Some("number".to_string()),
+                            display_type: Some(DisplayType::Number),
                         },
                         Trait {
                             trait_type: "Max Editions".to_string(),
                             value: "3".to_string(),
-                            display_type: Some("number".to_string()),
+                            display_type: Some(DisplayType::Number),
                         },
                         Trait {
                             trait_type: "Edition Type".to_string(),
@@ -1092,6 +3613,7 @@ mod tests {
                 payment_addr: Some(ROYALTIES_CONTRACT_ADDR.to_string()),
                 seller_fee_bps: Some(100),
                 token_uri: Some(String::from("")),
+                content_hash: None,
             });
 
         assert_eq!(
@@ -1109,4 +3631,124 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn mint_token_msg_with_tier() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let gold = EditionTier {
+            name: "Gold".to_string(),
+            uri: Some("ipfs://gold".to_string()),
+            attributes: vec![Trait {
+                trait_type: "Signed".to_string(),
+                value: "Yes".to_string(),
+                display_type: None,
+            }],
+            assignment: TierAssignment::Range { start: 1, end: 1 },
+        };
+        let msg = InstantiateMsg {
+            max_per_address: None,
+            uri: String::from("ipfs://base"),
+            party_type: PartyType::Duration(100),
+            tiers: vec![gold.clone()],
+            bs721_code_id: 2,
+            payment_address: String::from(ROYALTIES_CONTRACT_ADDR),
+            ..default_instantiate_msg(&env)
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.bs721_address = Some(Addr::unchecked(NFT_CONTRACT_ADDR));
+
+        let mint_msg = |deps: &mut cosmwasm_std::OwnedDeps<_, _, _>, config: &Config| {
+            let (_, msg) = mint_token_msg(
+                deps.as_mut().storage,
+                &env,
+                config,
+                &info.sender,
+                &info.sender,
+            )
+            .unwrap();
+            let WasmMsg::Execute { msg, .. } = msg else {
+                panic!("expected an execute message");
+            };
+            match from_json(msg).unwrap() {
+                Bs721BaseExecuteMsg::<EditionMetadata, Empty>::Mint(mint) => mint,
+                _ => panic!("expected a mint message"),
+            }
+        };
+
+        let mint = mint_msg(&mut deps, &config);
+        assert_eq!(mint.token_uri, Some("ipfs://gold".to_string()));
+        let attributes = mint.extension.attributes.unwrap();
+        assert_eq!(
+            attributes[attributes.len() - 2..],
+            [
+                Trait {
+                    trait_type: "Tier".to_string(),
+                    value: "Gold".to_string(),
+                    display_type: None,
+                },
+                gold.attributes[0].clone(),
+            ]
+        );
+        assert_eq!(
+            query_token_tier(deps.as_ref(), 1).unwrap().tier,
+            Some(TierResponse {
+                tier_id: 0,
+                tier: gold
+            })
+        );
+
+        config.next_token_id = 2;
+        let mint = mint_msg(&mut deps, &config);
+        assert_eq!(
+            mint.token_uri,
+            Some("ipfs://base".to_string()),
+            "expected the launchparty uri out of the tiers"
+        );
+        let end_time = env.block.time.plus_seconds(100);
+        let mint_end = Trait {
+            trait_type: "Mint End".to_string(),
+            value: end_time.seconds().to_string(),
+            display_type: Some(DisplayType::Date),
+        };
+        assert_eq!(
+            mint.extension.attributes.unwrap()[1..],
+            [
+                Trait {
+                    trait_type: "Edition Type".to_string(),
+                    value: "Open Edition".to_string(),
+                    display_type: None,
+                },
+                mint_end.clone(),
+            ]
+        );
+        assert_eq!(query_token_tier(deps.as_ref(), 2).unwrap().tier, None);
+
+        config.next_token_id = 3;
+        config.party_type = PartyType::MaxEditionUntil {
+            max_editions: 5,
+            end_time,
+        };
+        let mint = mint_msg(&mut deps, &config);
+        assert_eq!(
+            mint.extension.attributes.unwrap()[1..],
+            [
+                Trait {
+                    trait_type: "Max Editions".to_string(),
+                    value: "5".to_string(),
+                    display_type: Some(DisplayType::Number),
+                },
+                Trait {
+                    trait_type: "Edition Type".to_string(),
+                    value: "Limited Edition".to_string(),
+                    display_type: None,
+                },
+                mint_end,
+            ],
+            "expected a limited edition with its mint end"
+        );
+    }
 }
@@ -1,4 +1,7 @@
-use cosmwasm_std::{StdError, Uint128};
+use bs721::MetadataError;
+use bs_mint_hooks::MintHookError;
+use bs_std::fees::FeeSplitError;
+use cosmwasm_std::{StdError, Timestamp, Uint128};
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -10,6 +13,15 @@ pub enum ContractError {
     #[error("{0}")]
     Payment(#[from] PaymentError),
 
+    #[error("{0}")]
+    MintHook(#[from] MintHookError),
+
+    #[error("{0}")]
+    FeeSplit(#[from] FeeSplitError),
+
+    #[error("{0}")]
+    Metadata(#[from] MetadataError),
+
     #[error("unauthorized")]
     Unauthorized {},
 
@@ -19,6 +31,9 @@ pub enum ContractError {
     #[error("party duration cannot be zero")]
     ZeroDuration {},
 
+    #[error("party end time must be after its start time")]
+    InvalidPartyEndTime {},
+
     #[error("BS721 contract already linked")]
     Bs721BaseAlreadyLinked {},
 
@@ -57,4 +72,311 @@ pub enum ContractError {
 
     #[error("max number of mint, remaining: {remaining}")]
     MaxPerAddressExceeded { remaining: u32 },
+
+    #[error("max {max} allowlist phases are allowed")]
+    TooManyAllowlistPhases { max: u32 },
+
+    #[error("allowlist phase must start before its end")]
+    InvalidAllowlistPhaseTime {},
+
+    #[error("allowlist phase price must be in the same denom of the public price")]
+    InvalidAllowlistPhaseDenom {},
+
+    #[error("merkle root must be a hex encoded sha256 hash")]
+    InvalidMerkleRoot {},
+
+    #[error("invalid merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("allowlist phase {phase_id} not found")]
+    AllowlistPhaseNotFound { phase_id: u32 },
+
+    #[error("allowlist phase {phase_id} is not active")]
+    AllowlistPhaseNotActive { phase_id: u32 },
+
+    #[error("allowlist phase {phase_id} already started")]
+    AllowlistPhaseStarted { phase_id: u32 },
+
+    #[error("allowlist allocation exceeded, remaining: {remaining}")]
+    AllowlistAllocationExceeded { remaining: u32 },
+
+    #[error("max {max} stages are allowed")]
+    TooManyStages { max: u32 },
+
+    #[error("stage must start before its end and only the last stage can be open ended")]
+    InvalidStageTime {},
+
+    #[error("stages must be ordered and not overlapping")]
+    StagesOverlap {},
+
+    #[error("stage price must be in the same denom of the public price")]
+    InvalidStageDenom {},
+
+    #[error("no active stage")]
+    NoActiveStage {},
+
+    #[error("stage supply exceeded, remaining: {remaining}")]
+    StageSupplyExceeded { remaining: u32 },
+
+    #[error("price denom must be the cw20 token address")]
+    InvalidCw20Denom {},
+
+    #[error("cw20 token not accepted")]
+    InvalidCw20Token {},
+
+    #[error("cw20 launchparty must be instantiated by the factory")]
+    Cw20RequiresFactory {},
+
+    #[error("randomized token assignment requires a max edition party")]
+    RandomizedRequiresMaxEdition {},
+
+    #[error("content hash cannot be shared by editions with different URIs")]
+    ContentHashWithDistinctUris {},
+
+    #[error("contracts cannot mint editions drawn at random")]
+    ContractRandomMint {},
+
+    #[error("launchparty is paused")]
+    Paused {},
+
+    #[error("launchparty is not paused")]
+    NotPaused {},
+
+    #[error("launchparty already started")]
+    AlreadyStarted {},
+
+    #[error("start time must be in the future")]
+    InvalidStartTime {},
+
+    #[error("price denom cannot be changed")]
+    InvalidPriceDenom {},
+
+    #[error("max per address cannot be zero")]
+    ZeroMaxPerAddress {},
+
+    #[error("stage {stage_id} not found")]
+    StageNotFound { stage_id: u32 },
+
+    #[error("stage {stage_id} already ended")]
+    StageEnded { stage_id: u32 },
+
+    #[error("crowdfund requires a party with an end time")]
+    CrowdfundRequiresEndTime {},
+
+    #[error("min raise cannot be zero")]
+    ZeroMinRaise {},
+
+    #[error("launchparty is not a crowdfund")]
+    NotCrowdfund {},
+
+    #[error("crowdfund not ended")]
+    CrowdfundNotEnded {},
+
+    #[error("crowdfund reached the min raise")]
+    MinRaiseReached {},
+
+    #[error("crowdfund did not reach the min raise")]
+    MinRaiseNotReached {},
+
+    #[error("crowdfund already released")]
+    AlreadyReleased {},
+
+    #[error("token {token_id} cannot be refunded")]
+    NotRefundable { token_id: String },
+
+    #[error("nothing to claim")]
+    NothingToClaim {},
+
+    #[error("reserved supply cannot exceed max editions")]
+    ReservedSupplyExceedsEditions {},
+
+    #[error("reserved supply exceeded, remaining: {remaining}")]
+    ReservedSupplyExceeded { remaining: u32 },
+
+    #[error("no recipients provided")]
+    NoRecipients {},
+
+    #[error("auction start price must be higher than the floor price")]
+    InvalidAuctionPrice {},
+
+    #[error("auction must start before its end")]
+    InvalidAuctionTime {},
+
+    #[error(
+        "auction step interval must be positive and the auction can have at most {max_steps} steps"
+    )]
+    InvalidAuctionStep { max_steps: u64 },
+
+    #[error("auction rebate cannot be used in a crowdfund")]
+    RebateWithCrowdfund {},
+
+    #[error("launchparty is not a dutch auction with rebate")]
+    NotAuctionRebate {},
+
+    #[error("party not ended")]
+    PartyNotEnded {},
+
+    #[error("auction already settled")]
+    AlreadySettled {},
+
+    #[error("redeem ratio must be between 1 and {max}")]
+    InvalidRedeemRatio { max: u32 },
+
+    #[error("token range must start before its end")]
+    InvalidTokenRange {},
+
+    #[error("burn to mint cannot be used in a crowdfund or a dutch auction")]
+    BurnToMintWithSale {},
+
+    #[error("launchparty is not a burn to mint")]
+    NotBurnToMint {},
+
+    #[error("editions can only be minted by redeeming tokens")]
+    BurnToMintOnly {},
+
+    #[error("token {token_id} is not eligible for redemption")]
+    TokenNotEligible { token_id: String },
+
+    #[error("no tokens pending redemption")]
+    NoPendingTokens {},
+
+    #[error("token gate requires between 1 and {max} collections")]
+    InvalidGateCollections { max: u32 },
+
+    #[error("gate discount must be positive and cannot exceed 10000 bps")]
+    InvalidGateDiscount {},
+
+    #[error("gate discount cannot be used in a dutch auction")]
+    GateDiscountWithAuction {},
+
+    #[error("launchparty is not token gated")]
+    NotTokenGated {},
+
+    #[error("minting requires a gate token for each edition")]
+    GateTokenRequired {},
+
+    #[error("gate tokens cannot be used during an allowlist phase")]
+    GateWithAllowlist {},
+
+    #[error("collection {collection} does not open the gate")]
+    InvalidGateCollection { collection: String },
+
+    #[error("gate token {token_id} is not owned by the sender")]
+    NotGateTokenOwner { token_id: String },
+
+    #[error("gate token {token_id} already claimed")]
+    GateTokenClaimed { token_id: String },
+
+    #[error("max {max} tiers are allowed")]
+    TooManyTiers { max: u32 },
+
+    #[error("tier ranges cannot overlap")]
+    TiersOverlap {},
+
+    #[error("tier weight cannot be zero")]
+    ZeroTierWeight {},
+
+    #[error("provenance hash must be a hex encoded sha256 hash")]
+    InvalidProvenanceHash {},
+
+    #[error("launchparty is not a delayed reveal")]
+    NotDelayedReveal {},
+
+    #[error("metadata already revealed")]
+    AlreadyRevealed {},
+
+    #[error("base uri does not match the provenance hash")]
+    InvalidReveal {},
+
+    #[error("metadata not revealed")]
+    NotRevealed {},
+
+    #[error("reveal already completed")]
+    RevealCompleted {},
+
+    #[error("reveal not completed")]
+    RevealNotCompleted {},
+
+    #[error("reserved editions not yet airdropped")]
+    ReservedSupplyPending {},
+
+    #[error("launchparty already finalized")]
+    AlreadyFinalized {},
+
+    #[error("raffle tickets per address must be between 1 and {max}")]
+    InvalidRaffleTickets { max: u32 },
+
+    #[error("beacon public key must be a hex encoded compressed secp256k1 public key")]
+    InvalidBeaconKey {},
+
+    #[error("raffle entries must end after the start of the launchparty")]
+    InvalidRaffleEntryEnd {},
+
+    #[error(
+        "raffle requires a party limited only by its maximum number of editions, not all reserved"
+    )]
+    RaffleRequiresMaxEdition {},
+
+    #[error("raffle cannot be combined with other sales")]
+    RaffleWithSale {},
+
+    #[error("launchparty is not a raffle")]
+    NotRaffle {},
+
+    #[error("editions can only be won in the raffle")]
+    RaffleOnly {},
+
+    #[error("raffle tickets cannot be zero")]
+    ZeroRaffleTickets {},
+
+    #[error("raffle entries closed")]
+    RaffleEntriesClosed {},
+
+    #[error("raffle entries still open")]
+    RaffleEntriesOpen {},
+
+    #[error("max tickets per address exceeded, remaining: {remaining}")]
+    MaxTicketsExceeded { remaining: u32 },
+
+    #[error("raffle not oversubscribed, every ticket wins")]
+    RaffleNotOversubscribed {},
+
+    #[error("raffle already drawn")]
+    RaffleAlreadyDrawn {},
+
+    #[error("raffle draw expired, every ticket is refunded")]
+    RaffleDrawExpired {},
+
+    #[error("beacon is not a valid VRF proof of the raffle beacon key")]
+    InvalidBeacon {},
+
+    #[error("raffle beacon not submitted")]
+    RaffleBeaconMissing {},
+
+    #[error("raffle not drawn")]
+    RaffleNotDrawn {},
+
+    #[error("no raffle tickets")]
+    NoRaffleTickets {},
+
+    #[error("raffle tickets already claimed")]
+    RaffleAlreadyClaimed {},
+
+    #[error("raffle winners have not claimed their editions yet")]
+    RaffleClaimsPending {},
+
+    #[error("mint limits, window duration and cooldown cannot be zero")]
+    InvalidMintLimits {},
+
+    #[error("max {max} editions per transaction")]
+    MaxPerTxExceeded { max: u32 },
+
+    #[error("max editions per block exceeded, remaining: {remaining}")]
+    MaxPerBlockExceeded { remaining: u32 },
+
+    #[error("max editions per window exceeded, remaining: {remaining}")]
+    MaxPerWindowExceeded { remaining: u32 },
+
+    #[error("mint cooldown, next mint allowed at {next_mint}")]
+    MintCooldown { next_mint: Timestamp },
 }
@@ -0,0 +1,453 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, Empty, QuerierWrapper, StdResult, Timestamp, Uint128,
+    WasmMsg, WasmQuery,
+};
+use cw20::Cw20ExecuteMsg;
+
+use bs721_base::ExecuteMsg as Bs721BaseExecuteMsg;
+use bs_controllers::HooksResponse;
+use serde::de::DeserializeOwned;
+
+use crate::msg::{
+    AllowlistMintedResponse, AllowlistPhasesResponse, AllowlistProof, CrowdfundResponse,
+    CurrentStageResponse, DutchAuctionResponse, ExecuteMsg, GateClaimResponse, GateToken,
+    MaxPerAddressResponse, NftReceiveMsg, QueryMsg, RaffleEntryResponse, RaffleResponse,
+    ReceiveMsg, RedeemPendingResponse, ReferralStatsResponse, RevealResponse, StatusResponse,
+    TiersResponse, TokenTierResponse, TopReferrersResponse,
+};
+use crate::state::Config;
+
+/// LaunchpartyContract is a wrapper around Addr that provides a lot of helpers
+#[cw_serde]
+pub struct LaunchpartyContract(pub Addr);
+
+impl LaunchpartyContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    pub fn call_with_funds<T: Into<ExecuteMsg>>(
+        &self,
+        msg: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&msg.into())?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds,
+        }
+        .into())
+    }
+
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        req: QueryMsg,
+    ) -> StdResult<T> {
+        let query = WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&req)?,
+        }
+        .into();
+        querier.query(&query)
+    }
+
+    /// Mints `amount` tokens, `funds` must cover the price of all of them.
+    pub fn mint(
+        &self,
+        amount: u32,
+        referral: Option<String>,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::Mint {
+                amount,
+                referral,
+                allowlist: None,
+                recipient: None,
+                gate_tokens: None,
+            },
+            funds,
+        )
+    }
+
+    /// Mints `amount` tokens to `recipient`, `funds` must cover the price of all of them.
+    pub fn mint_to(
+        &self,
+        amount: u32,
+        referral: Option<String>,
+        recipient: String,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::Mint {
+                amount,
+                referral,
+                allowlist: None,
+                recipient: Some(recipient),
+                gate_tokens: None,
+            },
+            funds,
+        )
+    }
+
+    /// Mints `amount` tokens during an allowlist phase, `funds` must cover the phase price of all
+    /// of them.
+    pub fn mint_allowlist(
+        &self,
+        amount: u32,
+        referral: Option<String>,
+        allowlist: AllowlistProof,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::Mint {
+                amount,
+                referral,
+                allowlist: Some(allowlist),
+                recipient: None,
+                gate_tokens: None,
+            },
+            funds,
+        )
+    }
+
+    /// Mints `amount` tokens opening the gate of the public mint with `gate_tokens`, `funds` must
+    /// cover the discounted price of all of them.
+    pub fn mint_gated(
+        &self,
+        amount: u32,
+        gate_tokens: Vec<GateToken>,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::Mint {
+                amount,
+                referral: None,
+                allowlist: None,
+                recipient: None,
+                gate_tokens: Some(gate_tokens),
+            },
+            funds,
+        )
+    }
+
+    /// Mints `amount` tokens sending `payment` of the cw20 token `cw20_address` to the launchparty.
+    pub fn mint_cw20<T: Into<String>>(
+        &self,
+        cw20_address: T,
+        payment: Uint128,
+        amount: u32,
+        referral: Option<String>,
+        allowlist: Option<AllowlistProof>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = Cw20ExecuteMsg::Send {
+            contract: self.addr().into(),
+            amount: payment,
+            msg: to_json_binary(&ReceiveMsg::Mint {
+                amount,
+                referral,
+                allowlist,
+                recipient: None,
+                gate_tokens: None,
+            })?,
+        };
+        Ok(WasmMsg::Execute {
+            contract_addr: cw20_address.into(),
+            msg: to_json_binary(&msg)?,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    /// Only callable by the creator, mints one reserved edition to each recipient.
+    pub fn airdrop_mint(&self, recipients: Vec<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::AirdropMint { recipients })
+    }
+
+    /// Only callable by the creator before the phase starts.
+    pub fn update_merkle_root(&self, phase_id: u32, merkle_root: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdateMerkleRoot {
+            phase_id,
+            merkle_root,
+        })
+    }
+
+    /// Only callable by the creator.
+    pub fn pause(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Pause {})
+    }
+
+    /// Only callable by the creator.
+    pub fn resume(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Resume {})
+    }
+
+    /// Only callable by the creator.
+    pub fn update_price(&self, price: Coin, stage_id: Option<u32>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdatePrice { price, stage_id })
+    }
+
+    /// Only callable by the creator.
+    pub fn update_max_per_address(&self, max_per_address: Option<u32>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdateMaxPerAddress { max_per_address })
+    }
+
+    /// Only callable by the creator before the launchparty starts.
+    pub fn update_start_time(&self, start_time: Timestamp) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdateStartTime { start_time })
+    }
+
+    /// Only callable by the creator.
+    pub fn end_party(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::EndParty {})
+    }
+
+    /// Sends the token `token_id` of the collection `bs721_address` to the launchparty to be
+    /// burned for a refund of its price. Only allowed if a crowdfund failed.
+    pub fn refund<T: Into<String>>(&self, bs721_address: T, token_id: T) -> StdResult<CosmosMsg> {
+        let msg = Bs721BaseExecuteMsg::<Empty, Empty>::SendNft {
+            contract: self.addr().into(),
+            token_id: token_id.into(),
+            msg: to_json_binary(&NftReceiveMsg::Refund {})?,
+        };
+        Ok(WasmMsg::Execute {
+            contract_addr: bs721_address.into(),
+            msg: to_json_binary(&msg)?,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    /// Sends the token `token_id` of the collection `collection` to the launchparty to be
+    /// redeemed. Only allowed in a burn-to-mint launchparty.
+    pub fn redeem<T: Into<String>>(&self, collection: T, token_id: T) -> StdResult<CosmosMsg> {
+        let msg = Bs721BaseExecuteMsg::<Empty, Empty>::SendNft {
+            contract: self.addr().into(),
+            token_id: token_id.into(),
+            msg: to_json_binary(&NftReceiveMsg::Redeem {})?,
+        };
+        Ok(WasmMsg::Execute {
+            contract_addr: collection.into(),
+            msg: to_json_binary(&msg)?,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    pub fn cancel_redeem(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::CancelRedeem {})
+    }
+
+    /// Releases the funds of a successful crowdfund, callable by anyone.
+    pub fn release(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Release {})
+    }
+
+    pub fn claim_referral(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ClaimReferral {})
+    }
+
+    pub fn claim_rebate(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ClaimRebate {})
+    }
+
+    pub fn settle_auction(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::SettleAuction {})
+    }
+
+    /// Only callable by the creator.
+    pub fn reveal<T: Into<String>>(&self, base_uri: T) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Reveal {
+            base_uri: base_uri.into(),
+        })
+    }
+
+    /// Continues the reveal, callable by anyone.
+    pub fn reveal_batch(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RevealBatch {})
+    }
+
+    /// Enters `tickets` raffle tickets, `funds` must cover the price of all of them.
+    pub fn enter_raffle(&self, tickets: u32, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        self.call_with_funds(ExecuteMsg::EnterRaffle { tickets }, funds)
+    }
+
+    /// Draws the raffle winners with the hex encoded beacon VRF proof, callable by anyone.
+    pub fn submit_beacon<T: Into<String>>(&self, proof: T) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::SubmitBeacon {
+            proof: proof.into(),
+        })
+    }
+
+    /// Draws the next batch of raffle winners, callable by anyone.
+    pub fn draw_raffle(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::DrawRaffle {})
+    }
+
+    pub fn claim_raffle(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ClaimRaffle {})
+    }
+
+    /// Finalizes the launchparty once over, callable by anyone.
+    pub fn finalize(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Finalize {})
+    }
+
+    /// Only callable by the creator.
+    pub fn add_premint_hook(&self, hook: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::AddPreMintHook { hook })
+    }
+
+    /// Only callable by the creator.
+    pub fn add_postmint_hook(&self, hook: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::AddPostMintHook { hook })
+    }
+
+    /// Only callable by the creator.
+    pub fn remove_premint_hook(&self, hook: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RemovePreMintHook { hook })
+    }
+
+    /// Only callable by the creator.
+    pub fn remove_postmint_hook(&self, hook: String) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RemovePostMintHook { hook })
+    }
+
+    pub fn crowdfund(&self, querier: &QuerierWrapper) -> StdResult<CrowdfundResponse> {
+        self.query(querier, QueryMsg::Crowdfund {})
+    }
+
+    pub fn status(&self, querier: &QuerierWrapper) -> StdResult<StatusResponse> {
+        self.query(querier, QueryMsg::Status {})
+    }
+
+    pub fn config(&self, querier: &QuerierWrapper) -> StdResult<Config> {
+        self.query(querier, QueryMsg::GetConfig {})
+    }
+
+    pub fn max_per_address<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<MaxPerAddressResponse> {
+        let req = QueryMsg::MaxPerAddress {
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn allowlist_phases(&self, querier: &QuerierWrapper) -> StdResult<AllowlistPhasesResponse> {
+        self.query(querier, QueryMsg::AllowlistPhases {})
+    }
+
+    pub fn allowlist_minted<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        phase_id: u32,
+        address: T,
+    ) -> StdResult<AllowlistMintedResponse> {
+        let req = QueryMsg::AllowlistMinted {
+            phase_id,
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn current_stage(&self, querier: &QuerierWrapper) -> StdResult<CurrentStageResponse> {
+        self.query(querier, QueryMsg::CurrentStage {})
+    }
+
+    pub fn dutch_auction(&self, querier: &QuerierWrapper) -> StdResult<DutchAuctionResponse> {
+        self.query(querier, QueryMsg::DutchAuction {})
+    }
+
+    pub fn referral_stats<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<ReferralStatsResponse> {
+        let req = QueryMsg::ReferralStats {
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn top_referrers(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TopReferrersResponse> {
+        self.query(querier, QueryMsg::TopReferrers { start_after, limit })
+    }
+
+    pub fn tiers(&self, querier: &QuerierWrapper) -> StdResult<TiersResponse> {
+        self.query(querier, QueryMsg::Tiers {})
+    }
+
+    pub fn token_tier(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: u32,
+    ) -> StdResult<TokenTierResponse> {
+        self.query(querier, QueryMsg::TokenTier { token_id })
+    }
+
+    pub fn gate_claim<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        collection: T,
+        token_id: T,
+    ) -> StdResult<GateClaimResponse> {
+        let req = QueryMsg::GateClaim {
+            collection: collection.into(),
+            token_id: token_id.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn redeem_pending<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<RedeemPendingResponse> {
+        let req = QueryMsg::RedeemPending {
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn reveal_state(&self, querier: &QuerierWrapper) -> StdResult<RevealResponse> {
+        self.query(querier, QueryMsg::Reveal {})
+    }
+
+    pub fn raffle(&self, querier: &QuerierWrapper) -> StdResult<RaffleResponse> {
+        self.query(querier, QueryMsg::Raffle {})
+    }
+
+    pub fn raffle_entry<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<RaffleEntryResponse> {
+        let req = QueryMsg::RaffleEntry {
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn premint_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::PreMintHooks {})
+    }
+
+    pub fn postmint_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::PostMintHooks {})
+    }
+}
@@ -1,9 +1,14 @@
+mod allowlist;
 pub mod contract;
 mod error;
+pub mod helpers;
 pub mod msg;
+mod raffle;
+mod shuffle;
 pub mod state;
 
 #[cfg(test)]
 mod multitest;
 
 pub use crate::error::ContractError;
+pub use crate::helpers::LaunchpartyContract;
@@ -1,7 +1,18 @@
+use bs721::{validate_content_hash, Bs721ReceiveMsg};
+use bs_controllers::HooksResponse;
+use bs_mint_hooks::{sg_mint_hooks_execute, sg_mint_hooks_query};
+use bs_std::fees::{FeeShare, MAX_BPS};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Coin, Env, Timestamp};
+use cosmwasm_std::{Addr, Coin, Env, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
+use sha2::{Digest, Sha256};
 
-use crate::{state::Config, ContractError};
+use crate::{
+    allowlist::validate_merkle_root,
+    raffle::decode_point,
+    state::{Config, Trait},
+    ContractError,
+};
 
 /// Possible launchparty type. Each type defines how the party end.
 #[cw_serde]
@@ -10,6 +21,223 @@ pub enum PartyType {
     MaxEdition(u32),
     /// Number of seconds after the launchparty start_time.
     Duration(u32),
+    /// Time after which no more tokens can be minted.
+    EndTime(Timestamp),
+    /// Maximum number of mintable tokens and end time, the party ends at the first reached.
+    MaxEditionUntil {
+        max_editions: u32,
+        end_time: Timestamp,
+    },
+}
+
+/// Address the per address limits are applied to when tokens are minted to a recipient.
+#[cw_serde]
+pub enum MintLimitTarget {
+    /// Limits are applied to the address paying for the mint.
+    Payer,
+    /// Limits are applied to the address receiving the tokens.
+    Recipient,
+}
+
+/// Maximum editions an address can mint within a window. The window starts at the first mint of
+/// the address and restarts at its first mint once elapsed.
+#[cw_serde]
+pub struct MintWindow {
+    pub max_editions: u32,
+    /// Duration of the window in seconds.
+    pub duration: u64,
+}
+
+/// Limits of the mint against bots draining the launchparty. The per address limits are applied
+/// as defined by the `MintLimitTarget`.
+#[cw_serde]
+pub struct MintLimits {
+    /// Maximum editions minted in a single block, by all the addresses.
+    pub max_per_block: Option<u32>,
+    /// Maximum editions minted in a single transaction.
+    pub max_per_tx: Option<u32>,
+    /// Maximum editions an address can mint within a window.
+    pub max_per_window: Option<MintWindow>,
+    /// Seconds an address must wait between two mints.
+    pub cooldown: Option<u64>,
+}
+
+/// Phase in which only the addresses included in the allowlist Merkle tree can mint.
+#[cw_serde]
+pub struct AllowlistPhase {
+    /// Hex encoded Merkle root of the allowlist. Each leaf is the sha256 of the address
+    /// concatenated with its allocation, e.g. `sha256("bitsong1...5")`.
+    pub merkle_root: String,
+    /// Start time of the phase.
+    pub start_time: Timestamp,
+    /// End time of the phase, minting is not allowed from this time on.
+    pub end_time: Timestamp,
+    /// Price of single nft minting during the phase. Must be in the same denom of the public price.
+    pub price: Coin,
+}
+
+/// Stage of the public mint. Stages are held in order, each one with its own price and limits.
+#[cw_serde]
+pub struct MintStage {
+    /// Name of the stage, e.g. `og`, `presale` or `public`.
+    pub name: String,
+    /// Start time of the stage.
+    pub start_time: Timestamp,
+    /// End time of the stage, minting is not allowed from this time on. Only the last stage can
+    /// be open ended.
+    pub end_time: Option<Timestamp>,
+    /// Price of single nft minting during the stage. Must be in the same denom of the public price.
+    pub price: Coin,
+    /// Maximum amount of tokens an address can mint during the stage.
+    pub max_per_address: Option<u32>,
+    /// Maximum amount of tokens that can be minted during the stage.
+    pub max_supply: Option<u32>,
+}
+
+/// How the price of a dutch auction decreases over time.
+#[cw_serde]
+pub enum AuctionDecay {
+    /// The price decreases continuously from the start to the end of the auction.
+    Linear,
+    /// The price decreases by the same amount every `interval` seconds.
+    Step { interval: u64 },
+}
+
+/// Dutch auction of the public mint. The price starts from `start_price` and decreases toward
+/// `floor_price`, reached at `end_time`.
+#[cw_serde]
+pub struct DutchAuction {
+    pub start_price: Uint128,
+    pub floor_price: Uint128,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub decay: AuctionDecay,
+    /// If true, once the party is over buyers are refunded down to the clearing price, which is
+    /// the price paid by the last buyer.
+    pub rebate: bool,
+}
+
+/// How a tier is assigned to the minted editions.
+#[cw_serde]
+pub enum TierAssignment {
+    /// Editions with token ID in the inclusive range belong to the tier.
+    Range { start: u32, end: u32 },
+    /// Editions not in any range are drawn among the weighted tiers, with probability
+    /// proportional to the weight.
+    Weight(u32),
+}
+
+/// Tier of the editions, such as signed first editions, with its own artwork and attributes.
+#[cw_serde]
+pub struct EditionTier {
+    /// Name of the tier, recorded in the `Tier` trait of the editions.
+    pub name: String,
+    /// Token URI of the editions of the tier. If not provided, the launchparty URI is used.
+    pub uri: Option<String>,
+    /// Additional traits of the editions of the tier.
+    pub attributes: Vec<Trait>,
+    pub assignment: TierAssignment,
+}
+
+/// Token gate of the public mint, restricting it or giving a discount to the holders of tokens of
+/// other collections. Each gate token can be used to mint a single edition.
+#[cw_serde]
+pub struct TokenGate {
+    /// Addresses of the bs721 collections whose tokens open the gate.
+    pub collections: Vec<String>,
+    /// If true, only holders can mint during the public mint.
+    pub holders_only: bool,
+    /// Discount, in basis points of the price, on the editions minted with a gate token.
+    pub discount_bps: u16,
+}
+
+/// Token used to open the gate of the public mint.
+#[cw_serde]
+pub struct GateToken {
+    pub collection: String,
+    pub token_id: String,
+}
+
+/// What happens to the tokens redeemed in a burn-to-mint launchparty.
+#[cw_serde]
+pub enum RedeemAction {
+    /// Redeemed tokens are burned.
+    Burn,
+    /// Redeemed tokens are held by the launchparty.
+    Hold,
+}
+
+/// Inclusive range of token IDs.
+#[cw_serde]
+pub struct TokenRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Burn-to-mint mode of the launchparty. Editions are not sold but redeemed by sending tokens of
+/// another collection through `SendNft`, with a `Redeem` message.
+#[cw_serde]
+pub struct BurnToMint {
+    /// Address of the collection whose tokens are redeemed.
+    pub collection: String,
+    /// Number of tokens to send for each minted edition.
+    pub ratio: u32,
+    /// Token IDs that can be redeemed. If empty, all the tokens of the collection are eligible.
+    pub eligible_ranges: Vec<TokenRange>,
+    pub action: RedeemAction,
+}
+
+/// Raffle mode of the launchparty. Editions are not minted on a first come first served basis:
+/// from the launchparty `start_time` to `entry_end_time` addresses enter the raffle depositing
+/// the price of each ticket. Once the entries are closed the winning tickets are drawn from the
+/// randomness beacon, and each address claims the editions won and the refund of the other
+/// tickets.
+#[cw_serde]
+pub struct Raffle {
+    /// End of the entry period.
+    pub entry_end_time: Timestamp,
+    /// Maximum number of tickets an address can enter.
+    pub max_tickets_per_address: u32,
+    /// Hex encoded compressed secp256k1 public key of the randomness beacon. The beacon is the
+    /// ECVRF-SECP256K1-SHA256-TAI proof of the hash chained over the entries, returned by the
+    /// `Raffle` query once the entries are closed. The VRF output is unique, so the beacon cannot
+    /// choose among different draws.
+    pub beacon_public_key: String,
+}
+
+/// Delayed reveal of the editions. Editions are minted with a placeholder URI, and the creator
+/// commits to the final metadata with its provenance hash until the reveal.
+#[cw_serde]
+pub struct DelayedReveal {
+    /// Token URI of the editions until the reveal.
+    pub placeholder_uri: String,
+    /// Hex encoded sha256 of the base URI of the final metadata, e.g. `sha256("ipfs://Qm...")`.
+    /// Only the URI string is committed, so the commitment binds the metadata only if the URI is
+    /// content-addressed, like an IPFS CID or an Arweave transaction.
+    pub provenance_hash: String,
+    /// If true, a random offset shifts the metadata assigned to the editions. The offset is derived
+    /// from the seed mixed by every mint, and is fixed once the mints are over.
+    pub random_offset: bool,
+}
+
+/// What happens to the minter role of the collection when the launchparty is finalized.
+#[cw_serde]
+pub enum MinterHandover {
+    /// The minter role is handed to the creator.
+    Creator,
+    /// The minter role is renounced, no more editions can ever be minted.
+    Renounce,
+}
+
+/// Proof that the sender belongs to the allowlist of a phase.
+#[cw_serde]
+pub struct AllowlistProof {
+    /// Identifier of the phase, given by its position in the instantiation message.
+    pub phase_id: u32,
+    /// Maximum amount of tokens the sender can mint during the phase.
+    pub allocation: u32,
+    /// Hex encoded hashes of the Merkle proof, from the leaf up to the root.
+    pub proof: Vec<String>,
 }
 
 /// Structure required by the launchparty-fixed contract during its instantiation.
@@ -21,28 +249,82 @@ pub struct InstantiateMsg {
     pub name: String,
     /// BS721 Uri
     pub uri: String,
-    /// Price of single nft minting.
+    /// Hash of the content shared by every edition, as hex encoded sha256 digest or base32 CIDv1,
+    /// stored on each minted token. Not allowed when editions point to different URIs, with
+    /// randomized IDs, tiers with their own URI or a delayed reveal.
+    pub content_hash: Option<String>,
+    /// Price of single nft minting. If `cw20_address` is provided the denom must be the address
+    /// of the cw20 token.
     pub price: Coin,
     /// Maximum amount of tokens an address can mint.
     pub max_per_address: Option<u32>,
+    /// Address the per address limits are applied to when minting to a recipient.
+    pub limit_target: MintLimitTarget,
+    /// If provided, limits minting per block, per transaction and per address over time, both in
+    /// the allowlist phases and in the public mint.
+    pub mint_limits: Option<MintLimits>,
+    /// Editions reserved for the creator airdrops, not mintable by the public.
+    pub reserved_supply: u32,
+    /// Address of the cw20 token used to pay for mints. If not provided, mints are paid in native
+    /// coins. The cw20 protocol fee is paid to `bs721_admin`, so only the factory can instantiate
+    /// a cw20 launchparty.
+    pub cw20_address: Option<String>,
     /// Payment address for the royalties.
     pub payment_address: String,
     /// Basis per point of the `price` sent to the royalties address during mint or burn.
     pub seller_fee_bps: u16,
-    /// Basis per point of the `price` sent to the referred address during mint or burn.
-    pub referral_fee_bps: u16,
-    /// Basis per point of the `price` sent to the community pool during mint or burn.
-    pub protocol_fee_bps: u16,
+    /// Split of the mint price among the royalties, the referral, the protocol and any other
+    /// address such as a label or a charity. The shares must sum to 10000 bps.
+    pub fee_split: Vec<FeeShare>,
+    /// If true, referral fees are accrued in the contract and claimed by each referral with
+    /// `ClaimReferral`, instead of being sent on every mint.
+    pub accrue_referrals: bool,
     /// Start time of the launchparty.
     pub start_time: Timestamp,
     /// End condition of the collection launchparty.
     pub party_type: PartyType,
+    /// If true, minters receive a random not yet minted index of the collection as token ID, with
+    /// the token URI `{uri}/{index}.json`. Requires a party with a maximum number of editions.
+    /// Contracts cannot mint editions drawn at random.
+    pub randomized: bool,
+    /// If provided the launchparty is a crowdfund: mint funds are escrowed in the contract and
+    /// released only if this amount is raised by the end of the party, otherwise minters can
+    /// burn their tokens for a full refund. Requires a party with an end time.
+    pub min_raise: Option<Uint128>,
     /// Code id used to instantiate a bs721 metadata onchain token contract.
     pub bs721_code_id: u64,
     pub bs721_admin: String,
+    /// Creator of the collection, allowed to manage the launchparty. If not provided it will be
+    /// the sender.
+    pub creator: Option<String>,
+    /// Allowlist phases of the launchparty, usually held before the public `start_time`.
+    pub allowlist_phases: Vec<AllowlistPhase>,
+    /// Ordered stages of the public mint. If empty, a single stage starting at `start_time` with
+    /// the public `price` is used.
+    pub stages: Vec<MintStage>,
+    /// If provided, the price of the public mint is given by the dutch auction instead of the
+    /// stages price.
+    pub dutch_auction: Option<DutchAuction>,
+    /// If provided, editions can only be minted by redeeming tokens of another collection.
+    pub burn_to_mint: Option<BurnToMint>,
+    /// If provided, editions can only be won in the raffle. Requires a party limited only by its
+    /// maximum number of editions, and cannot be combined with other sales.
+    pub raffle: Option<Raffle>,
+    /// If provided, the public mint is restricted or discounted to the holders of tokens of other
+    /// collections.
+    pub token_gate: Option<TokenGate>,
+    /// Tiers of the editions. Editions in no tier get the launchparty URI and attributes.
+    pub tiers: Vec<EditionTier>,
+    /// If provided, editions are minted with a placeholder URI until the creator reveals the
+    /// final metadata. The reveal replaces the URI of every edition, tier URIs included.
+    pub reveal: Option<DelayedReveal>,
+    /// What happens to the minter role of the collection when the launchparty is finalized.
+    pub minter_handover: MinterHandover,
 }
 
-/// Possible state-changing messages that the launchparty-fixed contract can handle.
+/// Possible state-changing messages that the launchparty-fixed contract can handle. Mint hooks
+/// can be added and removed only by the creator.
+#[sg_mint_hooks_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Allows to mint a bs721 token and, optionally, to refer an address.
@@ -52,10 +334,122 @@ pub enum ExecuteMsg {
         amount: u32,
         /// Referral address used for minting.
         referral: Option<String>,
+        /// Proof required to mint during an allowlist phase. If not provided the public mint is used.
+        allowlist: Option<AllowlistProof>,
+        /// Address receiving the tokens. If not provided the tokens are minted to the sender.
+        recipient: Option<String>,
+        /// Tokens owned by the sender opening the gate of the public mint, one for each edition.
+        gate_tokens: Option<Vec<GateToken>>,
+    },
+    /// Mints for free one of the reserved editions to each recipient. Only the creator can
+    /// airdrop.
+    AirdropMint { recipients: Vec<String> },
+    /// Allows to mint paying with the cw20 token accepted by the launchparty. The message wrapped
+    /// in the `Cw20ReceiveMsg` must be a `ReceiveMsg`.
+    Receive(Cw20ReceiveMsg),
+    /// Replaces the Merkle root of an allowlist phase. Only the creator can update it, and only
+    /// before the phase starts.
+    UpdateMerkleRoot { phase_id: u32, merkle_root: String },
+    /// Pauses the minting. Only the creator can pause the launchparty.
+    Pause {},
+    /// Resumes the minting. Only the creator can resume the launchparty.
+    Resume {},
+    /// Updates the mint price. Only the creator can update it, and the denom cannot change.
+    UpdatePrice {
+        price: Coin,
+        /// Stage to update. If not provided, the public price and the price of all the stages
+        /// not yet ended are updated.
+        stage_id: Option<u32>,
     },
+    /// Updates the maximum amount of tokens an address can mint. Only the creator can update it.
+    UpdateMaxPerAddress { max_per_address: Option<u32> },
+    /// Moves the start time of the launchparty, all its stages, its dutch auction, the end of its
+    /// raffle entries and its end time by the same offset. Only the creator can move it, and only before the launchparty starts.
+    UpdateStartTime { start_time: Timestamp },
+    /// Ends the launchparty, no more tokens can be minted. Only the creator can end it.
+    EndParty {},
+    /// Handles the tokens sent to the launchparty. The message wrapped in the `Bs721ReceiveMsg`
+    /// must be a `NftReceiveMsg`.
+    ReceiveNft(Bs721ReceiveMsg),
+    /// Releases the escrowed funds of a crowdfund that reached its minimum raise. Callable by
+    /// anyone once the party is ended.
+    Release {},
+    /// Sends to the sender the accrued referral fees, the referral fees escrowed during a
+    /// crowdfund once released, and the referral fees on the amount held for the rebate of a
+    /// dutch auction once settled.
+    ClaimReferral {},
+    /// Refunds to the sender the amount paid over the clearing price of a dutch auction with
+    /// rebate. Allowed only once the party is over.
+    ClaimRebate {},
+    /// Pays the royalties and protocol fee on the amount held for the rebate of a dutch auction,
+    /// at the clearing price. The referral fees on it are claimed with `ClaimReferral`. Callable
+    /// by anyone once the party is over.
+    SettleAuction {},
+    /// Returns to the sender the tokens sent for a burn-to-mint redemption not yet completed.
+    CancelRedeem {},
+    /// Reveals the final metadata of a delayed reveal launchparty, whose sha256 must match the
+    /// provenance hash. The edition `id` gets the URI `{base_uri}/{index}.json`, with `index`
+    /// shifted by the random offset if any. Only the creator can reveal, once the party is over.
+    Reveal { base_uri: String },
+    /// Updates the URI of the next batch of editions not yet revealed. Callable by anyone until
+    /// the reveal is completed.
+    RevealBatch {},
+    /// Enters `tickets` tickets in the raffle, depositing the price of each one. Allowed only
+    /// during the entry period.
+    EnterRaffle { tickets: u32 },
+    /// Draws the first batch of winning tickets of the raffle from the beacon, the hex encoded
+    /// VRF proof of the hash chained over the entries: the compressed point Gamma, the 16 bytes
+    /// challenge and the 32 bytes scalar. Callable by anyone relaying the beacon once the entries
+    /// are closed, if the tickets exceed the editions.
+    SubmitBeacon { proof: String },
+    /// Draws the next batch of winning tickets of the raffle, callable by anyone until every
+    /// edition has its winning ticket.
+    DrawRaffle {},
+    /// Mints to the sender the editions won in the raffle and refunds the other tickets. If the
+    /// beacon is not submitted in time, or the launchparty is finalized before the claim, all
+    /// the tickets are refunded.
+    ClaimRaffle {},
+    /// Hands over the minter role of the collection, records the final supply and opens the
+    /// trading of the collection. Callable by anyone once the party is over, the reveal is
+    /// completed and the reserved editions are airdropped. The creator can finalize without
+    /// airdropping the reserved editions, which are forfeited. A raffle must be drawn and its
+    /// winners must have claimed, unless 37 days have passed since the end of the entries.
+    Finalize {},
+}
+
+/// Messages that can be sent along with cw20 tokens to the launchparty.
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Mints bs721 tokens paying with the sent cw20 tokens.
+    Mint {
+        /// Amount of token to mint.
+        amount: u32,
+        /// Referral address used for minting.
+        referral: Option<String>,
+        /// Proof required to mint during an allowlist phase. If not provided the public mint is used.
+        allowlist: Option<AllowlistProof>,
+        /// Address receiving the tokens. If not provided the tokens are minted to the sender.
+        recipient: Option<String>,
+        /// Tokens owned by the sender opening the gate of the public mint, one for each edition.
+        gate_tokens: Option<Vec<GateToken>>,
+    },
+    /// Enters raffle tickets paying with the sent cw20 tokens.
+    EnterRaffle { tickets: u32 },
+}
+
+/// Messages that can be sent along with a bs721 token to the launchparty.
+#[cw_serde]
+pub enum NftReceiveMsg {
+    /// Burns the token refunding its price to the sender. Allowed only when a crowdfund ended
+    /// without reaching its minimum raise.
+    Refund {},
+    /// Redeems the token in a burn-to-mint launchparty. Once the sender has sent the number of
+    /// tokens required by the ratio, an edition is minted to the sender.
+    Redeem {},
 }
 
 /// Possible query messages that the launchparty-fixed contract can handle.
+#[sg_mint_hooks_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -63,48 +457,723 @@ pub enum QueryMsg {
     #[returns(Config)]
     GetConfig {},
 
-    /// Returns the maximum amount of token an address can mint.
-    #[returns(MaxPerAddressResponse)]
-    MaxPerAddress { address: String },
+    /// Returns the maximum amount of token an address can mint.
+    #[returns(MaxPerAddressResponse)]
+    MaxPerAddress { address: String },
+
+    /// Returns all the allowlist phases.
+    #[returns(AllowlistPhasesResponse)]
+    AllowlistPhases {},
+
+    /// Returns the amount of tokens minted by an address during an allowlist phase.
+    #[returns(AllowlistMintedResponse)]
+    AllowlistMinted { phase_id: u32, address: String },
+
+    /// Returns the stage of the public mint active at the current time, if any.
+    #[returns(CurrentStageResponse)]
+    CurrentStage {},
+
+    /// Returns the state of the crowdfund.
+    #[returns(CrowdfundResponse)]
+    Crowdfund {},
+
+    /// Returns a summary of the launchparty state.
+    #[returns(StatusResponse)]
+    Status {},
+
+    /// Returns the dutch auction with its price curve and current price.
+    #[returns(DutchAuctionResponse)]
+    DutchAuction {},
+
+    /// Returns the referral activity of an address.
+    #[returns(ReferralStatsResponse)]
+    ReferralStats { address: String },
+
+    /// Returns the referral addresses sorted by amount earned, from the highest.
+    #[returns(TopReferrersResponse)]
+    TopReferrers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns all the edition tiers.
+    #[returns(TiersResponse)]
+    Tiers {},
+
+    /// Returns the tier of a minted edition, if any.
+    #[returns(TokenTierResponse)]
+    TokenTier { token_id: u32 },
+
+    /// Returns the address that used a token to open the gate of the public mint, if any.
+    #[returns(GateClaimResponse)]
+    GateClaim {
+        collection: String,
+        token_id: String,
+    },
+
+    /// Returns the tokens sent by an address for a burn-to-mint redemption not yet completed.
+    #[returns(RedeemPendingResponse)]
+    RedeemPending { address: String },
+
+    /// Returns the state of the delayed reveal.
+    #[returns(RevealResponse)]
+    Reveal {},
+
+    /// Returns the state of the raffle.
+    #[returns(RaffleResponse)]
+    Raffle {},
+
+    /// Returns the tickets entered in the raffle by an address and the editions won.
+    #[returns(RaffleEntryResponse)]
+    RaffleEntry { address: String },
+}
+
+#[cw_serde]
+pub struct MaxPerAddressResponse {
+    /// Returns the maximum amount of token an address can mint.
+    pub remaining: Option<u32>,
+}
+
+#[cw_serde]
+pub struct AllowlistPhasesResponse {
+    pub phases: Vec<AllowlistPhaseResponse>,
+}
+
+#[cw_serde]
+pub struct AllowlistPhaseResponse {
+    pub phase_id: u32,
+    pub phase: AllowlistPhase,
+}
+
+#[cw_serde]
+pub struct AllowlistMintedResponse {
+    pub minted: u32,
+}
+
+#[cw_serde]
+pub struct StatusResponse {
+    /// True if tokens can be minted at the current time.
+    pub active: bool,
+    /// True if the minting is paused by the creator.
+    pub paused: bool,
+    /// Number of tokens minted.
+    pub minted: u32,
+    /// Number of tokens that can still be minted by the public in a party with a maximum number of
+    /// editions.
+    pub remaining: Option<u32>,
+    /// Editions reserved for airdrops not yet minted.
+    pub reserved: u32,
+    /// End time of a party limited in time.
+    pub end_time: Option<Timestamp>,
+    /// Total amount paid by minters, net of refunds.
+    pub raised: Coin,
+    /// Address of the bs721 token contract.
+    pub bs721_address: Option<Addr>,
+    /// Number of editions of the collection, recorded when the launchparty is finalized.
+    pub final_supply: Option<u32>,
+}
+
+#[cw_serde]
+pub struct ReferralStatsResponse {
+    pub address: Addr,
+    /// Number of tokens minted with the address as referral.
+    pub mints: u32,
+    /// Total referral fees earned.
+    pub earned: Coin,
+    /// Referral fees that can currently be claimed with `ClaimReferral`.
+    pub claimable: Coin,
+}
+
+#[cw_serde]
+pub struct TopReferrersResponse {
+    pub referrers: Vec<ReferralStatsResponse>,
+}
+
+#[cw_serde]
+pub struct TiersResponse {
+    pub tiers: Vec<TierResponse>,
+}
+
+#[cw_serde]
+pub struct TierResponse {
+    pub tier_id: u32,
+    pub tier: EditionTier,
+}
+
+#[cw_serde]
+pub struct TokenTierResponse {
+    pub tier: Option<TierResponse>,
+}
+
+#[cw_serde]
+pub struct GateClaimResponse {
+    pub claimed_by: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct RedeemPendingResponse {
+    pub token_ids: Vec<String>,
+    /// Number of tokens still to send to mint an edition.
+    pub missing: u32,
+}
+
+#[cw_serde]
+pub struct RevealResponse {
+    /// None if the launchparty is not a delayed reveal.
+    pub reveal: Option<DelayedReveal>,
+    /// Base URI of the final metadata, once revealed.
+    pub base_uri: Option<String>,
+    pub offset: Option<u32>,
+    /// True if the URI of every edition has been updated.
+    pub completed: bool,
+}
+
+#[cw_serde]
+pub struct RaffleResponse {
+    /// None if the launchparty is not a raffle.
+    pub raffle: Option<Raffle>,
+    /// Number of tickets entered.
+    pub tickets: u32,
+    /// Number of editions won in the raffle.
+    pub editions: u32,
+    /// Hex encoded input of the beacon VRF, once the entries are closed.
+    pub beacon_message: Option<String>,
+    /// Hex encoded VRF proof of the beacon, once submitted.
+    pub beacon: Option<String>,
+    /// Hex encoded VRF output of the beacon, the seed of the draw.
+    pub seed: Option<String>,
+    /// True if every winning ticket is drawn.
+    pub drawn: bool,
+    /// Number of winning tickets whose editions have been claimed.
+    pub claimed: u32,
+}
+
+#[cw_serde]
+pub struct RaffleEntryResponse {
+    /// Tickets of the address, identified by their entry order.
+    pub tickets: Vec<u32>,
+    /// Total amount deposited for the tickets.
+    pub deposit: Uint128,
+    /// Number of tickets won, once the winning tickets are known.
+    pub won: Option<u32>,
+    pub claimed: bool,
+}
+
+#[cw_serde]
+pub struct DutchAuctionResponse {
+    /// None if the public mint is not a dutch auction.
+    pub auction: Option<DutchAuction>,
+    /// Points of the price curve, the price is linear between two points.
+    pub curve: Vec<AuctionPricePoint>,
+    pub current_price: Option<Uint128>,
+    /// Price paid by the last buyer.
+    pub clearing_price: Option<Uint128>,
+    /// Number of tokens sold during the auction.
+    pub sold: u32,
+}
+
+#[cw_serde]
+pub struct AuctionPricePoint {
+    pub time: Timestamp,
+    pub price: Uint128,
+}
+
+#[cw_serde]
+pub struct CrowdfundResponse {
+    /// Minimum amount to raise. None if the launchparty is not a crowdfund.
+    pub min_raise: Option<Uint128>,
+    /// Amount currently escrowed in the contract.
+    pub raised: Uint128,
+    /// True if the escrowed funds have been released.
+    pub released: bool,
+}
+
+#[cw_serde]
+pub struct CurrentStageResponse {
+    pub stage: Option<StageResponse>,
+}
+
+#[cw_serde]
+pub struct StageResponse {
+    pub stage_id: u32,
+    pub stage: MintStage,
+    /// Amount of tokens minted during the stage.
+    pub minted: u32,
+}
+
+impl InstantiateMsg {
+    const MAX_FEE_BPS: u16 = 10_000;
+    const MAX_ALLOWLIST_PHASES: usize = 10;
+    const MAX_STAGES: usize = 10;
+    const MAX_TIERS: usize = 20;
+
+    /// Performs basic validation checks on the InstantiateMsg type.
+    ///
+    /// # Validation Checks:
+    ///
+    /// - start time must be in the future.
+    /// - maximum bps allowed for the seller.
+    /// - content hash, only for editions sharing the same URI.
+    /// - mint limits.
+    /// - end condition of the launchparty.
+    /// - allowlist phases.
+    /// - stages are valid, ordered and not overlapping.
+    /// - dutch auction.
+    /// - burn to mint, not allowed in a crowdfund or a dutch auction.
+    /// - raffle, only in a party limited by its maximum number of editions and without other
+    ///   sales.
+    /// - token gate, its discount is not allowed in a dutch auction.
+    /// - tiers are valid and their ranges not overlapping.
+    /// - delayed reveal.
+    pub fn validate(&self, env: Env) -> Result<(), ContractError> {
+        // validate seller_fee_bps
+        if self.seller_fee_bps > Self::MAX_FEE_BPS {
+            return Err(ContractError::FeeBps {
+                profile: String::from("seller"),
+            });
+        }
+
+        if let Some(content_hash) = &self.content_hash {
+            validate_content_hash("content_hash", content_hash)?;
+            // the hash is stamped on every edition, so they must all point to the same content.
+            if self.randomized
+                || self.reveal.is_some()
+                || self.tiers.iter().any(|tier| tier.uri.is_some())
+            {
+                return Err(ContractError::ContentHashWithDistinctUris {});
+            }
+        }
+
+        self.party_type.validate()?;
+
+        if let Some(mint_limits) = &self.mint_limits {
+            mint_limits.validate()?;
+        }
+
+        let end_time = self.party_type.end_time(self.start_time);
+        if let PartyType::EndTime(end_time) | PartyType::MaxEditionUntil { end_time, .. } =
+            self.party_type
+        {
+            if end_time <= self.start_time || end_time <= env.block.time {
+                return Err(ContractError::InvalidPartyEndTime {});
+            }
+        }
+
+        let max_editions = self.party_type.max_editions();
+        if let Some(number) = max_editions {
+            if self.reserved_supply > number {
+                return Err(ContractError::ReservedSupplyExceedsEditions {});
+            }
+        }
+
+        if self.randomized && max_editions.is_none() {
+            return Err(ContractError::RandomizedRequiresMaxEdition {});
+        }
+
+        if let Some(min_raise) = self.min_raise {
+            if end_time.is_none() {
+                return Err(ContractError::CrowdfundRequiresEndTime {});
+            }
+            if min_raise.is_zero() {
+                return Err(ContractError::ZeroMinRaise {});
+            }
+        }
+
+        if let Some(auction) = &self.dutch_auction {
+            auction.validate()?;
+            if auction.rebate && self.min_raise.is_some() {
+                return Err(ContractError::RebateWithCrowdfund {});
+            }
+        }
+
+        if let Some(burn_to_mint) = &self.burn_to_mint {
+            burn_to_mint.validate()?;
+            if self.min_raise.is_some() || self.dutch_auction.is_some() {
+                return Err(ContractError::BurnToMintWithSale {});
+            }
+        }
+
+        if let Some(raffle) = &self.raffle {
+            raffle.validate()?;
+            if raffle.entry_end_time <= self.start_time || raffle.entry_end_time <= env.block.time {
+                return Err(ContractError::InvalidRaffleEntryEnd {});
+            }
+            // every edition not reserved is won in the raffle.
+            match self.party_type {
+                PartyType::MaxEdition(number) if number > self.reserved_supply => {}
+                _ => return Err(ContractError::RaffleRequiresMaxEdition {}),
+            }
+            if self.min_raise.is_some()
+                || self.dutch_auction.is_some()
+                || self.burn_to_mint.is_some()
+                || self.token_gate.is_some()
+                || !self.allowlist_phases.is_empty()
+                || !self.stages.is_empty()
+            {
+                return Err(ContractError::RaffleWithSale {});
+            }
+        }
+
+        if let Some(token_gate) = &self.token_gate {
+            token_gate.validate()?;
+            if token_gate.discount_bps > 0 && self.dutch_auction.is_some() {
+                return Err(ContractError::GateDiscountWithAuction {});
+            }
+        }
+
+        if self.allowlist_phases.len() > Self::MAX_ALLOWLIST_PHASES {
+            return Err(ContractError::TooManyAllowlistPhases {
+                max: Self::MAX_ALLOWLIST_PHASES as u32,
+            });
+        }
+        for phase in &self.allowlist_phases {
+            phase.validate(&self.price.denom)?;
+        }
+
+        if self.stages.len() > Self::MAX_STAGES {
+            return Err(ContractError::TooManyStages {
+                max: Self::MAX_STAGES as u32,
+            });
+        }
+        for (index, stage) in self.stages.iter().enumerate() {
+            let is_last = index == self.stages.len() - 1;
+            stage.validate(&self.price.denom, is_last)?;
+
+            if let Some(next) = self.stages.get(index + 1) {
+                if stage.end_time.unwrap() > next.start_time {
+                    return Err(ContractError::StagesOverlap {});
+                }
+            }
+        }
+
+        if self.tiers.len() > Self::MAX_TIERS {
+            return Err(ContractError::TooManyTiers {
+                max: Self::MAX_TIERS as u32,
+            });
+        }
+        for (index, tier) in self.tiers.iter().enumerate() {
+            tier.validate()?;
+
+            if let Some((start, end)) = tier.range() {
+                let overlaps = self.tiers[..index]
+                    .iter()
+                    .filter_map(EditionTier::range)
+                    .any(|(other_start, other_end)| start <= other_end && other_start <= end);
+                if overlaps {
+                    return Err(ContractError::TiersOverlap {});
+                }
+            }
+        }
+
+        if let Some(reveal) = &self.reveal {
+            reveal.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AllowlistPhase {
+    /// Performs basic validation checks on the allowlist phase.
+    ///
+    /// # Validation Checks
+    ///
+    /// - the merkle root must be a hex encoded sha256 hash.
+    /// - the phase must start before its end.
+    /// - the price must be in the same denom of the public price.
+    pub fn validate(&self, denom: &str) -> Result<(), ContractError> {
+        validate_merkle_root(&self.merkle_root)?;
+
+        if self.start_time >= self.end_time {
+            return Err(ContractError::InvalidAllowlistPhaseTime {});
+        }
+
+        if self.price.denom != denom {
+            return Err(ContractError::InvalidAllowlistPhaseDenom {});
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if minting is allowed in the phase at the given time.
+    pub fn is_active(&self, time: Timestamp) -> bool {
+        self.start_time <= time && time < self.end_time
+    }
+}
+
+impl MintStage {
+    /// Performs basic validation checks on the stage.
+    ///
+    /// # Validation Checks
+    ///
+    /// - the stage must start before its end, and only the last stage can be open ended.
+    /// - the price must be in the same denom of the public price.
+    pub fn validate(&self, denom: &str, is_last: bool) -> Result<(), ContractError> {
+        match self.end_time {
+            Some(end_time) if self.start_time >= end_time => {
+                return Err(ContractError::InvalidStageTime {});
+            }
+            None if !is_last => return Err(ContractError::InvalidStageTime {}),
+            _ => {}
+        }
+
+        if self.price.denom != denom {
+            return Err(ContractError::InvalidStageDenom {});
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if minting is allowed in the stage at the given time.
+    pub fn is_active(&self, time: Timestamp) -> bool {
+        self.start_time <= time && self.end_time.is_none_or(|end_time| time < end_time)
+    }
+}
+
+impl DutchAuction {
+    const MAX_STEPS: u64 = 100;
+
+    /// Performs basic validation checks on the dutch auction.
+    ///
+    /// # Validation Checks
+    ///
+    /// - the start price must be higher than the floor price.
+    /// - the auction must start before its end.
+    /// - the step interval must be positive and the auction cannot have more than 100 steps.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.start_price <= self.floor_price {
+            return Err(ContractError::InvalidAuctionPrice {});
+        }
+
+        if self.start_time >= self.end_time {
+            return Err(ContractError::InvalidAuctionTime {});
+        }
+
+        if let AuctionDecay::Step { interval } = self.decay {
+            let duration = self.end_time.seconds() - self.start_time.seconds();
+            if interval == 0 || interval > duration || duration / interval > Self::MAX_STEPS {
+                return Err(ContractError::InvalidAuctionStep {
+                    max_steps: Self::MAX_STEPS,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the price of a single token at the given time.
+    pub fn price_at(&self, time: Timestamp) -> Uint128 {
+        if time <= self.start_time {
+            return self.start_price;
+        }
+        if time >= self.end_time {
+            return self.floor_price;
+        }
+
+        let duration = self.end_time.seconds() - self.start_time.seconds();
+        let elapsed = time.seconds() - self.start_time.seconds();
+        let (elapsed, duration) = match self.decay {
+            AuctionDecay::Linear => (elapsed, duration),
+            AuctionDecay::Step { interval } => (elapsed / interval, duration / interval),
+        };
+
+        let decrease = (self.start_price - self.floor_price).multiply_ratio(elapsed, duration);
+        self.start_price - decrease
+    }
+
+    /// Returns the points of the price curve, from the start to the end of the auction.
+    pub fn curve(&self) -> Vec<AuctionPricePoint> {
+        let times = match self.decay {
+            AuctionDecay::Linear => vec![self.start_time, self.end_time],
+            AuctionDecay::Step { interval } => {
+                let duration = self.end_time.seconds() - self.start_time.seconds();
+                (0..=duration / interval)
+                    .map(|step| self.start_time.plus_seconds(step * interval))
+                    .collect()
+            }
+        };
+
+        times
+            .into_iter()
+            .map(|time| AuctionPricePoint {
+                time,
+                price: self.price_at(time),
+            })
+            .collect()
+    }
+}
+
+impl EditionTier {
+    /// Performs basic validation checks on the tier.
+    ///
+    /// # Validation Checks
+    ///
+    /// - the range must start before its end.
+    /// - the weight cannot be zero.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match self.assignment {
+            TierAssignment::Range { start, end } if start > end => {
+                Err(ContractError::InvalidTokenRange {})
+            }
+            TierAssignment::Weight(0) => Err(ContractError::ZeroTierWeight {}),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns true if the edition `token_id` belongs to the tier by range.
+    pub fn contains(&self, token_id: u32) -> bool {
+        self.range()
+            .is_some_and(|(start, end)| start <= token_id && token_id <= end)
+    }
+
+    /// Returns the inclusive range of the tier, if assigned by range.
+    pub fn range(&self) -> Option<(u32, u32)> {
+        match self.assignment {
+            TierAssignment::Range { start, end } => Some((start, end)),
+            TierAssignment::Weight(_) => None,
+        }
+    }
+}
+
+impl TokenGate {
+    const MAX_COLLECTIONS: usize = 10;
+
+    /// Performs basic validation checks on the token gate.
+    ///
+    /// # Validation Checks
+    ///
+    /// - at least one and at most 10 collections.
+    /// - the discount cannot exceed the price, and is required if minting is not restricted to
+    ///   holders.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.collections.is_empty() || self.collections.len() > Self::MAX_COLLECTIONS {
+            return Err(ContractError::InvalidGateCollections {
+                max: Self::MAX_COLLECTIONS as u32,
+            });
+        }
+
+        if self.discount_bps > MAX_BPS || (!self.holders_only && self.discount_bps == 0) {
+            return Err(ContractError::InvalidGateDiscount {});
+        }
+
+        Ok(())
+    }
 }
 
-#[cw_serde]
-pub struct MaxPerAddressResponse {
-    /// Returns the maximum amount of token an address can mint.
-    pub remaining: Option<u32>,
+impl DelayedReveal {
+    /// Performs basic validation checks on the delayed reveal.
+    ///
+    /// # Validation Checks
+    ///
+    /// - the provenance hash must be a hex encoded sha256 hash.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match hex::decode(&self.provenance_hash) {
+            Ok(hash) if hash.len() == 32 => Ok(()),
+            _ => Err(ContractError::InvalidProvenanceHash {}),
+        }
+    }
+
+    /// Checks that `base_uri` is the one committed with the provenance hash. The content behind
+    /// the URI is not checked, which is why the URI should be content-addressed.
+    pub fn verify(&self, base_uri: &str) -> Result<(), ContractError> {
+        let hash = hex::encode(Sha256::digest(base_uri.as_bytes()));
+        if !hash.eq_ignore_ascii_case(&self.provenance_hash) {
+            return Err(ContractError::InvalidReveal {});
+        }
+        Ok(())
+    }
 }
 
-impl InstantiateMsg {
-    const MAX_FEE_BPS: u16 = 10_000;
+impl BurnToMint {
+    const MAX_RATIO: u32 = 10;
 
-    /// Performs basic validation checks on the InstantiateMsg type.
+    /// Performs basic validation checks on the burn-to-mint mode.
     ///
-    /// # Validation Checks:
+    /// # Validation Checks
     ///
-    /// - start time must be in the future.
-    /// - maximum bps allowed for both seller and referral.
-    /// - end condition of the launchparty.
-    pub fn validate(&self, _env: Env) -> Result<(), ContractError> {
-        // validate seller_fee_bps
-        if self.seller_fee_bps > Self::MAX_FEE_BPS {
-            return Err(ContractError::FeeBps {
-                profile: String::from("seller"),
+    /// - the ratio must be positive and at most 10.
+    /// - each range must start before its end.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.ratio == 0 || self.ratio > Self::MAX_RATIO {
+            return Err(ContractError::InvalidRedeemRatio {
+                max: Self::MAX_RATIO,
             });
         }
 
-        // validate referral_fee_bps
-        if self.referral_fee_bps > Self::MAX_FEE_BPS {
-            return Err(ContractError::FeeBps {
-                profile: String::from("referral"),
-            });
+        if self
+            .eligible_ranges
+            .iter()
+            .any(|range| range.start > range.end)
+        {
+            return Err(ContractError::InvalidTokenRange {});
         }
 
-        self.party_type.validate()?;
+        Ok(())
+    }
+
+    /// Returns true if the token can be redeemed. With eligible ranges, only numeric token IDs
+    /// within one of them are accepted.
+    pub fn is_eligible(&self, token_id: &str) -> bool {
+        if self.eligible_ranges.is_empty() {
+            return true;
+        }
+
+        token_id.parse::<u32>().is_ok_and(|id| {
+            self.eligible_ranges
+                .iter()
+                .any(|range| range.start <= id && id <= range.end)
+        })
+    }
+}
+
+impl MintLimits {
+    /// Performs basic validation checks on the mint limits.
+    ///
+    /// # Validation Checks
+    ///
+    /// - each limit, the window duration and the cooldown cannot be zero.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let zero_window = self
+            .max_per_window
+            .as_ref()
+            .is_some_and(|window| window.max_editions == 0 || window.duration == 0);
+        if self.max_per_block == Some(0)
+            || self.max_per_tx == Some(0)
+            || zero_window
+            || self.cooldown == Some(0)
+        {
+            return Err(ContractError::InvalidMintLimits {});
+        }
 
         Ok(())
     }
 }
 
+impl Raffle {
+    const MAX_TICKETS_PER_ADDRESS: u32 = 100;
+
+    /// Performs basic validation checks on the raffle.
+    ///
+    /// # Validation Checks
+    ///
+    /// - the maximum tickets per address must be positive and at most 100.
+    /// - the beacon public key must be a hex encoded compressed secp256k1 public key.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.max_tickets_per_address == 0
+            || self.max_tickets_per_address > Self::MAX_TICKETS_PER_ADDRESS
+        {
+            return Err(ContractError::InvalidRaffleTickets {
+                max: Self::MAX_TICKETS_PER_ADDRESS,
+            });
+        }
+
+        match hex::decode(&self.beacon_public_key) {
+            Ok(key) if decode_point(&key).is_some() => Ok(()),
+            _ => Err(ContractError::InvalidBeaconKey {}),
+        }
+    }
+}
+
 impl PartyType {
     /// Performs basic validation checks on the party type.
     ///
@@ -124,22 +1193,102 @@ impl PartyType {
                     return Err(ContractError::ZeroDuration {});
                 }
             }
+            PartyType::EndTime(_) => {}
+            PartyType::MaxEditionUntil { max_editions, .. } => {
+                if max_editions == &0u32 {
+                    return Err(ContractError::ZeroEditions {});
+                }
+            }
         }
         Ok(())
     }
+
+    /// Returns the maximum number of mintable tokens, if limited.
+    pub fn max_editions(&self) -> Option<u32> {
+        match self {
+            PartyType::MaxEdition(number) => Some(*number),
+            PartyType::MaxEditionUntil { max_editions, .. } => Some(*max_editions),
+            PartyType::Duration(_) | PartyType::EndTime(_) => None,
+        }
+    }
+
+    /// Returns the end time of a party starting at `start_time`, if limited in time.
+    pub fn end_time(&self, start_time: Timestamp) -> Option<Timestamp> {
+        match self {
+            PartyType::Duration(duration) => Some(start_time.plus_seconds(*duration as u64)),
+            PartyType::EndTime(end_time) | PartyType::MaxEditionUntil { end_time, .. } => {
+                Some(*end_time)
+            }
+            PartyType::MaxEdition(_) => None,
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
 // Unit tests
 // -------------------------------------------------------------------------------------------------
+
+/// Instantiate message of a single edition party without optional features, used as the base of
+/// the unit tests.
+#[cfg(test)]
+pub(crate) fn default_instantiate_msg(env: &Env) -> InstantiateMsg {
+    InstantiateMsg {
+        name: "Launchparty".to_string(),
+        symbol: "LP".to_string(),
+        uri: "ipfs://Qm......".to_string(),
+        content_hash: None,
+        price: cosmwasm_std::coin(1, "ubtsg"),
+        max_per_address: Some(1),
+        cw20_address: None,
+        payment_address: "payment_address".to_string(),
+        seller_fee_bps: 100,
+        accrue_referrals: false,
+        fee_split: bs_std::fees::royalties_fee_split(100, 3),
+        start_time: env.block.time,
+        party_type: PartyType::MaxEdition(1),
+        randomized: false,
+        min_raise: None,
+        limit_target: MintLimitTarget::Payer,
+        mint_limits: None,
+        reserved_supply: 0,
+        dutch_auction: None,
+        burn_to_mint: None,
+        raffle: None,
+        token_gate: None,
+        reveal: None,
+        minter_handover: MinterHandover::Creator,
+        tiers: vec![],
+        bs721_code_id: 1,
+        bs721_admin: "bs721_admin".to_string(),
+        creator: None,
+        allowlist_phases: vec![],
+        stages: vec![],
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use bs721::MetadataError;
+    use bs_std::fees::royalties_fee_split;
     use cosmwasm_std::{coin, testing::mock_env};
 
     use super::*;
 
+    /// Compressed generator of secp256k1, a valid beacon public key.
+    const GENERATOR: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
     #[test]
     fn party_type_validate_works() {
+        {
+            let party_type = PartyType::MaxEditionUntil {
+                max_editions: 0,
+                end_time: Timestamp::from_seconds(1),
+            };
+            let err = party_type.validate().unwrap_err();
+
+            assert_eq!(err, ContractError::ZeroEditions {});
+        }
+
         {
             let party_type = PartyType::Duration(0);
             let err = party_type.validate().unwrap_err();
@@ -166,19 +1315,10 @@ mod test {
     #[test]
     fn instantiate_msg_validate_works() {
         let mut msg = InstantiateMsg {
-            name: "Launchparty".to_string(),
-            symbol: "LP".to_string(),
-            uri: "ipfs://Qm......".to_string(),
-            price: coin(1, "ubtsg"),
             max_per_address: Some(100),
-            payment_address: "payment_address".to_string(),
-            seller_fee_bps: 100,
-            referral_fee_bps: 1,
-            protocol_fee_bps: 1,
+            fee_split: royalties_fee_split(1, 1),
             start_time: Timestamp::from_seconds(0),
-            party_type: PartyType::MaxEdition(1),
-            bs721_code_id: 1,
-            bs721_admin: "bs721_admin".to_string(),
+            ..default_instantiate_msg(&mock_env())
         };
 
         {
@@ -195,16 +1335,415 @@ mod test {
         }
 
         {
-            msg.referral_fee_bps = 10_001;
+            msg.content_hash = Some("ipfs://Qm......".to_string());
             let err = msg.validate(mock_env()).unwrap_err();
             assert_eq!(
                 err,
-                ContractError::FeeBps {
-                    profile: String::from("referral")
-                },
-                "expected to fail since fee bps higher than maximum allowed"
+                ContractError::Metadata(MetadataError::InvalidContentHash {
+                    field: "content_hash".to_string()
+                }),
+                "expected to fail since the content hash is not a digest or a CID"
+            );
+
+            msg.content_hash = Some("a".repeat(64));
+            msg.validate(mock_env()).unwrap();
+
+            msg.randomized = true;
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::ContentHashWithDistinctUris {},
+                "expected to fail since randomized editions have different URIs"
+            );
+            msg.randomized = false;
+
+            msg.reveal = Some(DelayedReveal {
+                placeholder_uri: "ipfs://placeholder".to_string(),
+                provenance_hash: "a".repeat(64),
+                random_offset: false,
+            });
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::ContentHashWithDistinctUris {});
+            msg.reveal = None;
+
+            msg.tiers = vec![EditionTier {
+                name: "Gold".to_string(),
+                uri: Some("ipfs://gold".to_string()),
+                attributes: vec![],
+                assignment: TierAssignment::Range { start: 1, end: 1 },
+            }];
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::ContentHashWithDistinctUris {});
+            msg.tiers[0].uri = None;
+            msg.validate(mock_env()).unwrap();
+            msg.tiers = vec![];
+
+            msg.content_hash = None;
+        }
+
+        {
+            let end_time = mock_env().block.time;
+            msg.party_type = PartyType::EndTime(end_time);
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidPartyEndTime {},
+                "expected to fail since the party ends at the instantiation"
+            );
+
+            msg.party_type = PartyType::MaxEditionUntil {
+                max_editions: 1,
+                end_time: end_time.plus_seconds(1),
+            };
+            msg.validate(mock_env()).unwrap();
+
+            msg.reserved_supply = 2;
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::ReservedSupplyExceedsEditions {});
+            msg.reserved_supply = 0;
+        }
+
+        {
+            msg.min_raise = Some(Uint128::new(100));
+            msg.validate(mock_env())
+                .expect("expected a crowdfund allowed with an end time");
+
+            msg.party_type = PartyType::MaxEdition(1);
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::CrowdfundRequiresEndTime {});
+        }
+
+        {
+            msg.mint_limits = Some(MintLimits {
+                max_per_block: Some(10),
+                max_per_tx: Some(2),
+                max_per_window: Some(MintWindow {
+                    max_editions: 5,
+                    duration: 0,
+                }),
+                cooldown: None,
+            });
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidMintLimits {},
+                "expected to fail since the window has no duration"
             );
-            msg.referral_fee_bps = 1_000;
+
+            let mint_limits = msg.mint_limits.as_mut().unwrap();
+            mint_limits.max_per_window = None;
+            mint_limits.cooldown = Some(0);
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::InvalidMintLimits {});
+            msg.mint_limits = None;
+        }
+
+        {
+            msg.min_raise = None;
+            msg.token_gate = Some(TokenGate {
+                collections: vec!["collection".to_string()],
+                holders_only: true,
+                discount_bps: 0,
+            });
+            msg.raffle = Some(Raffle {
+                entry_end_time: mock_env().block.time.plus_seconds(100),
+                max_tickets_per_address: 5,
+                beacon_public_key: GENERATOR.to_string(),
+            });
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::RaffleWithSale {},
+                "expected to fail since a raffle cannot be token gated"
+            );
+            msg.token_gate = None;
+            msg.validate(mock_env()).unwrap();
+
+            msg.reserved_supply = 1;
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::RaffleRequiresMaxEdition {},
+                "expected to fail since every edition is reserved"
+            );
+            msg.reserved_supply = 0;
+
+            msg.party_type = PartyType::Duration(100);
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::RaffleRequiresMaxEdition {});
+            msg.party_type = PartyType::MaxEdition(1);
+
+            let raffle = msg.raffle.as_mut().unwrap();
+            raffle.entry_end_time = mock_env().block.time;
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::InvalidRaffleEntryEnd {});
+
+            let raffle = msg.raffle.as_mut().unwrap();
+            raffle.entry_end_time = mock_env().block.time.plus_seconds(100);
+            raffle.max_tickets_per_address = 101;
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::InvalidRaffleTickets { max: 100 });
+
+            let raffle = msg.raffle.as_mut().unwrap();
+            raffle.max_tickets_per_address = 5;
+            raffle.beacon_public_key = GENERATOR[..64].to_string();
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::InvalidBeaconKey {});
+
+            let raffle = msg.raffle.as_mut().unwrap();
+            raffle.beacon_public_key = format!("04{}", &GENERATOR[2..]);
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidBeaconKey {},
+                "expected to fail since the public key is not a compressed point"
+            );
+        }
+    }
+
+    #[test]
+    fn stages_validate_works() {
+        let stage = |start: u64, end: Option<u64>| MintStage {
+            name: "stage".to_string(),
+            start_time: Timestamp::from_seconds(start),
+            end_time: end.map(Timestamp::from_seconds),
+            price: coin(1, "ubtsg"),
+            max_per_address: None,
+            max_supply: None,
+        };
+
+        let mut msg = InstantiateMsg {
+            max_per_address: Some(100),
+            fee_split: royalties_fee_split(1, 1),
+            start_time: Timestamp::from_seconds(0),
+            stages: vec![stage(0, Some(10)), stage(10, Some(20)), stage(20, None)],
+            ..default_instantiate_msg(&mock_env())
+        };
+        msg.validate(mock_env()).unwrap();
+
+        {
+            msg.stages = vec![stage(0, None), stage(10, None)];
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidStageTime {},
+                "expected to fail since only the last stage can be open ended"
+            );
+        }
+
+        {
+            msg.stages = vec![stage(10, Some(10))];
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::InvalidStageTime {});
+        }
+
+        {
+            msg.stages = vec![stage(0, Some(15)), stage(10, None)];
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::StagesOverlap {},
+                "expected to fail since stages are overlapping"
+            );
+        }
+
+        {
+            msg.stages = vec![stage(0, None)];
+            msg.stages[0].price = coin(1, "uatom");
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::InvalidStageDenom {});
+        }
+    }
+
+    #[test]
+    fn dutch_auction_works() {
+        let mut auction = DutchAuction {
+            start_price: Uint128::new(100),
+            floor_price: Uint128::new(10),
+            start_time: Timestamp::from_seconds(100),
+            end_time: Timestamp::from_seconds(200),
+            decay: AuctionDecay::Linear,
+            rebate: false,
+        };
+        auction.validate().unwrap();
+
+        assert_eq!(
+            auction.price_at(Timestamp::from_seconds(50)),
+            Uint128::new(100)
+        );
+        assert_eq!(
+            auction.price_at(Timestamp::from_seconds(150)),
+            Uint128::new(55)
+        );
+        assert_eq!(
+            auction.price_at(Timestamp::from_seconds(300)),
+            Uint128::new(10)
+        );
+
+        auction.decay = AuctionDecay::Step { interval: 30 };
+        auction.validate().unwrap();
+        assert_eq!(
+            auction.price_at(Timestamp::from_seconds(159)),
+            Uint128::new(70),
+            "expected the price after the first step"
+        );
+        assert_eq!(auction.curve().len(), 4);
+
+        {
+            auction.decay = AuctionDecay::Step { interval: 0 };
+            let err = auction.validate().unwrap_err();
+            assert_eq!(err, ContractError::InvalidAuctionStep { max_steps: 100 });
+            auction.decay = AuctionDecay::Linear;
+        }
+
+        {
+            auction.floor_price = Uint128::new(100);
+            let err = auction.validate().unwrap_err();
+            assert_eq!(err, ContractError::InvalidAuctionPrice {});
+            auction.floor_price = Uint128::new(10);
+        }
+
+        {
+            auction.end_time = auction.start_time;
+            let err = auction.validate().unwrap_err();
+            assert_eq!(err, ContractError::InvalidAuctionTime {});
+        }
+    }
+
+    #[test]
+    fn tiers_validate_works() {
+        let tier = |assignment: TierAssignment| EditionTier {
+            name: "tier".to_string(),
+            uri: None,
+            attributes: vec![],
+            assignment,
+        };
+
+        let mut msg = InstantiateMsg {
+            max_per_address: Some(100),
+            fee_split: royalties_fee_split(1, 1),
+            start_time: Timestamp::from_seconds(0),
+            party_type: PartyType::MaxEdition(100),
+            tiers: vec![
+                tier(TierAssignment::Range { start: 1, end: 10 }),
+                tier(TierAssignment::Range { start: 11, end: 11 }),
+                tier(TierAssignment::Weight(1)),
+            ],
+            ..default_instantiate_msg(&mock_env())
+        };
+        msg.validate(mock_env()).unwrap();
+        assert!(msg.tiers[0].contains(10));
+        assert!(!msg.tiers[0].contains(11));
+        assert!(!msg.tiers[2].contains(1));
+
+        {
+            msg.tiers[1].assignment = TierAssignment::Range { start: 10, end: 20 };
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::TiersOverlap {});
+        }
+
+        {
+            msg.tiers[1].assignment = TierAssignment::Range { start: 20, end: 11 };
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::InvalidTokenRange {});
+        }
+
+        {
+            msg.tiers[1].assignment = TierAssignment::Weight(0);
+            let err = msg.validate(mock_env()).unwrap_err();
+            assert_eq!(err, ContractError::ZeroTierWeight {});
+        }
+    }
+
+    #[test]
+    fn token_gate_validate_works() {
+        let mut token_gate = TokenGate {
+            collections: vec!["fanclub".to_string()],
+            holders_only: true,
+            discount_bps: 0,
+        };
+        token_gate.validate().unwrap();
+
+        {
+            token_gate.holders_only = false;
+            let err = token_gate.validate().unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidGateDiscount {},
+                "expected to fail since an open gate requires a discount"
+            );
+            token_gate.discount_bps = 1_000;
+            token_gate.validate().unwrap();
+        }
+
+        {
+            token_gate.collections = vec![];
+            let err = token_gate.validate().unwrap_err();
+            assert_eq!(err, ContractError::InvalidGateCollections { max: 10 });
+        }
+    }
+
+    #[test]
+    fn burn_to_mint_works() {
+        let mut burn_to_mint = BurnToMint {
+            collection: "tickets".to_string(),
+            ratio: 2,
+            eligible_ranges: vec![],
+            action: RedeemAction::Burn,
+        };
+        burn_to_mint.validate().unwrap();
+        assert!(
+            burn_to_mint.is_eligible("stub"),
+            "expected any token eligible without ranges"
+        );
+
+        burn_to_mint.eligible_ranges = vec![
+            TokenRange { start: 1, end: 10 },
+            TokenRange { start: 20, end: 20 },
+        ];
+        burn_to_mint.validate().unwrap();
+        assert!(burn_to_mint.is_eligible("10"));
+        assert!(burn_to_mint.is_eligible("20"));
+        assert!(!burn_to_mint.is_eligible("11"));
+        assert!(!burn_to_mint.is_eligible("stub"));
+
+        {
+            burn_to_mint
+                .eligible_ranges
+                .push(TokenRange { start: 5, end: 4 });
+            let err = burn_to_mint.validate().unwrap_err();
+            assert_eq!(err, ContractError::InvalidTokenRange {});
+            burn_to_mint.eligible_ranges.pop();
+        }
+
+        {
+            burn_to_mint.ratio = 0;
+            let err = burn_to_mint.validate().unwrap_err();
+            assert_eq!(err, ContractError::InvalidRedeemRatio { max: 10 });
         }
     }
+
+    #[test]
+    fn delayed_reveal_works() {
+        let base_uri = "ipfs://final";
+        let mut reveal = DelayedReveal {
+            placeholder_uri: "ipfs://placeholder".to_string(),
+            provenance_hash: hex::encode(Sha256::digest(base_uri.as_bytes())),
+            random_offset: true,
+        };
+        reveal.validate().unwrap();
+        reveal.verify(base_uri).unwrap();
+
+        let err = reveal.verify("ipfs://other").unwrap_err();
+        assert_eq!(err, ContractError::InvalidReveal {});
+
+        reveal.provenance_hash = reveal.provenance_hash.to_uppercase();
+        reveal
+            .verify(base_uri)
+            .expect("expected the hash compared case insensitive");
+
+        reveal.provenance_hash = "stub".to_string();
+        let err = reveal.validate().unwrap_err();
+        assert_eq!(err, ContractError::InvalidProvenanceHash {});
+    }
 }
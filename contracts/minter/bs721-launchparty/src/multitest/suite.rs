@@ -1,16 +1,51 @@
+use std::marker::PhantomData;
+
 use anyhow::Result as AnyResult;
-use cosmwasm_std::{Addr, Coin, Empty, Timestamp};
-use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use bs_controllers::HooksResponse;
+use bs_mint_hooks::{post::PostMintHookMsg, pre::PreMintHookMsg};
+use bs_std::fees::{royalties_fee_split, FeeRecipient, FeeShare};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Timestamp, Uint128,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20QueryMsg};
+use cw_multi_test::{next_block, App, AppResponse, Contract, ContractWrapper, Executor};
 use derivative::Derivative;
 
-use bs721_base::msg::QueryMsg as Bs721BaseQueryMsg;
+use bs721::{CollectionInfo, RoyaltyInfoResponse};
+use bs721_base::helpers::Bs721Contract;
+use bs721_base::msg::{CollectionInfoResponse, TokenMetadataResponse};
+use bs721_base::{MinterResponse, QueryMsg as Bs721QueryMsg};
 
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, MaxPerAddressResponse, PartyType, QueryMsg},
+    helpers::LaunchpartyContract,
+    msg::{
+        AllowlistMintedResponse, AllowlistPhase, AllowlistPhasesResponse, AllowlistProof,
+        BurnToMint, CrowdfundResponse, CurrentStageResponse, DelayedReveal, DutchAuction,
+        DutchAuctionResponse, EditionTier, GateToken, InstantiateMsg, MaxPerAddressResponse,
+        MintLimitTarget, MintLimits, MintStage, MinterHandover, PartyType, Raffle,
+        RaffleEntryResponse, RaffleResponse, RedeemAction, RedeemPendingResponse,
+        ReferralStatsResponse, RevealResponse, StatusResponse, TokenGate, TokenRange,
+        TokenTierResponse, TopReferrersResponse,
+    },
+    raffle::{mock_beacon_proof, mock_beacon_public_key},
     state::Config,
 };
 
 pub const CREATOR: &str = "creator";
+/// Secret key of the randomness beacon of the raffle.
+const BEACON_SECRET_KEY: [u8; 32] = [42; 32];
+
+/// Returns the hex encoded public key of the randomness beacon.
+pub fn beacon_public_key() -> String {
+    mock_beacon_public_key(&BEACON_SECRET_KEY)
+}
+
+/// Returns the hex encoded VRF proof of the randomness beacon on the hex encoded `message`.
+pub fn beacon_proof(message: &str) -> String {
+    mock_beacon_proof(&BEACON_SECRET_KEY, &hex::decode(message).unwrap())
+}
 
 /// Helper function to create a wrapper around the bs721 base contract
 pub fn contract_bs721_base() -> Box<dyn Contract<Empty>> {
@@ -21,6 +56,15 @@ pub fn contract_bs721_base() -> Box<dyn Contract<Empty>> {
     ))
 }
 
+/// Helper function to create a wrapper around the cw20 base contract
+pub fn contract_cw20_base() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ))
+}
+
 /// Helper function to create a wrapper around the launchparty contract
 pub fn contract_launchparty() -> Box<dyn Contract<Empty>> {
     Box::new(
@@ -33,6 +77,76 @@ pub fn contract_launchparty() -> Box<dyn Contract<Empty>> {
     )
 }
 
+/// Address rejected by the mock mint hook.
+pub const BLOCKED: &str = "blocked";
+
+/// Messages handled by the mock mint hook.
+#[cw_serde]
+pub enum MockMintHookMsg {
+    PreMintHook(PreMintHookMsg),
+    PostMintHook(PostMintHookMsg),
+}
+
+/// Helper function to create a mint hook contract rejecting the mints of the `BLOCKED` address.
+pub fn contract_mint_hook() -> Box<dyn Contract<Empty>> {
+    fn execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: MockMintHookMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            MockMintHookMsg::PreMintHook(msg) if msg.buyer == BLOCKED => {
+                Err(StdError::generic_err("buyer not allowed"))
+            }
+            MockMintHookMsg::PreMintHook(msg) => Ok(Response::new()
+                .add_attribute("action", "premint_hook")
+                .add_attribute("token_id", msg.token_id.unwrap_or_default())),
+            MockMintHookMsg::PostMintHook(msg) => Ok(Response::new()
+                .add_attribute("action", "postmint_hook")
+                .add_attribute("token_id", msg.token_id.unwrap_or_default())),
+        }
+    }
+
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("no queries"))
+    }
+
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+}
+
+/// Helper function to create a contract standing for the factory, the only trusted instantiator
+/// of a cw20 launchparty.
+pub fn contract_factory() -> Box<dyn Contract<Empty>> {
+    fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("no queries"))
+    }
+
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+}
+
 // -------------------------------------------------------------------------------------------------
 // TestSuiteBuilder
 // -------------------------------------------------------------------------------------------------
@@ -51,16 +165,21 @@ pub struct TestSuiteBuilder {
     pub name: String,
     #[derivative(Default(value = "String::from(\"uri\")"))]
     pub uri: String,
+    /// Hash of the content shared by every edition.
+    #[derivative(Default(value = "None"))]
+    pub content_hash: Option<String>,
     /// Price of single nft minting.
     pub price: Coin,
     /// Maximum numer of tokens an address can mint
     #[derivative(Default(value = "None"))]
     pub max_per_address: Option<u32>,
-    /// BS721 token uri.
-    pub base_token_uri: String,
     pub seller_fee_bps: u16,
     pub referral_fee_bps: u16,
     pub protocol_fee_bps: u16,
+    /// Shares of the fee split sent to other addresses, taken from the royalties share.
+    pub fee_shares: Vec<FeeShare>,
+    pub accrue_referrals: bool,
+    #[derivative(Default(value = "String::from(\"contract2\")"))]
     pub payment_address: String,
     /// Start time of the launchparty.
     #[derivative(Default(value = "Timestamp::from_seconds(1571797419)"))]
@@ -68,6 +187,41 @@ pub struct TestSuiteBuilder {
     /// End condition of the collection launchparty.
     #[derivative(Default(value = "PartyType::MaxEdition(1)"))]
     pub party_type: PartyType,
+    /// Address the per address limits are applied to.
+    #[derivative(Default(value = "MintLimitTarget::Payer"))]
+    pub limit_target: MintLimitTarget,
+    /// Anti-bot limits of the mint.
+    pub mint_limits: Option<MintLimits>,
+    /// Editions reserved for the creator airdrops.
+    pub reserved_supply: u32,
+    /// If true, minters receive a random index of the collection.
+    pub randomized: bool,
+    /// Minimum raise of a crowdfund launchparty.
+    pub min_raise: Option<Uint128>,
+    /// Allowlist phases of the launchparty.
+    pub allowlist_phases: Vec<AllowlistPhase>,
+    /// Stages of the public mint.
+    pub stages: Vec<MintStage>,
+    /// Dutch auction of the public mint.
+    pub dutch_auction: Option<DutchAuction>,
+    /// Burn-to-mint mode, the collection is instantiated during the build.
+    pub burn_to_mint: Option<BurnToMint>,
+    /// Raffle mode, editions are won in the raffle.
+    pub raffle: Option<Raffle>,
+    /// Token gate of the public mint, the collection is instantiated during the build.
+    pub token_gate: Option<TokenGate>,
+    /// Tiers of the editions.
+    pub tiers: Vec<EditionTier>,
+    /// Delayed reveal of the editions.
+    pub reveal: Option<DelayedReveal>,
+    /// Minter handover of the collection when the launchparty is finalized.
+    #[derivative(Default(value = "MinterHandover::Creator"))]
+    pub minter_handover: MinterHandover,
+    /// Address of the cw20 token used to pay for mints, set during the build if cw20 balances
+    /// are defined.
+    pub cw20_address: Option<String>,
+    /// Initial balances of the cw20 token used to pay for mints.
+    pub cw20_balances: Vec<Cw20Coin>,
     pub init_funds: Vec<(Addr, Vec<Coin>)>,
 }
 
@@ -78,18 +232,172 @@ impl TestSuiteBuilder {
         self
     }
 
+    /// Helper function to set the content hash of the editions.
+    pub fn with_content_hash(mut self, content_hash: &str) -> Self {
+        self.content_hash = Some(content_hash.to_string());
+        self
+    }
+
+    /// Helper function to assign random indexes of the collection to minters.
+    pub fn with_randomized(mut self) -> Self {
+        self.randomized = true;
+        self
+    }
+
+    /// Helper function to define the address the per address limits are applied to.
+    pub fn with_limit_target(mut self, limit_target: MintLimitTarget) -> Self {
+        self.limit_target = limit_target;
+        self
+    }
+
+    /// Helper function to limit minting per block, per transaction and per address over time.
+    pub fn with_mint_limits(mut self, mint_limits: MintLimits) -> Self {
+        self.mint_limits = Some(mint_limits);
+        self
+    }
+
+    /// Helper function to reserve editions for the creator airdrops.
+    pub fn with_reserved_supply(mut self, reserved_supply: u32) -> Self {
+        self.reserved_supply = reserved_supply;
+        self
+    }
+
+    /// Helper function to run the launchparty as a crowdfund.
+    pub fn with_min_raise(mut self, min_raise: u128) -> Self {
+        self.min_raise = Some(Uint128::new(min_raise));
+        self
+    }
+
+    /// Helper function to accrue the referral fees in the launchparty.
+    pub fn with_accrue_referrals(mut self) -> Self {
+        self.accrue_referrals = true;
+        self
+    }
+
     /// Helper function to set referral fee bp.
     pub fn with_referral_fee_bps(mut self, referral_fee_bps: u16) -> Self {
         self.referral_fee_bps = referral_fee_bps;
         self
     }
 
+    /// Helper function to send a share of the mint price to an address.
+    pub fn with_fee_share(mut self, address: &str, bps: u16) -> Self {
+        self.fee_shares.push(FeeShare {
+            recipient: FeeRecipient::Address(address.to_string()),
+            bps,
+        });
+        self
+    }
+
+    /// Helper function to set protocol fee bp.
+    pub fn with_protocol_fee_bps(mut self, protocol_fee_bps: u16) -> Self {
+        self.protocol_fee_bps = protocol_fee_bps;
+        self
+    }
+
     /// Helper function to define the end condition of the launchparty.
     pub fn with_party_type(mut self, party_type: PartyType) -> Self {
         self.party_type = party_type;
         self
     }
 
+    /// Helper function to define the start time of the public mint.
+    pub fn with_start_time(mut self, start_time: Timestamp) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    /// Helper function to add an allowlist phase to the launchparty.
+    pub fn with_allowlist_phase(mut self, phase: AllowlistPhase) -> Self {
+        self.allowlist_phases.push(phase);
+        self
+    }
+
+    /// Helper function to add a stage to the public mint.
+    pub fn with_stage(mut self, stage: MintStage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Helper function to price the public mint with a dutch auction.
+    pub fn with_dutch_auction(mut self, dutch_auction: DutchAuction) -> Self {
+        self.dutch_auction = Some(dutch_auction);
+        self
+    }
+
+    /// Helper function to mint editions by redeeming tickets of another collection, instantiated
+    /// during the build.
+    pub fn with_burn_to_mint(
+        mut self,
+        ratio: u32,
+        eligible_ranges: Vec<TokenRange>,
+        action: RedeemAction,
+    ) -> Self {
+        self.burn_to_mint = Some(BurnToMint {
+            collection: String::new(),
+            ratio,
+            eligible_ranges,
+            action,
+        });
+        self
+    }
+
+    /// Helper function to run the launchparty as a raffle whose entries close after
+    /// `entry_duration` seconds, drawn by the test beacon.
+    pub fn with_raffle(mut self, entry_duration: u64, max_tickets_per_address: u32) -> Self {
+        self.raffle = Some(Raffle {
+            entry_end_time: self.start_time.plus_seconds(entry_duration),
+            max_tickets_per_address,
+            beacon_public_key: beacon_public_key(),
+        });
+        self
+    }
+
+    /// Helper function to gate the public mint with tickets of another collection, instantiated
+    /// during the build.
+    pub fn with_token_gate(mut self, holders_only: bool, discount_bps: u16) -> Self {
+        self.token_gate = Some(TokenGate {
+            collections: vec![],
+            holders_only,
+            discount_bps,
+        });
+        self
+    }
+
+    /// Helper function to add a tier of the editions.
+    pub fn with_tier(mut self, tier: EditionTier) -> Self {
+        self.tiers.push(tier);
+        self
+    }
+
+    /// Helper function to mint editions with a placeholder URI until revealed.
+    pub fn with_reveal(mut self, reveal: DelayedReveal) -> Self {
+        self.reveal = Some(reveal);
+        self
+    }
+
+    /// Helper function to define the minter handover when the launchparty is finalized.
+    pub fn with_minter_handover(mut self, minter_handover: MinterHandover) -> Self {
+        self.minter_handover = minter_handover;
+        self
+    }
+
+    /// Helper function to define the payment address for the royalties.
+    pub fn with_payment_address(mut self, payment_address: &str) -> Self {
+        self.payment_address = payment_address.to_string();
+        self
+    }
+
+    /// Helper function to pay mints with a cw20 token, initialized with the given balance for the
+    /// address. The price denom is replaced by the cw20 token address.
+    pub fn with_cw20_funds(mut self, addr: &str, amount: u128) -> Self {
+        self.cw20_balances.push(Cw20Coin {
+            address: addr.to_string(),
+            amount: Uint128::new(amount),
+        });
+        self
+    }
+
     /// Helper function to initialize the bank module with funds associated to particular addresses.
     pub fn with_funds(mut self, addr: &str, funds: &[Coin]) -> Self {
         self.init_funds.push((Addr::unchecked(addr), funds.into()));
@@ -103,37 +411,59 @@ impl TestSuiteBuilder {
     }
 
     /// Helper function to instantiate the launchparty contract with parameters defined by the TestSuiteBuilder
-    pub fn instantiate_launchparty(&self, app: &mut App, code_id: u64, bs721_code_id: u64) -> Addr {
+    pub fn instantiate_launchparty(
+        &self,
+        app: &mut App,
+        code_id: u64,
+        bs721_code_id: u64,
+        sender: Addr,
+    ) -> Addr {
+        let mut fee_split = royalties_fee_split(self.referral_fee_bps, self.protocol_fee_bps);
+        for share in &self.fee_shares {
+            fee_split[0].bps -= share.bps;
+            fee_split.push(share.clone());
+        }
+
         // could we also use mem to optimize code and avoid clone
         let init_msg = InstantiateMsg {
             symbol: self.symbol.clone(),
             name: self.name.clone(),
             uri: self.uri.clone(),
+            content_hash: self.content_hash.clone(),
             price: self.price.clone(),
             max_per_address: self.max_per_address,
+            limit_target: self.limit_target.clone(),
+            mint_limits: self.mint_limits.clone(),
+            reserved_supply: self.reserved_supply,
             seller_fee_bps: self.seller_fee_bps,
-            referral_fee_bps: self.referral_fee_bps,
-            protocol_fee_bps: self.protocol_fee_bps,
+            fee_split,
+            accrue_referrals: self.accrue_referrals,
             start_time: self.start_time,
             party_type: self.party_type.clone(),
+            randomized: self.randomized,
+            min_raise: self.min_raise,
             bs721_code_id,
-            payment_address: "contract2".to_string(),
+            payment_address: self.payment_address.clone(),
             bs721_admin: String::from("bs721_admin"),
+            creator: Some(self.creator.clone()),
+            cw20_address: self.cw20_address.clone(),
+            allowlist_phases: self.allowlist_phases.clone(),
+            stages: self.stages.clone(),
+            dutch_auction: self.dutch_auction.clone(),
+            burn_to_mint: self.burn_to_mint.clone(),
+            raffle: self.raffle.clone(),
+            token_gate: self.token_gate.clone(),
+            tiers: self.tiers.clone(),
+            reveal: self.reveal.clone(),
+            minter_handover: self.minter_handover.clone(),
         };
 
-        app.instantiate_contract(
-            code_id,
-            Addr::unchecked(self.creator.clone()),
-            &init_msg,
-            &[],
-            "Launchparty",
-            None,
-        )
-        .unwrap()
+        app.instantiate_contract(code_id, sender, &init_msg, &[], "Launchparty", None)
+            .unwrap()
     }
 
     #[track_caller]
-    pub fn build(self) -> Suite {
+    pub fn build(mut self) -> Suite {
         let mut app: App = App::default();
 
         app.update_block(|block| block.time = Timestamp::from_seconds(1571797419));
@@ -141,8 +471,75 @@ impl TestSuiteBuilder {
         let bs721_base_code_id = app.store_code(contract_bs721_base());
         let launchparty_code_id = app.store_code(contract_launchparty());
 
+        if !self.cw20_balances.is_empty() {
+            let cw20_code_id = app.store_code(contract_cw20_base());
+            let cw20_address = app
+                .instantiate_contract(
+                    cw20_code_id,
+                    Addr::unchecked(CREATOR),
+                    &cw20_base::msg::InstantiateMsg {
+                        name: "Social Token".to_string(),
+                        symbol: "SOCIAL".to_string(),
+                        decimals: 6,
+                        initial_balances: self.cw20_balances.clone(),
+                        mint: None,
+                        marketing: None,
+                    },
+                    &[],
+                    "cw20",
+                    None,
+                )
+                .unwrap();
+            self.price.denom = cw20_address.to_string();
+            self.cw20_address = Some(cw20_address.to_string());
+        }
+
+        // the tickets collection is redeemed in a burn-to-mint launchparty and opens the token
+        // gate.
+        let mut tickets_address = None;
+        if self.burn_to_mint.is_some() || self.token_gate.is_some() {
+            let address = app
+                .instantiate_contract(
+                    bs721_base_code_id,
+                    Addr::unchecked(CREATOR),
+                    &bs721_base::InstantiateMsg {
+                        name: "Tickets".to_string(),
+                        symbol: "TICKET".to_string(),
+                        minter: CREATOR.to_string(),
+                        uri: None,
+                        collection_info: CollectionInfo::<RoyaltyInfoResponse>::default(),
+                    },
+                    &[],
+                    "tickets",
+                    None,
+                )
+                .unwrap();
+            if let Some(burn_to_mint) = self.burn_to_mint.as_mut() {
+                burn_to_mint.collection = address.to_string();
+            }
+            if let Some(token_gate) = self.token_gate.as_mut() {
+                token_gate.collections = vec![address.to_string()];
+            }
+            tickets_address = Some(address);
+        }
+
+        // a cw20 launchparty is instantiated by the factory.
+        let sender = if self.cw20_address.is_some() {
+            let factory_code_id = app.store_code(contract_factory());
+            app.instantiate_contract(
+                factory_code_id,
+                Addr::unchecked(CREATOR),
+                &Empty {},
+                &[],
+                "factory",
+                None,
+            )
+            .unwrap()
+        } else {
+            Addr::unchecked(self.creator.clone())
+        };
         let contract_address =
-            self.instantiate_launchparty(&mut app, launchparty_code_id, bs721_base_code_id);
+            self.instantiate_launchparty(&mut app, launchparty_code_id, bs721_base_code_id, sender);
 
         app.init_modules(|router, _, storage| -> AnyResult<()> {
             for (addr, coin) in self.init_funds {
@@ -155,6 +552,8 @@ impl TestSuiteBuilder {
         Suite {
             app,
             contract_address,
+            cw20_address: self.cw20_address.map(Addr::unchecked),
+            tickets_address,
         }
     }
 }
@@ -165,12 +564,16 @@ pub struct Suite {
     app: App,
     /// Address of the launchparty contract.
     contract_address: Addr,
+    /// Address of the cw20 token used to pay for mints.
+    cw20_address: Option<Addr>,
+    /// Address of the collection redeemed in a burn-to-mint launchparty or opening the token gate.
+    tickets_address: Option<Addr>,
 }
 
 impl Suite {
-    /// Returns the contract address.
-    fn contract_address(&self) -> Addr {
-        self.contract_address.clone()
+    /// Returns the typed client of the launchparty contract.
+    fn launchparty(&self) -> LaunchpartyContract {
+        LaunchpartyContract(self.contract_address.clone())
     }
 
     /// Helper function to mint a bs721 token. The sender is defined as a const.
@@ -181,37 +584,285 @@ impl Suite {
         amount: u32,
         funds: Option<Coin>,
     ) -> AnyResult<AppResponse> {
-        let msg = ExecuteMsg::Mint { referral, amount };
+        let send_funds: Vec<Coin> = funds.map_or_else(Vec::new, |sent_coin| vec![sent_coin]);
+        let msg = self.launchparty().mint(amount, referral, send_funds)?;
 
+        self.app.execute(Addr::unchecked(sender.to_string()), msg)
+    }
+
+    /// Helper function to mint tickets of the burn-to-mint or token gate collection to `owner`.
+    pub fn mint_tickets(&mut self, owner: &str, token_ids: &[&str]) {
+        let tickets = self.tickets_address.clone().unwrap();
+        for token_id in token_ids {
+            let msg = bs721_base::ExecuteMsg::<Option<Empty>, Empty>::Mint(bs721_base::MintMsg {
+                token_id: token_id.to_string(),
+                owner: owner.to_string(),
+                token_uri: None,
+                content_hash: None,
+                seller_fee_bps: None,
+                payment_addr: None,
+                extension: None,
+            });
+            self.app
+                .execute_contract(Addr::unchecked(CREATOR), tickets.clone(), &msg, &[])
+                .unwrap();
+        }
+    }
+
+    /// Helper function to mint opening the token gate with the given tickets.
+    pub fn mint_gated(
+        &mut self,
+        sender: &str,
+        amount: u32,
+        token_ids: &[&str],
+        funds: Option<Coin>,
+    ) -> AnyResult<AppResponse> {
+        let tickets = self.tickets_address.clone().unwrap();
+        let gate_tokens = token_ids
+            .iter()
+            .map(|token_id| GateToken {
+                collection: tickets.to_string(),
+                token_id: token_id.to_string(),
+            })
+            .collect();
         let send_funds: Vec<Coin> = funds.map_or_else(Vec::new, |sent_coin| vec![sent_coin]);
+        let msg = self
+            .launchparty()
+            .mint_gated(amount, gate_tokens, send_funds)?;
 
-        self.app.execute_contract(
-            Addr::unchecked(sender.to_string()),
-            self.contract_address(),
-            &msg,
-            &send_funds,
-        )
+        self.app.execute(Addr::unchecked(sender), msg)
     }
 
-    /// Helper function to query launchparty contract configuration.
-    pub fn query_config(&self) -> Config {
+    /// Helper function to query the address that used a ticket to open the token gate.
+    pub fn query_gate_claim(&self, token_id: &str) -> Option<Addr> {
+        let tickets = self.tickets_address.clone().unwrap();
+        self.launchparty()
+            .gate_claim(&self.app.wrap(), tickets.as_str(), token_id)
+            .unwrap()
+            .claimed_by
+    }
+
+    /// Helper function to send a ticket of the burn-to-mint collection to the launchparty.
+    pub fn redeem(&mut self, sender: &str, token_id: &str) -> AnyResult<AppResponse> {
+        let tickets = self.tickets_address.clone().unwrap();
+        let msg = self.launchparty().redeem(tickets.as_str(), token_id)?;
+
+        self.app.execute(Addr::unchecked(sender), msg)
+    }
+
+    /// Helper function to query the tickets owned by an address.
+    pub fn query_tickets(&self, owner: &str) -> Vec<String> {
+        self.query_nft_token(self.tickets_address.clone().unwrap(), owner)
+    }
+
+    /// Helper function to query the tickets sent by an address for a redemption not yet
+    /// completed.
+    pub fn query_redeem_pending(&self, address: &str) -> RedeemPendingResponse {
+        self.launchparty()
+            .redeem_pending(&self.app.wrap(), address)
+            .unwrap()
+    }
+
+    /// Helper function to store and instantiate the mock mint hook contract.
+    pub fn instantiate_mint_hook(&mut self) -> Addr {
+        let code_id = self.app.store_code(contract_mint_hook());
         self.app
-            .wrap()
-            .query_wasm_smart(self.contract_address(), &QueryMsg::GetConfig {})
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(CREATOR),
+                &Empty {},
+                &[],
+                "mint-hook",
+                None,
+            )
+            .unwrap()
+    }
+
+    /// Helper function to mint a bs721 token to a recipient.
+    pub fn mint_to(
+        &mut self,
+        sender: impl ToString,
+        recipient: impl ToString,
+        amount: u32,
+        funds: Option<Coin>,
+    ) -> AnyResult<AppResponse> {
+        let send_funds: Vec<Coin> = funds.map_or_else(Vec::new, |sent_coin| vec![sent_coin]);
+        let msg = self
+            .launchparty()
+            .mint_to(amount, None, recipient.to_string(), send_funds)?;
+
+        self.app.execute(Addr::unchecked(sender.to_string()), msg)
+    }
+
+    /// Helper function to airdrop reserved editions.
+    pub fn airdrop_mint(
+        &mut self,
+        sender: impl ToString,
+        recipients: &[&str],
+    ) -> AnyResult<AppResponse> {
+        let recipients = recipients.iter().map(|r| r.to_string()).collect();
+        let msg = self.launchparty().airdrop_mint(recipients)?;
+
+        self.app.execute(Addr::unchecked(sender.to_string()), msg)
+    }
+
+    /// Helper function to mint a bs721 token paying with the cw20 token.
+    pub fn mint_cw20(
+        &mut self,
+        sender: impl ToString,
+        referral: Option<String>,
+        amount: u32,
+        payment: u128,
+    ) -> AnyResult<AppResponse> {
+        let msg = self.launchparty().mint_cw20(
+            self.cw20_address.clone().unwrap(),
+            Uint128::new(payment),
+            amount,
+            referral,
+            None,
+        )?;
+
+        self.app.execute(Addr::unchecked(sender.to_string()), msg)
+    }
+
+    /// Helper function to mint a bs721 token during an allowlist phase.
+    pub fn mint_allowlist(
+        &mut self,
+        sender: impl ToString,
+        amount: u32,
+        allowlist: AllowlistProof,
+        funds: Option<Coin>,
+    ) -> AnyResult<AppResponse> {
+        let send_funds: Vec<Coin> = funds.map_or_else(Vec::new, |sent_coin| vec![sent_coin]);
+        let msg = self
+            .launchparty()
+            .mint_allowlist(amount, None, allowlist, send_funds)?;
+
+        self.app.execute(Addr::unchecked(sender.to_string()), msg)
+    }
+
+    /// Helper function to replace the Merkle root of an allowlist phase.
+    pub fn update_merkle_root(
+        &mut self,
+        sender: impl ToString,
+        phase_id: u32,
+        merkle_root: impl Into<String>,
+    ) -> AnyResult<AppResponse> {
+        let msg = self
+            .launchparty()
+            .update_merkle_root(phase_id, merkle_root.into())?;
+
+        self.app.execute(Addr::unchecked(sender.to_string()), msg)
+    }
+
+    /// Helper function to execute a message on the launchparty built with its typed client.
+    pub fn execute_launchparty(
+        &mut self,
+        sender: impl ToString,
+        msg: impl FnOnce(&LaunchpartyContract) -> StdResult<CosmosMsg>,
+    ) -> AnyResult<AppResponse> {
+        let msg = msg(&self.launchparty())?;
+
+        self.app.execute(Addr::unchecked(sender.to_string()), msg)
+    }
+
+    /// Helper function to move the block time forward.
+    pub fn advance_time(&mut self, seconds: u64) {
+        self.app
+            .update_block(|block| block.time = block.time.plus_seconds(seconds));
+    }
+
+    /// Helper function to move to the next block.
+    pub fn next_block(&mut self) {
+        self.app.update_block(next_block);
+    }
+
+    /// Helper function to query the amount of tokens minted by an address in an allowlist phase.
+    pub fn query_allowlist_minted(
+        &self,
+        phase_id: u32,
+        address: impl Into<String>,
+    ) -> AllowlistMintedResponse {
+        self.launchparty()
+            .allowlist_minted(&self.app.wrap(), phase_id, address)
+            .unwrap()
+    }
+
+    /// Helper function to query the allowlist phases of the launchparty.
+    pub fn query_allowlist_phases(&self) -> AllowlistPhasesResponse {
+        self.launchparty()
+            .allowlist_phases(&self.app.wrap())
             .unwrap()
     }
 
+    /// Helper function to query the active stage of the public mint.
+    pub fn query_current_stage(&self) -> CurrentStageResponse {
+        self.launchparty().current_stage(&self.app.wrap()).unwrap()
+    }
+
+    /// Helper function to query the state of the crowdfund.
+    pub fn query_crowdfund(&self) -> CrowdfundResponse {
+        self.launchparty().crowdfund(&self.app.wrap()).unwrap()
+    }
+
+    /// Helper function to query the dutch auction.
+    pub fn query_dutch_auction(&self) -> DutchAuctionResponse {
+        self.launchparty().dutch_auction(&self.app.wrap()).unwrap()
+    }
+
+    /// Helper function to query the referral stats of an address.
+    pub fn query_referral_stats(&self, address: impl Into<String>) -> ReferralStatsResponse {
+        self.launchparty()
+            .referral_stats(&self.app.wrap(), address)
+            .unwrap()
+    }
+
+    /// Helper function to query the referrers sorted by amount earned.
+    pub fn query_top_referrers(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> TopReferrersResponse {
+        self.launchparty()
+            .top_referrers(&self.app.wrap(), start_after.map(String::from), limit)
+            .unwrap()
+    }
+
+    /// Helper function to query the pre-mint hooks.
+    pub fn query_premint_hooks(&self) -> HooksResponse {
+        self.launchparty().premint_hooks(&self.app.wrap()).unwrap()
+    }
+
+    /// Helper function to query the launchparty status.
+    pub fn query_status(&self) -> StatusResponse {
+        self.launchparty().status(&self.app.wrap()).unwrap()
+    }
+
+    /// Helper function to query launchparty contract configuration.
+    pub fn query_config(&self) -> Config {
+        self.launchparty().config(&self.app.wrap()).unwrap()
+    }
+
     /// Helper function to query the max number of tokens an address can mint.
     pub fn query_max_per_address(&self, address: impl Into<String>) -> MaxPerAddressResponse {
-        self.app
+        self.launchparty()
+            .max_per_address(&self.app.wrap(), address)
+            .unwrap()
+    }
+
+    /// Helper function to query the cw20 token balance of a specific address.
+    pub fn query_cw20_balance(&self, address: impl Into<String>) -> Uint128 {
+        let resp: BalanceResponse = self
+            .app
             .wrap()
             .query_wasm_smart(
-                self.contract_address(),
-                &QueryMsg::MaxPerAddress {
+                self.cw20_address.clone().unwrap(),
+                &Cw20QueryMsg::Balance {
                     address: address.into(),
                 },
             )
-            .unwrap()
+            .unwrap();
+        resp.balance
     }
 
     /// Helper function to query the balance of a specific address.
@@ -226,23 +877,134 @@ impl Suite {
             .unwrap()
     }
 
-    pub fn query_nft_token(
+    /// Helper function to query the URI of a bs721 token.
+    pub fn query_token_uri(
         &self,
         bs721_address: impl Into<String>,
-        owner: impl Into<String>,
-    ) -> Vec<String> {
-        let resp: bs721::TokensResponse = self
+        token_id: impl Into<String>,
+    ) -> Option<String> {
+        let bs721 = Bs721Contract::<Empty, Empty>(
+            Addr::unchecked(bs721_address.into()),
+            PhantomData,
+            PhantomData,
+        );
+        bs721
+            .nft_info::<_, Option<Empty>>(&self.app.wrap(), token_id)
+            .unwrap()
+            .token_uri
+    }
+
+    /// Helper function to query the content hash of a bs721 token.
+    pub fn query_token_content_hash(
+        &self,
+        bs721_address: impl Into<String>,
+        token_id: impl Into<String>,
+    ) -> Option<String> {
+        let bs721 = Bs721Contract::<Empty, Empty>(
+            Addr::unchecked(bs721_address.into()),
+            PhantomData,
+            PhantomData,
+        );
+        bs721
+            .nft_info::<_, Option<Empty>>(&self.app.wrap(), token_id)
+            .unwrap()
+            .content_hash
+    }
+
+    /// Helper function to enter raffle tickets.
+    pub fn enter_raffle(
+        &mut self,
+        sender: &str,
+        tickets: u32,
+        funds: Option<Coin>,
+    ) -> AnyResult<AppResponse> {
+        let send_funds: Vec<Coin> = funds.map_or_else(Vec::new, |sent_coin| vec![sent_coin]);
+        let msg = self.launchparty().enter_raffle(tickets, send_funds)?;
+
+        self.app.execute(Addr::unchecked(sender), msg)
+    }
+
+    /// Helper function to submit the beacon of the raffle, proved by the test beacon.
+    pub fn submit_beacon(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        let message = self.query_raffle().beacon_message.unwrap();
+        let msg = self.launchparty().submit_beacon(beacon_proof(&message))?;
+
+        self.app.execute(Addr::unchecked(sender), msg)
+    }
+
+    /// Helper function to query the state of the raffle.
+    pub fn query_raffle(&self) -> RaffleResponse {
+        self.launchparty().raffle(&self.app.wrap()).unwrap()
+    }
+
+    /// Helper function to query the raffle tickets of an address.
+    pub fn query_raffle_entry(&self, address: &str) -> RaffleEntryResponse {
+        self.launchparty()
+            .raffle_entry(&self.app.wrap(), address)
+            .unwrap()
+    }
+
+    /// Helper function to query the state of the delayed reveal.
+    pub fn query_reveal(&self) -> RevealResponse {
+        self.launchparty().reveal_state(&self.app.wrap()).unwrap()
+    }
+
+    /// Helper function to query the minter of the bs721 collection, which fails once renounced.
+    pub fn query_collection_minter(&self, bs721_address: impl Into<String>) -> StdResult<String> {
+        let resp: MinterResponse = self
             .app
+            .wrap()
+            .query_wasm_smart(bs721_address.into(), &Bs721QueryMsg::<Empty>::Minter {})?;
+        Ok(resp.minter)
+    }
+
+    /// Helper function to query the collection info of the bs721 collection.
+    pub fn query_collection_info(
+        &self,
+        bs721_address: impl Into<String>,
+    ) -> CollectionInfoResponse {
+        self.app
             .wrap()
             .query_wasm_smart(
                 bs721_address.into(),
-                &Bs721BaseQueryMsg::<Empty>::Tokens {
-                    owner: owner.into(),
-                    start_after: None,
-                    limit: None,
-                },
+                &Bs721QueryMsg::<Empty>::CollectionInfo {},
             )
-            .unwrap();
-        resp.tokens
+            .unwrap()
+    }
+
+    /// Helper function to query the tier of a minted edition.
+    pub fn query_token_tier(&self, token_id: u32) -> TokenTierResponse {
+        self.launchparty()
+            .token_tier(&self.app.wrap(), token_id)
+            .unwrap()
+    }
+
+    pub fn query_nft_token(
+        &self,
+        bs721_address: impl Into<String>,
+        owner: impl Into<String>,
+    ) -> Vec<String> {
+        let bs721 = Bs721Contract::<Empty, Empty>(
+            Addr::unchecked(bs721_address.into()),
+            PhantomData,
+            PhantomData,
+        );
+        bs721
+            .tokens(&self.app.wrap(), owner, None, None)
+            .unwrap()
+            .tokens
+    }
+
+    pub fn query_token_metadata(
+        &self,
+        bs721_address: impl Into<String>,
+        token_id: impl Into<String>,
+    ) -> TokenMetadataResponse {
+        let bs721 = Bs721Contract::<Empty, Empty>(
+            Addr::unchecked(bs721_address.into()),
+            PhantomData,
+            PhantomData,
+        );
+        bs721.token_metadata(&self.app.wrap(), token_id).unwrap()
     }
 }
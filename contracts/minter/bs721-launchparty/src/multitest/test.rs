@@ -1,6 +1,15 @@
-use cosmwasm_std::{coin, Addr, Uint128};
+use bs_mint_hooks::MintHookError;
+use cosmwasm_std::{coin, Addr, Event, Timestamp, Uint128};
+use sha2::{Digest, Sha256};
 
-use super::suite::TestSuiteBuilder;
+use super::suite::{beacon_proof, TestSuiteBuilder, BLOCKED, CREATOR};
+use crate::msg::{
+    AllowlistPhase, AllowlistProof, AuctionDecay, AuctionPricePoint, DelayedReveal, DutchAuction,
+    EditionTier, MintLimitTarget, MintLimits, MintStage, MintWindow, MinterHandover, PartyType,
+    RedeemAction, TierAssignment, TokenRange,
+};
+use crate::state::Trait;
+use crate::ContractError;
 
 #[test]
 fn instantiate() {
@@ -49,6 +58,52 @@ fn mint_single_no_referral() {
         .unwrap_err();
 }
 
+#[test]
+fn mint_exports_edition_metadata() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .build();
+
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+
+    let metadata = suite.query_token_metadata("contract1", "1");
+    assert!(
+        metadata.json.contains(concat!(
+            r#""attributes":[{"display_type":"number","trait_type":"Edition","value":"1"},"#,
+            r#"{"display_type":"number","trait_type":"Max Editions","value":"1"},"#,
+            r#"{"trait_type":"Edition Type","value":"Limited Edition"}]"#
+        )),
+        "expected the edition attributes stored by the collection, got {}",
+        metadata.json
+    );
+}
+
+#[test]
+fn mint_sets_content_hash() {
+    let content_hash = "a".repeat(64);
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(2))
+        .with_content_hash(&content_hash)
+        .build();
+
+    suite
+        .mint("address1", None, 2, Some(coin(2, "ubtsg")))
+        .unwrap();
+
+    for token_id in ["1", "2"] {
+        assert_eq!(
+            suite.query_token_content_hash("contract1", token_id),
+            Some(content_hash.clone()),
+            "expected every edition minted with the content hash of the launchparty"
+        );
+    }
+}
+
 #[test]
 fn mint_single_with_referral() {
     let mut suite = TestSuiteBuilder::new()
@@ -57,18 +112,21 @@ fn mint_single_with_referral() {
         .with_price(coin(10, "ubtsg"))
         .build();
 
-    let referral = Some("referral".to_string());
+    let referral = "referral".to_string();
     suite
-        .mint("address1", referral.clone(), 1, Some(coin(10, "ubtsg")))
+        .mint(
+            "address1",
+            Some(referral.clone()),
+            1,
+            Some(coin(10, "ubtsg")),
+        )
         .unwrap();
 
     // retrieve royalties contract to query it
     let royalties_address = suite.query_config().payment_address;
 
     assert_eq!(
-        suite
-            .query_address_balance(referral.unwrap(), "ubtsg")
-            .amount,
+        suite.query_address_balance(referral, "ubtsg").amount,
         Uint128::one(),
         "expected to have the referral address balance equal to the 10% of the NFT price"
     );
@@ -233,3 +291,2620 @@ fn query_max_per_address() {
         );
     }
 }
+
+/// Builds a two leaves allowlist Merkle tree returning its root and the proof of each leaf.
+fn allowlist_tree(leaves: [(&str, u32); 2]) -> (String, [String; 2]) {
+    let hashes: Vec<[u8; 32]> = leaves
+        .iter()
+        .map(|(address, allocation)| {
+            Sha256::digest(format!("{address}{allocation}").as_bytes()).into()
+        })
+        .collect();
+    let (first, second) = if hashes[0] <= hashes[1] {
+        (hashes[0], hashes[1])
+    } else {
+        (hashes[1], hashes[0])
+    };
+    let root = Sha256::new()
+        .chain_update(first)
+        .chain_update(second)
+        .finalize();
+
+    (
+        hex::encode(root),
+        [hex::encode(hashes[1]), hex::encode(hashes[0])],
+    )
+}
+
+#[test]
+fn mint_allowlist() {
+    let (root, proofs) = allowlist_tree([("address1", 2), ("address2", 1)]);
+    let now = Timestamp::from_seconds(1571797419);
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address3", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_max_per_address(1)
+        .with_start_time(now.plus_seconds(100))
+        .with_allowlist_phase(AllowlistPhase {
+            merkle_root: root,
+            start_time: now,
+            end_time: now.plus_seconds(100),
+            price: coin(5, "ubtsg"),
+        })
+        .build();
+
+    let proof = AllowlistProof {
+        phase_id: 0,
+        allocation: 2,
+        proof: vec![proofs[0].clone()],
+    };
+
+    let err = suite
+        .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotStarted {},
+        "expected to fail since the public mint is not started"
+    );
+
+    {
+        let err = suite
+            .mint_allowlist("address1", 1, proof.clone(), Some(coin(10, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPaymentAmount(Uint128::new(10), Uint128::new(5)),
+            "expected to fail since the phase price must be paid"
+        );
+    }
+
+    suite
+        .mint_allowlist("address1", 2, proof.clone(), Some(coin(10, "ubtsg")))
+        .unwrap();
+    assert_eq!(suite.query_allowlist_minted(0, "address1").minted, 2);
+
+    {
+        let err = suite
+            .mint_allowlist("address1", 1, proof.clone(), Some(coin(5, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AllowlistAllocationExceeded { remaining: 0 },
+        );
+    }
+
+    {
+        let err = suite
+            .mint_allowlist("address3", 1, proof.clone(), Some(coin(5, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidMerkleProof {},
+            "expected to fail since address3 is not in the allowlist"
+        );
+    }
+
+    suite.advance_time(100);
+
+    {
+        let err = suite
+            .mint_allowlist("address1", 1, proof, Some(coin(5, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AllowlistPhaseNotActive { phase_id: 0 },
+        );
+    }
+
+    // allowlist mints are not counted in the public maximum per address
+    suite
+        .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+
+    let config = suite.query_config();
+    assert_eq!(
+        vec!["1", "2", "3"],
+        suite.query_nft_token(config.bs721_address.unwrap(), "address1"),
+    );
+}
+
+#[test]
+fn update_merkle_root() {
+    let (root, _) = allowlist_tree([("address1", 2), ("address2", 1)]);
+    let now = Timestamp::from_seconds(1571797419);
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_price(coin(10, "ubtsg"))
+        .with_allowlist_phase(AllowlistPhase {
+            merkle_root: root.clone(),
+            start_time: now.plus_seconds(10),
+            end_time: now.plus_seconds(100),
+            price: coin(5, "ubtsg"),
+        })
+        .build();
+
+    let (new_root, _) = allowlist_tree([("address3", 1), ("address4", 1)]);
+
+    {
+        let err = suite
+            .update_merkle_root("address1", 0, new_root.clone())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {},
+        );
+    }
+
+    {
+        let err = suite.update_merkle_root(CREATOR, 0, "invalid").unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidMerkleRoot {},
+        );
+    }
+
+    {
+        let err = suite
+            .update_merkle_root(CREATOR, 1, new_root.clone())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AllowlistPhaseNotFound { phase_id: 1 },
+        );
+    }
+
+    suite
+        .update_merkle_root(CREATOR, 0, new_root.clone())
+        .unwrap();
+    assert_eq!(
+        suite.query_allowlist_phases().phases[0].phase.merkle_root,
+        new_root
+    );
+
+    suite.advance_time(10);
+
+    let err = suite.update_merkle_root(CREATOR, 0, root).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AllowlistPhaseStarted { phase_id: 0 },
+        "expected to fail since the phase already started"
+    );
+}
+
+#[test]
+fn mint_stages() {
+    let now = Timestamp::from_seconds(1571797419);
+    let stage = |name: &str, start: u64, end: Option<u64>, price: u128| MintStage {
+        name: name.to_string(),
+        start_time: now.plus_seconds(start),
+        end_time: end.map(|end| now.plus_seconds(end)),
+        price: coin(price, "ubtsg"),
+        max_per_address: None,
+        max_supply: None,
+    };
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_funds("address3", &[coin(1_000, "ubtsg")])
+        .with_price(coin(3, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_stage(MintStage {
+            max_per_address: Some(1),
+            max_supply: Some(2),
+            ..stage("og", 0, Some(100), 1)
+        })
+        .with_stage(MintStage {
+            max_per_address: Some(2),
+            ..stage("presale", 100, Some(200), 2)
+        })
+        .with_stage(stage("public", 200, None, 3))
+        .build();
+
+    let current = suite.query_current_stage().stage.unwrap();
+    assert_eq!(current.stage_id, 0);
+    assert_eq!(current.stage.name, "og");
+    assert_eq!(suite.query_max_per_address("address1").remaining, Some(1));
+
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+    assert_eq!(suite.query_max_per_address("address1").remaining, Some(0));
+
+    {
+        let err = suite
+            .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::MaxPerAddressExceeded { remaining: 0 },
+            "expected to fail since the stage limit per address is reached"
+        );
+    }
+
+    suite
+        .mint("address2", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+    assert_eq!(suite.query_current_stage().stage.unwrap().minted, 2);
+
+    {
+        let err = suite
+            .mint("address3", None, 1, Some(coin(1, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::StageSupplyExceeded { remaining: 0 },
+            "expected to fail since the stage supply is reached"
+        );
+    }
+
+    suite.advance_time(100);
+    assert_eq!(
+        suite.query_current_stage().stage.unwrap().stage.name,
+        "presale"
+    );
+
+    {
+        let err = suite
+            .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPaymentAmount(Uint128::new(1), Uint128::new(2)),
+            "expected to fail since the presale price must be paid"
+        );
+    }
+
+    suite
+        .mint("address1", None, 2, Some(coin(4, "ubtsg")))
+        .unwrap();
+
+    suite.advance_time(100);
+    assert_eq!(
+        suite.query_current_stage().stage.unwrap().stage.name,
+        "public"
+    );
+    assert_eq!(suite.query_max_per_address("address1").remaining, None);
+
+    suite
+        .mint("address1", None, 3, Some(coin(9, "ubtsg")))
+        .unwrap();
+
+    let config = suite.query_config();
+    assert_eq!(
+        suite
+            .query_nft_token(config.bs721_address.unwrap(), "address1")
+            .len(),
+        6
+    );
+}
+
+#[test]
+fn mint_between_stages() {
+    let now = Timestamp::from_seconds(1571797419);
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_stage(MintStage {
+            name: "presale".to_string(),
+            start_time: now,
+            end_time: Some(now.plus_seconds(10)),
+            price: coin(1, "ubtsg"),
+            max_per_address: None,
+            max_supply: None,
+        })
+        .with_stage(MintStage {
+            name: "public".to_string(),
+            start_time: now.plus_seconds(20),
+            end_time: None,
+            price: coin(1, "ubtsg"),
+            max_per_address: None,
+            max_supply: None,
+        })
+        .build();
+
+    suite.advance_time(10);
+    assert!(suite.query_current_stage().stage.is_none());
+
+    let err = suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoActiveStage {},
+    );
+}
+
+#[test]
+fn mint_with_cw20() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_cw20_funds("address1", 1_000)
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(100, "ubtsg"))
+        .with_referral_fee_bps(1_000)
+        .with_protocol_fee_bps(500)
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::MaxEdition(10))
+        .build();
+    let config = suite.query_config();
+
+    {
+        let err = suite
+            .mint("address1", None, 1, Some(coin(100, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Payment(cw_utils::PaymentError::NonPayable {}),
+            "expected to fail since native coins are not accepted"
+        );
+    }
+
+    {
+        let err = suite.mint_cw20("address1", None, 2, 100).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPaymentAmount(Uint128::new(100), Uint128::new(200)),
+        );
+    }
+
+    suite
+        .mint_cw20("address1", Some("referral".to_string()), 2, 200)
+        .unwrap();
+
+    assert_eq!(suite.query_cw20_balance("address1"), Uint128::new(800));
+    assert_eq!(
+        suite.query_cw20_balance("referral"),
+        Uint128::new(20),
+        "expected 10% of the price sent to the referral"
+    );
+    assert_eq!(
+        suite.query_cw20_balance("bs721_admin"),
+        Uint128::new(10),
+        "expected 5% of the price sent to the protocol address"
+    );
+    assert_eq!(suite.query_cw20_balance("royalties"), Uint128::new(170));
+
+    assert_eq!(
+        suite.query_nft_token(config.bs721_address.unwrap(), "address1"),
+        vec!["1", "2"],
+    );
+}
+
+#[test]
+fn mint_randomized_until_end_time() {
+    let end_time = Timestamp::from_seconds(1571797419).plus_seconds(100);
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEditionUntil {
+            max_editions: 5,
+            end_time,
+        })
+        .with_randomized()
+        .build();
+
+    suite
+        .mint("address1", None, 5, Some(coin(5, "ubtsg")))
+        .unwrap();
+
+    let mut tokens = suite.query_nft_token("contract1", "address1");
+    tokens.sort();
+    assert_eq!(
+        tokens,
+        vec!["1", "2", "3", "4", "5"],
+        "expected every index of the collection assigned once"
+    );
+    for token_id in tokens {
+        assert_eq!(
+            suite.query_token_uri("contract1", token_id.clone()),
+            Some(format!("uri/{token_id}.json")),
+            "expected the metadata file of the drawn index"
+        );
+    }
+}
+
+#[test]
+fn mint_randomized_rejects_contracts() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("contract1", &[coin(1_000, "ubtsg")])
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(5))
+        .with_randomized()
+        .build();
+
+    // the collection stands for any contract minting.
+    let err = suite
+        .mint("contract1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ContractRandomMint {},
+        "expected contracts not allowed to retry the draw by reverting the mint"
+    );
+
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+}
+
+#[test]
+fn mint_randomized() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(5))
+        .with_randomized()
+        .build();
+
+    suite
+        .mint("address1", None, 5, Some(coin(5, "ubtsg")))
+        .unwrap();
+
+    let bs721_address = suite.query_config().bs721_address.unwrap();
+    assert_eq!(
+        suite.query_nft_token(bs721_address.clone(), "address1"),
+        vec!["1", "2", "3", "4", "5"],
+        "expected every index of the collection assigned once"
+    );
+
+    assert_eq!(
+        suite.query_token_uri(bs721_address, "3"),
+        Some("uri/3.json".to_string())
+    );
+
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap_err();
+}
+
+#[test]
+fn creator_controls() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(10))
+        .build();
+
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| lp.pause())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {},
+            "expected to fail since only the creator can pause"
+        );
+    }
+
+    suite.execute_launchparty(CREATOR, |lp| lp.pause()).unwrap();
+    {
+        let err = suite
+            .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Paused {}
+        );
+    }
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.resume())
+        .unwrap();
+
+    {
+        let err = suite
+            .execute_launchparty(CREATOR, |lp| lp.update_price(coin(5, "uatom"), None))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPriceDenom {}
+        );
+    }
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.update_price(coin(5, "ubtsg"), None))
+        .unwrap();
+    assert_eq!(suite.query_config().price, coin(5, "ubtsg"));
+    assert_eq!(
+        suite.query_current_stage().stage.unwrap().stage.price,
+        coin(5, "ubtsg")
+    );
+    suite
+        .mint("address1", None, 1, Some(coin(5, "ubtsg")))
+        .unwrap();
+
+    {
+        let err = suite
+            .execute_launchparty(CREATOR, |lp| lp.update_max_per_address(Some(0)))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::ZeroMaxPerAddress {}
+        );
+    }
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.update_max_per_address(Some(2)))
+        .unwrap();
+    assert_eq!(suite.query_max_per_address("address1").remaining, Some(1));
+
+    {
+        let start_time = Timestamp::from_seconds(1571797419).plus_seconds(10);
+        let err = suite
+            .execute_launchparty(CREATOR, |lp| lp.update_start_time(start_time))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AlreadyStarted {},
+            "expected to fail since the launchparty already started"
+        );
+    }
+
+    let res = suite
+        .execute_launchparty(CREATOR, |lp| lp.end_party())
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "end_party")));
+
+    let err = suite
+        .mint("address1", None, 1, Some(coin(5, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PartyEnded {}
+    );
+}
+
+#[test]
+fn update_start_time() {
+    let now = Timestamp::from_seconds(1571797419);
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_start_time(now.plus_seconds(100))
+        .with_stage(MintStage {
+            name: "presale".to_string(),
+            start_time: now.plus_seconds(100),
+            end_time: Some(now.plus_seconds(200)),
+            price: coin(1, "ubtsg"),
+            max_per_address: None,
+            max_supply: None,
+        })
+        .build();
+
+    {
+        let err = suite
+            .execute_launchparty(CREATOR, |lp| lp.update_start_time(now))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidStartTime {}
+        );
+    }
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.update_start_time(now.plus_seconds(50)))
+        .unwrap();
+    assert_eq!(suite.query_config().start_time, now.plus_seconds(50));
+
+    suite.advance_time(50);
+    let stage = suite.query_current_stage().stage.unwrap().stage;
+    assert_eq!(
+        stage.end_time,
+        Some(now.plus_seconds(150)),
+        "expected stages moved by the same offset"
+    );
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+}
+
+#[test]
+fn update_start_time_moves_dutch_auction() {
+    let now = Timestamp::from_seconds(1571797419);
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_start_time(now.plus_seconds(100))
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_dutch_auction(DutchAuction {
+            start_price: Uint128::new(100),
+            floor_price: Uint128::new(10),
+            start_time: now.plus_seconds(100),
+            end_time: now.plus_seconds(190),
+            decay: AuctionDecay::Linear,
+            rebate: false,
+        })
+        .build();
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.update_start_time(now.plus_seconds(200)))
+        .unwrap();
+
+    let auction = suite.query_dutch_auction().auction.unwrap();
+    assert_eq!(
+        (auction.start_time, auction.end_time),
+        (now.plus_seconds(200), now.plus_seconds(290)),
+        "expected the auction moved by the same offset"
+    );
+
+    suite.advance_time(200);
+    assert_eq!(
+        suite.query_dutch_auction().current_price,
+        Some(Uint128::new(100)),
+        "expected the auction starting from its start price"
+    );
+    suite
+        .mint("address1", None, 1, Some(coin(100, "ubtsg")))
+        .unwrap();
+}
+
+#[test]
+fn crowdfund_refund() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_referral_fee_bps(1_000)
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::Duration(100))
+        .with_min_raise(100)
+        .build();
+    let bs721_address = suite.query_config().bs721_address.unwrap().to_string();
+
+    suite
+        .mint("address1", None, 2, Some(coin(20, "ubtsg")))
+        .unwrap();
+    suite
+        .mint(
+            "address2",
+            Some("referral".to_string()),
+            1,
+            Some(coin(10, "ubtsg")),
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_address_balance("royalties", "ubtsg").amount,
+        Uint128::zero(),
+        "expected funds escrowed in the contract"
+    );
+    assert_eq!(
+        suite.query_address_balance("referral", "ubtsg").amount,
+        Uint128::zero()
+    );
+    let stats = suite.query_referral_stats("referral");
+    assert_eq!(stats.mints, 1);
+    assert_eq!(stats.earned, coin(1, "ubtsg"));
+
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| {
+                lp.refund(bs721_address.clone(), "1".to_string())
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::CrowdfundNotEnded {}
+        );
+    }
+
+    suite.advance_time(101);
+
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| lp.release())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::MinRaiseNotReached {}
+        );
+    }
+
+    suite
+        .execute_launchparty("address1", |lp| {
+            lp.refund(bs721_address.clone(), "1".to_string())
+        })
+        .unwrap();
+    suite
+        .execute_launchparty("address2", |lp| {
+            lp.refund(bs721_address.clone(), "3".to_string())
+        })
+        .unwrap();
+
+    assert_eq!(
+        suite.query_address_balance("address1", "ubtsg").amount,
+        Uint128::new(990)
+    );
+    assert_eq!(
+        suite.query_address_balance("address2", "ubtsg").amount,
+        Uint128::new(1_000),
+        "expected a full refund, referral fee included"
+    );
+    assert_eq!(
+        suite.query_nft_token(bs721_address.clone(), "address1"),
+        vec!["2"]
+    );
+    assert_eq!(suite.query_crowdfund().raised, Uint128::new(10));
+    let stats = suite.query_referral_stats("referral");
+    assert_eq!(stats.mints, 0);
+    assert_eq!(stats.earned, coin(0, "ubtsg"));
+}
+
+#[test]
+fn crowdfund_release() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_referral_fee_bps(1_000)
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::Duration(100))
+        .with_min_raise(30)
+        .build();
+    let bs721_address = suite.query_config().bs721_address.unwrap().to_string();
+
+    suite
+        .mint(
+            "address1",
+            Some("referral".to_string()),
+            3,
+            Some(coin(30, "ubtsg")),
+        )
+        .unwrap();
+
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| lp.release())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::CrowdfundNotEnded {}
+        );
+    }
+
+    suite.advance_time(101);
+
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| {
+                lp.refund(bs721_address.clone(), "1".to_string())
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::MinRaiseReached {}
+        );
+    }
+
+    suite
+        .execute_launchparty("anyone", |lp| lp.release())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("royalties", "ubtsg").amount,
+        Uint128::new(27)
+    );
+    assert!(suite.query_crowdfund().released);
+
+    suite
+        .execute_launchparty("referral", |lp| lp.claim_referral())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("referral", "ubtsg").amount,
+        Uint128::new(3)
+    );
+
+    let err = suite
+        .execute_launchparty("referral", |lp| lp.claim_referral())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NothingToClaim {}
+    );
+}
+
+#[test]
+fn query_status() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(3))
+        .build();
+
+    let status = suite.query_status();
+    assert!(status.active);
+    assert_eq!(status.minted, 0);
+    assert_eq!(status.remaining, Some(3));
+    assert_eq!(status.end_time, None);
+    assert_eq!(status.raised, coin(0, "ubtsg"));
+    assert_eq!(status.bs721_address, Some(Addr::unchecked("contract1")));
+
+    suite
+        .mint("address1", None, 2, Some(coin(20, "ubtsg")))
+        .unwrap();
+
+    let status = suite.query_status();
+    assert!(status.active);
+    assert_eq!(status.minted, 2);
+    assert_eq!(status.remaining, Some(1));
+    assert_eq!(status.raised, coin(20, "ubtsg"));
+
+    suite
+        .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+
+    let status = suite.query_status();
+    assert!(!status.active, "expected party not active once sold out");
+    assert_eq!(status.remaining, Some(0));
+}
+
+#[test]
+fn query_status_duration() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_party_type(PartyType::Duration(100))
+        .build();
+
+    let status = suite.query_status();
+    assert!(status.active);
+    assert_eq!(status.remaining, None);
+    assert_eq!(
+        status.end_time,
+        Some(Timestamp::from_seconds(1571797419).plus_seconds(100))
+    );
+
+    suite.advance_time(101);
+    assert!(!suite.query_status().active);
+}
+
+#[test]
+fn mint_to_recipient() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("payer", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_max_per_address(1)
+        .build();
+
+    suite
+        .mint_to("payer", "address1", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    assert_eq!(suite.query_nft_token("contract1", "address1"), vec!["1"]);
+    assert!(suite.query_nft_token("contract1", "payer").is_empty());
+
+    // limits are applied to the payer by default.
+    let err = suite
+        .mint_to("payer", "address2", 1, Some(coin(10, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxPerAddressExceeded { remaining: 0 }
+    );
+}
+
+#[test]
+fn mint_to_recipient_limit() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("payer", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_max_per_address(1)
+        .with_limit_target(MintLimitTarget::Recipient)
+        .build();
+
+    suite
+        .mint_to("payer", "address1", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    suite
+        .mint_to("payer", "address2", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+
+    let err = suite
+        .mint_to("payer", "address1", 1, Some(coin(10, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxPerAddressExceeded { remaining: 0 },
+        "expected to fail since the recipient reached its limit"
+    );
+}
+
+#[test]
+fn airdrop_mint() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(3))
+        .with_reserved_supply(2)
+        .build();
+
+    assert_eq!(suite.query_status().remaining, Some(1));
+
+    {
+        let err = suite.airdrop_mint("address1", &["address2"]).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {},
+            "expected to fail since only the creator can airdrop"
+        );
+    }
+
+    suite
+        .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    {
+        let err = suite
+            .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::PartyEnded {},
+            "expected to fail since the remaining editions are reserved"
+        );
+    }
+
+    {
+        let err = suite
+            .airdrop_mint(CREATOR, &["address2", "address3", "address4"])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::ReservedSupplyExceeded { remaining: 2 }
+        );
+    }
+
+    suite
+        .airdrop_mint(CREATOR, &["address2", "address3"])
+        .unwrap();
+    assert_eq!(suite.query_nft_token("contract1", "address2"), vec!["2"]);
+    assert_eq!(suite.query_nft_token("contract1", "address3"), vec!["3"]);
+    assert_eq!(
+        suite.query_address_balance("contract2", "ubtsg"),
+        coin(10, "ubtsg")
+    );
+
+    let status = suite.query_status();
+    assert_eq!(status.minted, 3);
+    assert_eq!(status.remaining, Some(0));
+    assert_eq!(status.reserved, 0);
+}
+
+#[test]
+fn mint_hooks() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds(BLOCKED, &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_payment_address("payment")
+        .build();
+    let hook = suite.instantiate_mint_hook();
+
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| lp.add_premint_hook(hook.to_string()))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {},
+            "expected to fail since only the creator can add hooks"
+        );
+    }
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.add_premint_hook(hook.to_string()))
+        .unwrap();
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.add_postmint_hook(hook.to_string()))
+        .unwrap();
+    assert_eq!(suite.query_premint_hooks().hooks, vec![hook.to_string()]);
+
+    let res = suite
+        .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    for action in ["premint_hook", "postmint_hook"] {
+        assert!(res.has_event(
+            &Event::new("wasm")
+                .add_attribute("action", action)
+                .add_attribute("token_id", "1")
+        ));
+    }
+
+    let err = suite
+        .mint(BLOCKED, None, 1, Some(coin(10, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MintHook(MintHookError::PreMintHookFailed {}),
+        "expected to fail since the pre-mint hook rejects the buyer"
+    );
+    assert!(suite.query_nft_token("contract1", BLOCKED).is_empty());
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.remove_premint_hook(hook.to_string()))
+        .unwrap();
+    assert!(suite.query_premint_hooks().hooks.is_empty());
+
+    suite
+        .mint(BLOCKED, None, 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    assert_eq!(suite.query_nft_token("contract1", BLOCKED), vec!["2"]);
+}
+
+#[test]
+fn mint_dutch_auction() {
+    let start_time = Timestamp::from_seconds(1571797419);
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_dutch_auction(DutchAuction {
+            start_price: Uint128::new(100),
+            floor_price: Uint128::new(10),
+            start_time,
+            end_time: start_time.plus_seconds(90),
+            decay: AuctionDecay::Linear,
+            rebate: false,
+        })
+        .build();
+
+    let auction = suite.query_dutch_auction();
+    assert_eq!(
+        auction.curve,
+        vec![
+            AuctionPricePoint {
+                time: start_time,
+                price: Uint128::new(100),
+            },
+            AuctionPricePoint {
+                time: start_time.plus_seconds(90),
+                price: Uint128::new(10),
+            },
+        ]
+    );
+    assert_eq!(auction.current_price, Some(Uint128::new(100)));
+
+    suite
+        .mint("address1", None, 1, Some(coin(100, "ubtsg")))
+        .unwrap();
+
+    suite.advance_time(45);
+    {
+        let err = suite
+            .mint("address1", None, 1, Some(coin(100, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPaymentAmount(Uint128::new(100), Uint128::new(55)),
+            "expected to fail since the price decreased"
+        );
+    }
+    suite
+        .mint("address1", None, 1, Some(coin(55, "ubtsg")))
+        .unwrap();
+
+    let auction = suite.query_dutch_auction();
+    assert_eq!(auction.current_price, Some(Uint128::new(55)));
+    assert_eq!(auction.clearing_price, Some(Uint128::new(55)));
+    assert_eq!(auction.sold, 2);
+    assert_eq!(
+        suite.query_address_balance("contract2", "ubtsg"),
+        coin(155, "ubtsg")
+    );
+
+    suite.advance_time(100);
+    suite
+        .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+}
+
+#[test]
+fn dutch_auction_rebate() {
+    let start_time = Timestamp::from_seconds(1571797419);
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(3))
+        .with_dutch_auction(DutchAuction {
+            start_price: Uint128::new(100),
+            floor_price: Uint128::new(20),
+            start_time,
+            end_time: start_time.plus_seconds(40),
+            decay: AuctionDecay::Step { interval: 10 },
+            rebate: true,
+        })
+        .build();
+
+    assert_eq!(suite.query_dutch_auction().curve.len(), 5);
+
+    suite
+        .mint("address1", None, 2, Some(coin(200, "ubtsg")))
+        .unwrap();
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| lp.claim_rebate())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::PartyNotEnded {}
+        );
+    }
+
+    // the price decreases only at the end of each step.
+    suite.advance_time(25);
+    suite
+        .mint("address2", None, 1, Some(coin(60, "ubtsg")))
+        .unwrap();
+
+    // only the floor price is paid out during the auction.
+    assert_eq!(
+        suite.query_address_balance("contract2", "ubtsg"),
+        coin(60, "ubtsg")
+    );
+
+    // address1 paid 200 for two tokens with a clearing price of 60.
+    suite
+        .execute_launchparty("address1", |lp| lp.claim_rebate())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("address1", "ubtsg"),
+        coin(880, "ubtsg")
+    );
+    {
+        let err = suite
+            .execute_launchparty("address2", |lp| lp.claim_rebate())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NothingToClaim {}
+        );
+    }
+
+    suite
+        .execute_launchparty("anyone", |lp| lp.settle_auction())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("contract2", "ubtsg"),
+        coin(180, "ubtsg")
+    );
+    {
+        let err = suite
+            .execute_launchparty("anyone", |lp| lp.settle_auction())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AlreadySettled {}
+        );
+    }
+}
+
+#[test]
+fn dutch_auction_rebate_pays_referral() {
+    let start_time = Timestamp::from_seconds(1571797419);
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_referral_fee_bps(1_000)
+        .with_party_type(PartyType::MaxEdition(3))
+        .with_dutch_auction(DutchAuction {
+            start_price: Uint128::new(100),
+            floor_price: Uint128::new(20),
+            start_time,
+            end_time: start_time.plus_seconds(40),
+            decay: AuctionDecay::Step { interval: 10 },
+            rebate: true,
+        })
+        .build();
+
+    suite
+        .mint(
+            "address1",
+            Some("referral".to_string()),
+            2,
+            Some(coin(200, "ubtsg")),
+        )
+        .unwrap();
+    suite.advance_time(25);
+    suite
+        .mint("address2", None, 1, Some(coin(60, "ubtsg")))
+        .unwrap();
+
+    // the referral fee is paid at mint only on the floor price.
+    assert_eq!(
+        suite.query_address_balance("referral", "ubtsg"),
+        coin(4, "ubtsg")
+    );
+    assert_eq!(
+        suite.query_referral_stats("referral").claimable,
+        coin(0, "ubtsg")
+    );
+
+    suite
+        .execute_launchparty("anyone", |lp| lp.settle_auction())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("contract2", "ubtsg"),
+        coin(168, "ubtsg")
+    );
+
+    // the fee on the 40 held over the floor price of each referred token is claimable.
+    let stats = suite.query_referral_stats("referral");
+    assert_eq!(stats.earned, coin(12, "ubtsg"));
+    assert_eq!(stats.claimable, coin(8, "ubtsg"));
+
+    suite
+        .execute_launchparty("referral", |lp| lp.claim_referral())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("referral", "ubtsg"),
+        coin(12, "ubtsg")
+    );
+    let stats = suite.query_referral_stats("referral");
+    assert_eq!(stats.earned, coin(12, "ubtsg"));
+    assert_eq!(stats.claimable, coin(0, "ubtsg"));
+    {
+        let err = suite
+            .execute_launchparty("referral", |lp| lp.claim_referral())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NothingToClaim {}
+        );
+    }
+}
+
+#[test]
+fn referral_stats() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(100, "ubtsg"))
+        .with_referral_fee_bps(1_000)
+        .with_party_type(PartyType::MaxEdition(10))
+        .build();
+
+    suite
+        .mint(
+            "address1",
+            Some("referral1".to_string()),
+            2,
+            Some(coin(200, "ubtsg")),
+        )
+        .unwrap();
+    suite
+        .mint(
+            "address1",
+            Some("referral2".to_string()),
+            1,
+            Some(coin(100, "ubtsg")),
+        )
+        .unwrap();
+    suite
+        .mint(
+            "address1",
+            Some("referral3".to_string()),
+            4,
+            Some(coin(400, "ubtsg")),
+        )
+        .unwrap();
+    suite
+        .mint(
+            "address1",
+            Some("referral1".to_string()),
+            1,
+            Some(coin(100, "ubtsg")),
+        )
+        .unwrap();
+
+    let stats = suite.query_referral_stats("referral1");
+    assert_eq!(stats.mints, 3);
+    assert_eq!(stats.earned, coin(30, "ubtsg"));
+    assert_eq!(
+        stats.claimable,
+        coin(0, "ubtsg"),
+        "expected fees sent on every mint"
+    );
+    assert_eq!(
+        suite.query_address_balance("referral1", "ubtsg"),
+        coin(30, "ubtsg")
+    );
+
+    let stats = suite.query_referral_stats("address1");
+    assert_eq!(stats.mints, 0);
+    assert_eq!(stats.earned, coin(0, "ubtsg"));
+
+    let top = suite.query_top_referrers(None, Some(2));
+    assert_eq!(
+        top.referrers
+            .iter()
+            .map(|stats| (stats.address.as_str(), stats.earned.amount.u128()))
+            .collect::<Vec<_>>(),
+        vec![("referral3", 40), ("referral1", 30)],
+    );
+
+    let top = suite.query_top_referrers(Some("referral1"), None);
+    assert_eq!(top.referrers.len(), 1);
+    assert_eq!(top.referrers[0].address, "referral2");
+    assert_eq!(top.referrers[0].mints, 1);
+}
+
+#[test]
+fn accrue_referrals() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(100, "ubtsg"))
+        .with_referral_fee_bps(1_000)
+        .with_accrue_referrals()
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::MaxEdition(10))
+        .build();
+
+    let err = suite
+        .execute_launchparty("referral", |lp| lp.claim_referral())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NothingToClaim {}
+    );
+
+    suite
+        .mint(
+            "address1",
+            Some("referral".to_string()),
+            2,
+            Some(coin(200, "ubtsg")),
+        )
+        .unwrap();
+    suite
+        .mint(
+            "address1",
+            Some("referral".to_string()),
+            1,
+            Some(coin(100, "ubtsg")),
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_address_balance("referral", "ubtsg").amount,
+        Uint128::zero(),
+        "expected fees accrued in the launchparty"
+    );
+    assert_eq!(
+        suite.query_address_balance("royalties", "ubtsg").amount,
+        Uint128::new(270)
+    );
+    let stats = suite.query_referral_stats("referral");
+    assert_eq!(stats.mints, 3);
+    assert_eq!(stats.earned, coin(30, "ubtsg"));
+    assert_eq!(stats.claimable, coin(30, "ubtsg"));
+
+    suite
+        .execute_launchparty("referral", |lp| lp.claim_referral())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("referral", "ubtsg").amount,
+        Uint128::new(30)
+    );
+    let stats = suite.query_referral_stats("referral");
+    assert_eq!(stats.earned, coin(30, "ubtsg"));
+    assert_eq!(stats.claimable, coin(0, "ubtsg"));
+
+    let err = suite
+        .execute_launchparty("referral", |lp| lp.claim_referral())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NothingToClaim {}
+    );
+}
+
+#[test]
+fn mint_fee_split() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(100, "ubtsg"))
+        .with_referral_fee_bps(1_000)
+        .with_fee_share("label", 1_500)
+        .with_fee_share("charity", 500)
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::MaxEdition(10))
+        .build();
+
+    suite
+        .mint(
+            "address1",
+            Some("referral".to_string()),
+            2,
+            Some(coin(200, "ubtsg")),
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("referral", "ubtsg").amount,
+        Uint128::new(20)
+    );
+    assert_eq!(
+        suite.query_address_balance("label", "ubtsg").amount,
+        Uint128::new(30)
+    );
+    assert_eq!(
+        suite.query_address_balance("charity", "ubtsg").amount,
+        Uint128::new(10)
+    );
+    assert_eq!(
+        suite.query_address_balance("royalties", "ubtsg").amount,
+        Uint128::new(140)
+    );
+
+    suite
+        .mint("address1", None, 1, Some(coin(100, "ubtsg")))
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("label", "ubtsg").amount,
+        Uint128::new(45)
+    );
+    assert_eq!(
+        suite.query_address_balance("royalties", "ubtsg").amount,
+        Uint128::new(220),
+        "expected the referral share sent to the royalties without a referral"
+    );
+}
+
+#[test]
+fn crowdfund_fee_split() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(100, "ubtsg"))
+        .with_fee_share("label", 1_000)
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::Duration(100))
+        .with_min_raise(200)
+        .build();
+    let bs721_address = suite.query_config().bs721_address.unwrap().to_string();
+
+    suite
+        .mint("address1", None, 2, Some(coin(200, "ubtsg")))
+        .unwrap();
+    suite
+        .mint("address2", None, 1, Some(coin(100, "ubtsg")))
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("label", "ubtsg").amount,
+        Uint128::zero(),
+        "expected funds escrowed in the contract"
+    );
+
+    suite.advance_time(101);
+    suite
+        .execute_launchparty("anyone", |lp| lp.release())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance("label", "ubtsg").amount,
+        Uint128::new(30)
+    );
+    assert_eq!(
+        suite.query_address_balance("royalties", "ubtsg").amount,
+        Uint128::new(270)
+    );
+
+    let err = suite
+        .execute_launchparty("address1", |lp| {
+            lp.refund(bs721_address.clone(), "1".to_string())
+        })
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MinRaiseReached {}
+    );
+}
+
+#[test]
+fn burn_to_mint() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::MaxEdition(2))
+        .with_burn_to_mint(
+            2,
+            vec![TokenRange { start: 1, end: 10 }],
+            RedeemAction::Burn,
+        )
+        .build();
+    suite.mint_tickets("address1", &["1", "2", "3", "11"]);
+    suite.mint_tickets("address2", &["4", "5"]);
+
+    {
+        let err = suite
+            .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::BurnToMintOnly {},
+            "expected to fail since editions can only be redeemed"
+        );
+    }
+
+    {
+        let err = suite.redeem("address1", "11").unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::TokenNotEligible {
+                token_id: "11".to_string()
+            }
+        );
+    }
+
+    suite.redeem("address1", "1").unwrap();
+    let pending = suite.query_redeem_pending("address1");
+    assert_eq!(pending.token_ids, vec!["1"]);
+    assert_eq!(pending.missing, 1);
+    assert_eq!(suite.query_tickets("contract1"), vec!["1"]);
+    assert!(suite.query_nft_token("contract2", "address1").is_empty());
+
+    suite.redeem("address1", "2").unwrap();
+    assert_eq!(suite.query_nft_token("contract2", "address1"), vec!["1"]);
+    assert!(
+        suite.query_tickets("contract1").is_empty(),
+        "expected the redeemed tickets burned"
+    );
+    assert_eq!(suite.query_tickets("address1"), vec!["11", "3"]);
+    assert!(suite.query_redeem_pending("address1").token_ids.is_empty());
+
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| lp.redeem("contract2", "1"))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {},
+            "expected to fail since only tickets can be redeemed"
+        );
+    }
+
+    suite.redeem("address2", "4").unwrap();
+    suite.redeem("address2", "5").unwrap();
+    assert_eq!(suite.query_nft_token("contract2", "address2"), vec!["2"]);
+
+    {
+        let err = suite.redeem("address1", "3").unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::PartyEnded {},
+            "expected to fail since all the editions are minted"
+        );
+    }
+}
+
+#[test]
+fn burn_to_mint_hold_and_cancel() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_price(coin(10, "ubtsg"))
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::Duration(100))
+        .with_burn_to_mint(3, vec![], RedeemAction::Hold)
+        .build();
+    suite.mint_tickets("address1", &["a", "b", "c"]);
+
+    suite.redeem("address1", "a").unwrap();
+    suite.redeem("address1", "b").unwrap();
+    assert_eq!(suite.query_redeem_pending("address1").missing, 1);
+
+    suite
+        .execute_launchparty("address1", |lp| lp.cancel_redeem())
+        .unwrap();
+    assert_eq!(suite.query_tickets("address1"), vec!["a", "b", "c"]);
+    {
+        let err = suite
+            .execute_launchparty("address1", |lp| lp.cancel_redeem())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NoPendingTokens {}
+        );
+    }
+
+    for token_id in ["a", "b", "c"] {
+        suite.redeem("address1", token_id).unwrap();
+    }
+    assert_eq!(suite.query_nft_token("contract2", "address1"), vec!["1"]);
+    assert_eq!(
+        suite.query_tickets("contract1"),
+        vec!["a", "b", "c"],
+        "expected the redeemed tickets held by the launchparty"
+    );
+}
+
+#[test]
+fn token_gate_holders_only() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_token_gate(true, 2_000)
+        .build();
+    suite.mint_tickets("address1", &["1", "2"]);
+    suite.mint_tickets("address2", &["3"]);
+
+    {
+        let err = suite
+            .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::GateTokenRequired {},
+            "expected to fail since only holders can mint"
+        );
+    }
+
+    {
+        let err = suite
+            .mint_gated("address1", 2, &["1"], Some(coin(16, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::GateTokenRequired {},
+            "expected to fail since a gate token is required for each edition"
+        );
+    }
+
+    {
+        let err = suite
+            .mint_gated("address1", 1, &["3"], Some(coin(8, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotGateTokenOwner {
+                token_id: "3".to_string()
+            }
+        );
+    }
+
+    suite
+        .mint_gated("address1", 2, &["1", "2"], Some(coin(16, "ubtsg")))
+        .unwrap();
+    assert_eq!(
+        suite.query_nft_token("contract2", "address1"),
+        vec!["1", "2"]
+    );
+    assert_eq!(
+        suite.query_address_balance("royalties", "ubtsg"),
+        coin(16, "ubtsg"),
+        "expected the discounted price paid"
+    );
+    assert_eq!(
+        suite.query_gate_claim("1"),
+        Some(Addr::unchecked("address1"))
+    );
+    assert_eq!(suite.query_gate_claim("3"), None);
+
+    {
+        let err = suite
+            .mint_gated("address1", 1, &["1"], Some(coin(8, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::GateTokenClaimed {
+                token_id: "1".to_string()
+            },
+            "expected to fail since each gate token can be claimed once"
+        );
+    }
+
+    {
+        let err = suite
+            .mint_gated("address2", 1, &["3"], Some(coin(10, "ubtsg")))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidPaymentAmount(Uint128::new(10), Uint128::new(8))
+        );
+    }
+}
+
+#[test]
+fn token_gate_discount() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_payment_address("royalties")
+        .with_party_type(PartyType::MaxEdition(10))
+        .with_token_gate(false, 5_000)
+        .build();
+    suite.mint_tickets("address1", &["1"]);
+
+    suite
+        .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    suite
+        .mint_gated("address1", 1, &["1"], Some(coin(5, "ubtsg")))
+        .unwrap();
+
+    assert_eq!(
+        suite.query_nft_token("contract2", "address1"),
+        vec!["1", "2"]
+    );
+    assert_eq!(
+        suite.query_address_balance("royalties", "ubtsg"),
+        coin(15, "ubtsg")
+    );
+}
+
+#[test]
+fn edition_tiers() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(5))
+        .with_tier(EditionTier {
+            name: "Signed".to_string(),
+            uri: Some("ipfs://signed".to_string()),
+            attributes: vec![Trait {
+                trait_type: "Signed".to_string(),
+                value: "Yes".to_string(),
+                display_type: None,
+            }],
+            assignment: TierAssignment::Range { start: 1, end: 2 },
+        })
+        .with_tier(EditionTier {
+            name: "Gold".to_string(),
+            uri: Some("ipfs://gold".to_string()),
+            attributes: vec![],
+            assignment: TierAssignment::Weight(1),
+        })
+        .build();
+
+    suite
+        .mint("address1", None, 3, Some(coin(30, "ubtsg")))
+        .unwrap();
+
+    assert_eq!(
+        suite.query_token_uri("contract1", "2"),
+        Some("ipfs://signed".to_string())
+    );
+    assert_eq!(
+        suite.query_token_tier(2).tier.unwrap().tier.name,
+        "Signed".to_string()
+    );
+
+    let tier = suite.query_token_tier(3).tier.unwrap();
+    assert_eq!(
+        tier.tier_id, 1,
+        "expected the only weighted tier drawn out of the ranges"
+    );
+    assert_eq!(
+        suite.query_token_uri("contract1", "3"),
+        Some("ipfs://gold".to_string())
+    );
+    assert_eq!(suite.query_token_tier(4).tier, None);
+}
+
+#[test]
+fn delayed_reveal() {
+    let base_uri = "ipfs://final/";
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(55))
+        .with_reveal(DelayedReveal {
+            placeholder_uri: "ipfs://placeholder".to_string(),
+            provenance_hash: hex::encode(Sha256::digest(base_uri.as_bytes())),
+            random_offset: true,
+        })
+        .build();
+
+    suite
+        .mint("address1", None, 54, Some(coin(54, "ubtsg")))
+        .unwrap();
+    assert_eq!(
+        suite.query_token_uri("contract1", "1"),
+        Some("ipfs://placeholder".to_string())
+    );
+
+    let err = suite
+        .execute_launchparty(CREATOR, |launchparty| launchparty.reveal(base_uri))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PartyNotEnded {}
+    );
+
+    // selling out the last edition ends the party.
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+
+    let err = suite
+        .execute_launchparty("address1", |launchparty| launchparty.reveal(base_uri))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = suite
+        .execute_launchparty(CREATOR, |launchparty| launchparty.reveal("ipfs://other/"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidReveal {}
+    );
+
+    let err = suite
+        .execute_launchparty("address1", |launchparty| launchparty.reveal_batch())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotRevealed {}
+    );
+
+    suite
+        .execute_launchparty(CREATOR, |launchparty| launchparty.reveal(base_uri))
+        .unwrap();
+
+    let reveal = suite.query_reveal();
+    assert_eq!(reveal.base_uri, Some("ipfs://final".to_string()));
+    assert!(!reveal.completed, "expected the reveal split in batches");
+    let offset = reveal.offset.unwrap();
+    let revealed_uri = |token_id: u32| {
+        Some(format!(
+            "ipfs://final/{}.json",
+            (token_id - 1 + offset) % 55 + 1
+        ))
+    };
+
+    assert_eq!(suite.query_token_uri("contract1", "1"), revealed_uri(1));
+    // token IDs are updated in lexicographic order, the last ones in the next batch.
+    assert_eq!(
+        suite.query_token_uri("contract1", "9"),
+        Some("ipfs://placeholder".to_string())
+    );
+
+    let err = suite
+        .execute_launchparty(CREATOR, |launchparty| launchparty.reveal(base_uri))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AlreadyRevealed {}
+    );
+
+    suite
+        .execute_launchparty("address1", |launchparty| launchparty.reveal_batch())
+        .unwrap();
+    assert!(suite.query_reveal().completed);
+    assert_eq!(suite.query_token_uri("contract1", "9"), revealed_uri(9));
+    assert_eq!(suite.query_token_uri("contract1", "55"), revealed_uri(55));
+
+    let err = suite
+        .execute_launchparty("address1", |launchparty| launchparty.reveal_batch())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RevealCompleted {}
+    );
+}
+
+#[test]
+fn delayed_reveal_offset_fixed_by_mints() {
+    let base_uri = "ipfs://final/";
+    let reveal_offset = |delay: u64| {
+        let mut suite = TestSuiteBuilder::new()
+            .with_funds("address1", &[coin(1_000, "ubtsg")])
+            .with_price(coin(1, "ubtsg"))
+            .with_party_type(PartyType::MaxEdition(55))
+            .with_reveal(DelayedReveal {
+                placeholder_uri: "ipfs://placeholder".to_string(),
+                provenance_hash: hex::encode(Sha256::digest(base_uri.as_bytes())),
+                random_offset: true,
+            })
+            .build();
+        suite
+            .mint("address1", None, 55, Some(coin(55, "ubtsg")))
+            .unwrap();
+
+        suite.advance_time(delay);
+        suite
+            .execute_launchparty(CREATOR, |launchparty| launchparty.reveal(base_uri))
+            .unwrap();
+        suite.query_reveal().offset.unwrap()
+    };
+
+    let offset = reveal_offset(0);
+    for delay in [5, 60, 3_600] {
+        assert_eq!(
+            reveal_offset(delay),
+            offset,
+            "expected the offset not to depend on the time of the reveal"
+        );
+    }
+}
+#[test]
+fn finalize_hands_minter_to_creator() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(3))
+        .with_reserved_supply(1)
+        .build();
+
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+
+    let err = suite
+        .execute_launchparty("address1", |launchparty| launchparty.finalize())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PartyNotEnded {}
+    );
+
+    // the public editions are sold out, only the reserved one is left.
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+
+    let err = suite
+        .execute_launchparty("address1", |launchparty| launchparty.finalize())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ReservedSupplyPending {}
+    );
+
+    suite.airdrop_mint(CREATOR, &["address2"]).unwrap();
+    suite
+        .execute_launchparty("address1", |launchparty| launchparty.finalize())
+        .unwrap();
+
+    assert_eq!(suite.query_status().final_supply, Some(3));
+    assert_eq!(
+        suite.query_collection_minter("contract1").unwrap(),
+        CREATOR.to_string()
+    );
+    assert_eq!(
+        suite.query_collection_info("contract1").start_trading_time,
+        Some(Timestamp::from_seconds(1571797419)),
+        "expected the trading started at the finalization"
+    );
+
+    let err = suite
+        .execute_launchparty("address1", |launchparty| launchparty.finalize())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AlreadyFinalized {}
+    );
+}
+
+#[test]
+fn finalize_renounces_minter() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::Duration(100))
+        .with_reserved_supply(2)
+        .with_minter_handover(MinterHandover::Renounce)
+        .build();
+
+    suite
+        .mint("address1", None, 2, Some(coin(2, "ubtsg")))
+        .unwrap();
+    suite.advance_time(101);
+
+    // the creator can forfeit the reserved editions.
+    suite
+        .execute_launchparty(CREATOR, |launchparty| launchparty.finalize())
+        .unwrap();
+
+    assert_eq!(suite.query_status().final_supply, Some(2));
+    suite
+        .query_collection_minter("contract1")
+        .expect_err("expected the minter renounced");
+
+    let err = suite.airdrop_mint(CREATOR, &["address2"]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AlreadyFinalized {}
+    );
+}
+
+#[test]
+fn limited_time_limited_edition() {
+    let now = Timestamp::from_seconds(1571797419);
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_start_time(now.plus_seconds(100))
+        .with_party_type(PartyType::MaxEditionUntil {
+            max_editions: 3,
+            end_time: now.plus_seconds(200),
+        })
+        .build();
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.update_start_time(now.plus_seconds(50)))
+        .unwrap();
+    let status = suite.query_status();
+    assert_eq!(
+        status.end_time,
+        Some(now.plus_seconds(150)),
+        "expected the end time moved with the start time"
+    );
+    assert_eq!(status.remaining, Some(3));
+
+    suite.advance_time(50);
+    suite
+        .mint("address1", None, 2, Some(coin(2, "ubtsg")))
+        .unwrap();
+
+    // the party ends at the end time even if not sold out.
+    suite.advance_time(101);
+    let err = suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PartyEnded {}
+    );
+    assert!(!suite.query_status().active);
+}
+
+#[test]
+fn open_edition_with_end_time() {
+    let now = Timestamp::from_seconds(1571797419);
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::EndTime(now.plus_seconds(100)))
+        .build();
+
+    suite
+        .mint("address1", None, 20, Some(coin(20, "ubtsg")))
+        .unwrap();
+    let status = suite.query_status();
+    assert_eq!(status.remaining, None);
+    assert_eq!(status.end_time, Some(now.plus_seconds(100)));
+
+    suite.advance_time(100);
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .expect("expected minting allowed until the end time");
+
+    suite.advance_time(1);
+    let err = suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PartyEnded {}
+    );
+}
+
+#[test]
+fn raffle_draws_winners_from_beacon() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_funds("address3", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(4))
+        .with_reserved_supply(1)
+        .with_raffle(100, 3)
+        .build();
+
+    let err = suite
+        .mint("address1", None, 1, Some(coin(10, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleOnly {}
+    );
+
+    suite
+        .enter_raffle("address1", 2, Some(coin(20, "ubtsg")))
+        .unwrap();
+    suite
+        .enter_raffle("address1", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    suite
+        .enter_raffle("address2", 2, Some(coin(20, "ubtsg")))
+        .unwrap();
+    suite
+        .enter_raffle("address3", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+
+    let err = suite
+        .enter_raffle("address1", 1, Some(coin(10, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxTicketsExceeded { remaining: 0 }
+    );
+    let err = suite
+        .enter_raffle("address3", 1, Some(coin(5, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidPaymentAmount(Uint128::new(5), Uint128::new(10))
+    );
+
+    let raffle = suite.query_raffle();
+    assert_eq!(raffle.tickets, 6);
+    assert_eq!(
+        raffle.editions, 3,
+        "expected the reserved edition not raffled"
+    );
+    assert_eq!(
+        raffle.beacon_message, None,
+        "expected the beacon message unknown while the entries are open"
+    );
+    assert_eq!(suite.query_raffle_entry("address1").tickets, vec![0, 1, 2]);
+
+    let err = suite
+        .execute_launchparty("address1", |lp| lp.claim_raffle())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleEntriesOpen {}
+    );
+
+    suite.advance_time(100);
+    let err = suite
+        .enter_raffle("address3", 1, Some(coin(10, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleEntriesClosed {}
+    );
+
+    let err = suite
+        .execute_launchparty("address1", |lp| lp.claim_raffle())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleNotDrawn {}
+    );
+
+    // a proof of another message is not the beacon.
+    let err = suite
+        .execute_launchparty("address3", |lp| {
+            lp.submit_beacon(beacon_proof(&"00".repeat(32)))
+        })
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidBeacon {}
+    );
+
+    suite.submit_beacon("address3").unwrap();
+    assert!(suite.query_raffle().drawn);
+    let err = suite.submit_beacon("address3").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleAlreadyDrawn {}
+    );
+
+    let mut total_won = 0;
+    for (address, tickets) in [("address1", 3), ("address2", 2), ("address3", 1)] {
+        let won = suite.query_raffle_entry(address).won.unwrap();
+        total_won += won;
+
+        suite
+            .execute_launchparty(address, |lp| lp.claim_raffle())
+            .unwrap();
+        assert_eq!(
+            suite.query_address_balance(address, "ubtsg"),
+            coin(1_000 - 10 * won as u128, "ubtsg"),
+            "expected the tickets not won refunded"
+        );
+        assert_eq!(
+            suite.query_nft_token("contract1", address).len() as u32,
+            won
+        );
+        assert!(won <= tickets);
+    }
+    assert_eq!(total_won, 3, "expected a winning ticket for each edition");
+    assert_eq!(suite.query_status().minted, 3);
+    assert_eq!(suite.query_status().raised, coin(30, "ubtsg"));
+    assert_eq!(
+        suite.query_address_balance("contract2", "ubtsg"),
+        coin(30, "ubtsg"),
+        "expected the price of the editions won sent to the royalties"
+    );
+
+    let err = suite
+        .execute_launchparty("address1", |lp| lp.claim_raffle())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleAlreadyClaimed {}
+    );
+    let err = suite
+        .execute_launchparty("address4", |lp| lp.claim_raffle())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoRaffleTickets {}
+    );
+}
+
+#[test]
+fn raffle_draws_winners_in_batches() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(120))
+        .with_raffle(100, 100)
+        .build();
+
+    suite
+        .enter_raffle("address1", 100, Some(coin(100, "ubtsg")))
+        .unwrap();
+    suite
+        .enter_raffle("address2", 30, Some(coin(30, "ubtsg")))
+        .unwrap();
+    suite.advance_time(100);
+
+    let err = suite
+        .execute_launchparty("address1", |lp| lp.draw_raffle())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleBeaconMissing {}
+    );
+
+    suite.submit_beacon("address1").unwrap();
+    let raffle = suite.query_raffle();
+    assert!(raffle.seed.is_some());
+    assert!(!raffle.drawn, "expected the draw split in batches");
+    assert_eq!(suite.query_raffle_entry("address1").won, None);
+    let err = suite
+        .execute_launchparty("address1", |lp| lp.claim_raffle())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleNotDrawn {}
+    );
+
+    suite
+        .execute_launchparty("address2", |lp| lp.draw_raffle())
+        .unwrap();
+    assert!(suite.query_raffle().drawn);
+    let err = suite
+        .execute_launchparty("address2", |lp| lp.draw_raffle())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleAlreadyDrawn {}
+    );
+
+    let won1 = suite.query_raffle_entry("address1").won.unwrap();
+    let won2 = suite.query_raffle_entry("address2").won.unwrap();
+    assert_eq!(
+        won1 + won2,
+        120,
+        "expected a winning ticket for each edition"
+    );
+    for (address, won) in [("address1", won1), ("address2", won2)] {
+        suite
+            .execute_launchparty(address, |lp| lp.claim_raffle())
+            .unwrap();
+        assert_eq!(
+            suite.query_address_balance(address, "ubtsg"),
+            coin(1_000 - won as u128, "ubtsg"),
+            "expected the tickets not won refunded"
+        );
+    }
+    assert_eq!(suite.query_status().minted, 120);
+}
+
+#[test]
+fn raffle_undersubscribed_every_ticket_wins() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(5))
+        .with_raffle(100, 3)
+        .build();
+
+    suite
+        .enter_raffle("address1", 3, Some(coin(30, "ubtsg")))
+        .unwrap();
+    suite.advance_time(100);
+
+    assert!(suite.query_raffle().drawn);
+    let err = suite.submit_beacon("address1").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleNotOversubscribed {}
+    );
+
+    suite
+        .execute_launchparty("address1", |lp| lp.claim_raffle())
+        .unwrap();
+    assert_eq!(suite.query_nft_token("contract1", "address1").len(), 3);
+    assert_eq!(
+        suite.query_address_balance("address1", "ubtsg"),
+        coin(970, "ubtsg")
+    );
+}
+
+#[test]
+fn update_start_time_moves_raffle_entries() {
+    let now = Timestamp::from_seconds(1571797419);
+
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_start_time(now.plus_seconds(100))
+        .with_party_type(PartyType::MaxEdition(5))
+        .with_raffle(100, 3)
+        .build();
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.update_start_time(now.plus_seconds(300)))
+        .unwrap();
+    assert_eq!(
+        suite.query_config().raffle.unwrap().entry_end_time,
+        now.plus_seconds(400),
+        "expected the raffle entries moved by the same offset"
+    );
+
+    suite.advance_time(300);
+    suite
+        .enter_raffle("address1", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+}
+
+#[test]
+fn raffle_refunds_without_beacon() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(1))
+        .with_raffle(100, 3)
+        .build();
+
+    suite
+        .enter_raffle("address1", 2, Some(coin(20, "ubtsg")))
+        .unwrap();
+    suite
+        .enter_raffle("address2", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+
+    // the beacon must be submitted within a week of the end of the entries.
+    suite.advance_time(100 + 7 * 24 * 60 * 60);
+    let err = suite.submit_beacon("address1").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleDrawExpired {}
+    );
+
+    for address in ["address1", "address2"] {
+        suite
+            .execute_launchparty(address, |lp| lp.claim_raffle())
+            .unwrap();
+        assert_eq!(
+            suite.query_address_balance(address, "ubtsg"),
+            coin(1_000, "ubtsg"),
+            "expected every ticket refunded"
+        );
+    }
+    assert_eq!(suite.query_status().minted, 0);
+}
+
+#[test]
+fn raffle_finalize_waits_for_claims() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(1))
+        .with_raffle(100, 3)
+        .build();
+
+    suite
+        .enter_raffle("address1", 2, Some(coin(20, "ubtsg")))
+        .unwrap();
+    suite
+        .enter_raffle("address2", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    suite.advance_time(100);
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.end_party())
+        .unwrap();
+
+    let err = suite
+        .execute_launchparty(CREATOR, |lp| lp.finalize())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleClaimsPending {},
+        "expected the finalization blocked until the raffle is drawn"
+    );
+
+    suite.submit_beacon("address1").unwrap();
+    let (winner, loser) = if suite.query_raffle_entry("address1").won == Some(1) {
+        ("address1", "address2")
+    } else {
+        ("address2", "address1")
+    };
+
+    suite
+        .execute_launchparty(loser, |lp| lp.claim_raffle())
+        .unwrap();
+    let err = suite
+        .execute_launchparty(CREATOR, |lp| lp.finalize())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleClaimsPending {},
+        "expected the finalization blocked until the winner claims"
+    );
+
+    // after the claim window the unclaimed winning tickets are refunded.
+    suite.advance_time((7 + 30) * 24 * 60 * 60);
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.finalize())
+        .unwrap();
+    suite
+        .execute_launchparty(winner, |lp| lp.claim_raffle())
+        .unwrap();
+    assert_eq!(
+        suite.query_address_balance(winner, "ubtsg"),
+        coin(1_000, "ubtsg")
+    );
+    assert_eq!(suite.query_status().minted, 0);
+}
+
+#[test]
+fn raffle_finalize_after_claims() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(10, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(2))
+        .with_raffle(100, 3)
+        .build();
+
+    suite
+        .enter_raffle("address1", 2, Some(coin(20, "ubtsg")))
+        .unwrap();
+    suite
+        .enter_raffle("address2", 1, Some(coin(10, "ubtsg")))
+        .unwrap();
+    suite.advance_time(100);
+    suite.submit_beacon("address1").unwrap();
+
+    for address in ["address1", "address2"] {
+        suite
+            .execute_launchparty(address, |lp| lp.claim_raffle())
+            .unwrap();
+    }
+    assert_eq!(suite.query_raffle().claimed, 2);
+
+    suite
+        .execute_launchparty(CREATOR, |lp| lp.finalize())
+        .unwrap();
+}
+
+#[test]
+fn mint_limits_per_tx_and_block() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(100))
+        .with_mint_limits(MintLimits {
+            max_per_block: Some(3),
+            max_per_tx: Some(2),
+            max_per_window: None,
+            cooldown: None,
+        })
+        .build();
+
+    let err = suite
+        .mint("address1", None, 3, Some(coin(3, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxPerTxExceeded { max: 2 }
+    );
+
+    suite
+        .mint("address1", None, 2, Some(coin(2, "ubtsg")))
+        .unwrap();
+    let err = suite
+        .mint("address2", None, 2, Some(coin(2, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxPerBlockExceeded { remaining: 1 },
+        "expected the block limit shared by all the addresses"
+    );
+    suite
+        .mint("address2", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+
+    suite.next_block();
+    suite
+        .mint("address2", None, 2, Some(coin(2, "ubtsg")))
+        .expect("expected the block limit restored in the next block");
+    assert_eq!(suite.query_status().minted, 5);
+}
+
+#[test]
+fn mint_limits_window_and_cooldown() {
+    let mut suite = TestSuiteBuilder::new()
+        .with_funds("address1", &[coin(1_000, "ubtsg")])
+        .with_funds("address2", &[coin(1_000, "ubtsg")])
+        .with_price(coin(1, "ubtsg"))
+        .with_party_type(PartyType::MaxEdition(100))
+        .with_mint_limits(MintLimits {
+            max_per_block: None,
+            max_per_tx: None,
+            max_per_window: Some(MintWindow {
+                max_editions: 3,
+                duration: 100,
+            }),
+            cooldown: Some(10),
+        })
+        .build();
+    let now = Timestamp::from_seconds(1571797419);
+
+    suite
+        .mint("address1", None, 2, Some(coin(2, "ubtsg")))
+        .unwrap();
+    let err = suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MintCooldown {
+            next_mint: now.plus_seconds(10)
+        }
+    );
+    suite
+        .mint("address2", None, 1, Some(coin(1, "ubtsg")))
+        .expect("expected the cooldown applied to each address");
+
+    suite.advance_time(10);
+    let err = suite
+        .mint("address1", None, 2, Some(coin(2, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxPerWindowExceeded { remaining: 1 }
+    );
+    suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap();
+
+    suite.advance_time(10);
+    let err = suite
+        .mint("address1", None, 1, Some(coin(1, "ubtsg")))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxPerWindowExceeded { remaining: 0 }
+    );
+
+    // the window restarts once elapsed.
+    suite.advance_time(80);
+    suite
+        .mint("address1", None, 3, Some(coin(3, "ubtsg")))
+        .unwrap();
+    assert_eq!(suite.query_status().minted, 7);
+}
@@ -0,0 +1,346 @@
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::Map;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::PrimeField;
+use k256::{AffinePoint, CompressedPoint, FieldBytes, ProjectivePoint, Scalar};
+use sha2::{Digest, Sha256};
+
+use crate::{state::RaffleState, ContractError};
+
+/// Suite string of ECVRF-SECP256K1-SHA256-TAI, the VRF of the beacon.
+const SUITE: u8 = 0xfe;
+/// Length of a compressed secp256k1 point.
+const POINT_LEN: usize = 33;
+/// Length of the challenge of a proof, half the length of a scalar.
+const CHALLENGE_LEN: usize = 16;
+/// Length of a proof: the point Gamma, the challenge c and the scalar s.
+const PROOF_LEN: usize = POINT_LEN + CHALLENGE_LEN + 32;
+
+/// Sparse representation of the tickets not yet drawn. A missing position `i` holds the ticket
+/// `i`.
+const RAFFLE_POOL: Map<u32, u32> = Map::new("raffle_pool");
+/// Winning tickets with their draw order.
+const RAFFLE_WINNERS: Map<u32, u32> = Map::new("raffle_winners");
+
+/// Returns the first hash of the chain committing to the raffle entries, bound to the
+/// launchparty address.
+pub fn initial_entries_hash(launchparty: &Addr) -> [u8; 32] {
+    Sha256::digest(launchparty.as_bytes()).into()
+}
+
+/// Chains the hash of the previous entries with the `tickets` entered by `address`. The beacon
+/// evaluates its VRF on the last hash of the chain, so that it cannot be known before the entries
+/// are closed.
+pub fn chain_entry(previous: &[u8], address: &Addr, tickets: u32) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(previous)
+        .chain_update(address.as_bytes())
+        .chain_update(tickets.to_be_bytes())
+        .finalize()
+        .into()
+}
+
+/// Decodes a compressed secp256k1 point, rejecting the identity.
+pub fn decode_point(bytes: &[u8]) -> Option<ProjectivePoint> {
+    if bytes.len() != POINT_LEN {
+        return None;
+    }
+
+    let bytes = CompressedPoint::clone_from_slice(bytes);
+    Option::<AffinePoint>::from(AffinePoint::from_bytes(&bytes))
+        .map(ProjectivePoint::from)
+        .filter(|point| *point != ProjectivePoint::IDENTITY)
+}
+
+fn encode_point(point: &ProjectivePoint) -> CompressedPoint {
+    point.to_affine().to_bytes()
+}
+
+/// Hashes `alpha` to a curve point with the try-and-increment method.
+fn hash_to_curve(public_key: &[u8], alpha: &[u8]) -> Option<ProjectivePoint> {
+    (0..=u8::MAX).find_map(|counter| {
+        let hash = Sha256::new()
+            .chain_update([SUITE, 0x01])
+            .chain_update(public_key)
+            .chain_update(alpha)
+            .chain_update([counter, 0x00])
+            .finalize();
+
+        let mut bytes = [0x02; POINT_LEN];
+        bytes[1..].copy_from_slice(&hash);
+        decode_point(&bytes)
+    })
+}
+
+/// Returns the challenge of a proof, the truncated hash of the points of the Schnorr-like proof.
+fn challenge(points: [&ProjectivePoint; 5]) -> Scalar {
+    let mut hasher = Sha256::new().chain_update([SUITE, 0x02]);
+    for point in points {
+        hasher.update(encode_point(point));
+    }
+    let hash = hasher.chain_update([0x00]).finalize();
+
+    let mut bytes = FieldBytes::default();
+    bytes[32 - CHALLENGE_LEN..].copy_from_slice(&hash[..CHALLENGE_LEN]);
+    // a 128 bits integer is always lower than the order of the curve.
+    Scalar::from_repr(bytes).unwrap()
+}
+
+/// Verifies that `proof` is the ECVRF-SECP256K1-SHA256-TAI proof of `alpha` by the beacon,
+/// returning the VRF output as the seed of the draw. The output of a VRF is unique for a key and
+/// an input, so the beacon cannot choose among different seeds.
+pub fn verify_beacon(
+    public_key: &[u8],
+    alpha: &[u8],
+    proof: &[u8],
+) -> Result<[u8; 32], ContractError> {
+    if proof.len() != PROOF_LEN {
+        return Err(ContractError::InvalidBeacon {});
+    }
+    let key = decode_point(public_key).ok_or(ContractError::InvalidBeacon {})?;
+    let gamma = decode_point(&proof[..POINT_LEN]).ok_or(ContractError::InvalidBeacon {})?;
+
+    let mut c_bytes = FieldBytes::default();
+    c_bytes[32 - CHALLENGE_LEN..].copy_from_slice(&proof[POINT_LEN..POINT_LEN + CHALLENGE_LEN]);
+    let c = Option::<Scalar>::from(Scalar::from_repr(c_bytes))
+        .ok_or(ContractError::InvalidBeacon {})?;
+    let s_bytes = FieldBytes::clone_from_slice(&proof[POINT_LEN + CHALLENGE_LEN..]);
+    let s = Option::<Scalar>::from(Scalar::from_repr(s_bytes))
+        .ok_or(ContractError::InvalidBeacon {})?;
+
+    let h = hash_to_curve(public_key, alpha).ok_or(ContractError::InvalidBeacon {})?;
+    let u = ProjectivePoint::GENERATOR * s - key * c;
+    let v = h * s - gamma * c;
+    if challenge([&key, &h, &gamma, &u, &v]) != c {
+        return Err(ContractError::InvalidBeacon {});
+    }
+
+    Ok(Sha256::new()
+        .chain_update([SUITE, 0x03])
+        .chain_update(encode_point(&gamma))
+        .chain_update([0x00])
+        .finalize()
+        .into())
+}
+
+/// Draws up to `batch` winning tickets of the raffle from `seed`, returning the number of tickets
+/// drawn.
+///
+/// The winners are the first positions of a Fisher-Yates shuffle of the tickets: the ticket
+/// drawn at each step is swapped with the position of the step, so that every step updates at
+/// most two entries of the storage.
+pub fn draw_winners(
+    storage: &mut dyn Storage,
+    state: &mut RaffleState,
+    seed: &[u8],
+    batch: u32,
+) -> StdResult<u32> {
+    let end = state.editions.min(state.drawn.saturating_add(batch));
+    let start = state.drawn;
+
+    for position in start..end {
+        let hash = Sha256::new()
+            .chain_update(seed)
+            .chain_update(position.to_be_bytes())
+            .finalize();
+        let random = u64::from_be_bytes(hash[..8].try_into().unwrap());
+        let drawn = position + (random % (state.tickets - position) as u64) as u32;
+
+        let ticket = RAFFLE_POOL.may_load(storage, drawn)?.unwrap_or(drawn);
+        let replaced = RAFFLE_POOL.may_load(storage, position)?.unwrap_or(position);
+        RAFFLE_POOL.save(storage, drawn, &replaced)?;
+        RAFFLE_POOL.remove(storage, position);
+
+        RAFFLE_WINNERS.save(storage, ticket, &position)?;
+    }
+
+    state.drawn = end;
+    Ok(end - start)
+}
+
+/// Returns the number of winning `tickets`, None if the winning tickets are not yet drawn.
+pub fn won(storage: &dyn Storage, state: &RaffleState, tickets: &[u32]) -> Option<u32> {
+    if state.tickets <= state.editions {
+        return Some(tickets.len() as u32);
+    }
+    if !state.winners_drawn() {
+        return None;
+    }
+
+    Some(
+        tickets
+            .iter()
+            .filter(|ticket| RAFFLE_WINNERS.has(storage, **ticket))
+            .count() as u32,
+    )
+}
+
+/// Mock prover of the beacon, returning the hex encoded proof of `alpha` by `secret_key`.
+#[cfg(test)]
+pub fn mock_beacon_proof(secret_key: &[u8; 32], alpha: &[u8]) -> String {
+    let x = Scalar::from_repr((*secret_key).into()).unwrap();
+    let public_key = encode_point(&(ProjectivePoint::GENERATOR * x));
+
+    let h = hash_to_curve(&public_key, alpha).unwrap();
+    let gamma = h * x;
+    // a deterministic nonce, not RFC 6979 compliant but enough for tests.
+    let k_bytes: [u8; 32] = Sha256::new()
+        .chain_update(secret_key)
+        .chain_update(encode_point(&h))
+        .finalize()
+        .into();
+    let k = Scalar::from_repr(k_bytes.into()).unwrap();
+    let c = challenge([
+        &(ProjectivePoint::GENERATOR * x),
+        &h,
+        &gamma,
+        &(ProjectivePoint::GENERATOR * k),
+        &(h * k),
+    ]);
+    let s = k + c * x;
+
+    let mut proof = encode_point(&gamma).to_vec();
+    proof.extend_from_slice(&c.to_repr()[32 - CHALLENGE_LEN..]);
+    proof.extend_from_slice(&s.to_repr());
+    hex::encode(proof)
+}
+
+/// Returns the hex encoded compressed public key of the mock beacon `secret_key`.
+#[cfg(test)]
+pub fn mock_beacon_public_key(secret_key: &[u8; 32]) -> String {
+    let x = Scalar::from_repr((*secret_key).into()).unwrap();
+    hex::encode(encode_point(&(ProjectivePoint::GENERATOR * x)))
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit tests
+// -------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::testing::MockStorage;
+
+    use super::*;
+
+    const SECRET_KEY: [u8; 32] = [7; 32];
+
+    fn raffle_state(tickets: u32, editions: u32) -> RaffleState {
+        RaffleState {
+            tickets,
+            editions,
+            entries_hash: String::new(),
+            beacon: None,
+            seed: None,
+            drawn: 0,
+            claimed: 0,
+        }
+    }
+
+    #[test]
+    fn verify_beacon_works() {
+        let public_key = hex::decode(mock_beacon_public_key(&SECRET_KEY)).unwrap();
+        let message = chain_entry(
+            &initial_entries_hash(&Addr::unchecked("party")),
+            &Addr::unchecked("alice"),
+            2,
+        );
+        let proof = hex::decode(mock_beacon_proof(&SECRET_KEY, &message)).unwrap();
+        assert_eq!(proof.len(), PROOF_LEN);
+
+        let seed = verify_beacon(&public_key, &message, &proof).unwrap();
+        assert_eq!(
+            seed,
+            <[u8; 32]>::from(
+                Sha256::new()
+                    .chain_update([SUITE, 0x03])
+                    .chain_update(&proof[..POINT_LEN])
+                    .chain_update([0x00])
+                    .finalize()
+            ),
+            "expected the seed derived from Gamma only"
+        );
+
+        let other = chain_entry(&message, &Addr::unchecked("bob"), 1);
+        assert_eq!(
+            verify_beacon(&public_key, &other, &proof).unwrap_err(),
+            ContractError::InvalidBeacon {},
+            "expected the proof bound to the entries"
+        );
+
+        let other_key = hex::decode(mock_beacon_public_key(&[8; 32])).unwrap();
+        assert_eq!(
+            verify_beacon(&other_key, &message, &proof).unwrap_err(),
+            ContractError::InvalidBeacon {}
+        );
+        assert_eq!(
+            verify_beacon(&public_key, &message, &proof[..PROOF_LEN - 1]).unwrap_err(),
+            ContractError::InvalidBeacon {}
+        );
+
+        // any change of the proof, and of the output derived from Gamma, is rejected.
+        for position in [0, 1, POINT_LEN, POINT_LEN + CHALLENGE_LEN, PROOF_LEN - 1] {
+            let mut tampered = proof.clone();
+            tampered[position] ^= 1;
+            assert_eq!(
+                verify_beacon(&public_key, &message, &tampered).unwrap_err(),
+                ContractError::InvalidBeacon {}
+            );
+        }
+    }
+
+    #[test]
+    fn decode_point_works() {
+        let public_key = hex::decode(mock_beacon_public_key(&SECRET_KEY)).unwrap();
+        assert!(decode_point(&public_key).is_some());
+
+        assert!(decode_point(&public_key[..32]).is_none());
+        assert!(
+            decode_point(&[0; POINT_LEN]).is_none(),
+            "expected the identity rejected"
+        );
+        let mut uncompressed = public_key.clone();
+        uncompressed[0] = 0x04;
+        assert!(decode_point(&uncompressed).is_none());
+    }
+
+    #[test]
+    fn draw_winners_works() {
+        for (tickets, editions) in [(2u32, 1u32), (12, 5), (30, 29), (97, 40)] {
+            let mut storage = MockStorage::new();
+            let mut state = raffle_state(tickets, editions);
+            let seed = [tickets as u8; 32];
+
+            assert_eq!(won(&storage, &state, &[0]), None);
+            while !state.winners_drawn() {
+                let drawn = draw_winners(&mut storage, &mut state, &seed, 3).unwrap();
+                assert!(drawn > 0 && drawn <= 3);
+            }
+            assert_eq!(draw_winners(&mut storage, &mut state, &seed, 3).unwrap(), 0);
+
+            let all: Vec<u32> = (0..tickets).collect();
+            assert_eq!(
+                won(&storage, &state, &all),
+                Some(editions),
+                "expected a winning ticket for each edition"
+            );
+        }
+
+        // the draw does not depend on the batches.
+        let mut storage = MockStorage::new();
+        let mut state = raffle_state(50, 10);
+        draw_winners(&mut storage, &mut state, &[1; 32], 10).unwrap();
+        let mut batched_storage = MockStorage::new();
+        let mut batched_state = raffle_state(50, 10);
+        while !batched_state.winners_drawn() {
+            draw_winners(&mut batched_storage, &mut batched_state, &[1; 32], 4).unwrap();
+        }
+        for ticket in 0..50 {
+            assert_eq!(
+                won(&storage, &state, &[ticket]),
+                won(&batched_storage, &batched_state, &[ticket])
+            );
+        }
+
+        let state = raffle_state(3, 3);
+        assert_eq!(won(&MockStorage::new(), &state, &[0, 2]), Some(2));
+    }
+}
@@ -0,0 +1,186 @@
+use cosmwasm_std::{Addr, Env, StdError, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+use sha2::{Digest, Sha256};
+
+/// Seed mixed with the block data of every draw. Chaining the seed makes the result of a draw
+/// depend on all the previous ones, but it can still be computed before the mint is executed,
+/// which is why contracts are not allowed to mint drawn editions.
+const RANDOM_SEED: Item<[u8; 32]> = Item::new("random_seed");
+/// Sparse representation of the pool of not yet minted indexes. A missing position `i` holds
+/// the index `i`.
+const POOL: Map<u32, u32> = Map::new("random_pool");
+
+/// Draws a random not yet minted index from the pool `1..=pool_size`, given that `minted`
+/// indexes have already been drawn.
+///
+/// The pool is shuffled lazily with a Fisher-Yates step: the drawn position is replaced by the
+/// last available one, so that every draw updates at most two entries of the storage.
+pub fn draw_index(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    pool_size: u32,
+    minted: u32,
+) -> StdResult<u32> {
+    let remaining = pool_size.saturating_sub(minted);
+    if remaining == 0 {
+        return Err(StdError::generic_err("random pool is empty"));
+    }
+
+    let seed = next_seed(storage, env, sender)?;
+    let random = u64::from_be_bytes(seed[..8].try_into().unwrap());
+    let position = (random % remaining as u64) as u32;
+
+    let last = remaining - 1;
+    let drawn = POOL.may_load(storage, position)?.unwrap_or(position);
+    let last_value = POOL.may_load(storage, last)?.unwrap_or(last);
+
+    POOL.save(storage, position, &last_value)?;
+    POOL.remove(storage, last);
+
+    Ok(drawn + 1)
+}
+
+/// Draws a random position of `weights`, each one with probability proportional to its weight.
+/// The weights must not be all zero.
+pub fn draw_weighted(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    weights: &[u32],
+) -> StdResult<usize> {
+    let total: u64 = weights.iter().map(|weight| *weight as u64).sum();
+    if total == 0 {
+        return Err(StdError::generic_err("weights cannot be all zero"));
+    }
+
+    let seed = next_seed(storage, env, sender)?;
+    let mut random = u64::from_be_bytes(seed[..8].try_into().unwrap()) % total;
+    for (position, weight) in weights.iter().enumerate() {
+        if random < *weight as u64 {
+            return Ok(position);
+        }
+        random -= *weight as u64;
+    }
+
+    unreachable!("the random value is lower than the total weight")
+}
+
+/// Mixes the block data and the sender address of a mint into the seed.
+pub fn mix_seed(storage: &mut dyn Storage, env: &Env, sender: &Addr) -> StdResult<()> {
+    next_seed(storage, env, sender).map(|_| ())
+}
+
+/// Returns an offset in `0..size` derived from the current seed, without mixing new data into it.
+/// Once the mints are over, the result is fixed and cannot be influenced by the caller.
+pub fn seed_offset(storage: &dyn Storage, size: u32) -> StdResult<u32> {
+    if size == 0 {
+        return Err(StdError::generic_err("offset range is empty"));
+    }
+
+    let seed = RANDOM_SEED.may_load(storage)?.unwrap_or_default();
+    let random = u64::from_be_bytes(seed[..8].try_into().unwrap());
+    Ok((random % size as u64) as u32)
+}
+
+/// Returns a new seed mixing the previous one with block data and the sender address.
+fn next_seed(storage: &mut dyn Storage, env: &Env, sender: &Addr) -> StdResult<[u8; 32]> {
+    let previous = RANDOM_SEED.may_load(storage)?.unwrap_or_default();
+
+    let mut hasher = Sha256::new()
+        .chain_update(previous)
+        .chain_update(env.block.chain_id.as_bytes())
+        .chain_update(env.block.height.to_be_bytes())
+        .chain_update(env.block.time.nanos().to_be_bytes())
+        .chain_update(sender.as_bytes());
+    if let Some(transaction) = &env.transaction {
+        hasher.update(transaction.index.to_be_bytes());
+    }
+
+    let seed: [u8; 32] = hasher.finalize().into();
+    RANDOM_SEED.save(storage, &seed)?;
+
+    Ok(seed)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit tests
+// -------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    use super::*;
+
+    #[test]
+    fn draw_index_works() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let sender = Addr::unchecked("sender");
+
+        let mut drawn: Vec<u32> = (0..10)
+            .map(|minted| draw_index(deps.as_mut().storage, &env, &sender, 10, minted).unwrap())
+            .collect();
+        assert_ne!(
+            drawn,
+            (1..=10).collect::<Vec<u32>>(),
+            "expected indexes not drawn in order"
+        );
+
+        drawn.sort();
+        assert_eq!(
+            drawn,
+            (1..=10).collect::<Vec<u32>>(),
+            "expected every index of the pool drawn exactly once"
+        );
+
+        draw_index(deps.as_mut().storage, &env, &sender, 10, 10).unwrap_err();
+    }
+
+    #[test]
+    fn draw_weighted_works() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let sender = Addr::unchecked("sender");
+
+        let mut drawn = [0u32; 3];
+        for _ in 0..100 {
+            let position = draw_weighted(deps.as_mut().storage, &env, &sender, &[1, 0, 3]).unwrap();
+            drawn[position] += 1;
+        }
+        assert_eq!(drawn[1], 0, "expected a zero weight never drawn");
+        assert!(
+            drawn[2] > drawn[0],
+            "expected the heavier weight drawn more often"
+        );
+
+        draw_weighted(deps.as_mut().storage, &env, &sender, &[0, 0]).unwrap_err();
+    }
+
+    #[test]
+    fn seed_offset_works() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let sender = Addr::unchecked("sender");
+
+        let mut offsets = vec![];
+        for _ in 0..20 {
+            mix_seed(deps.as_mut().storage, &env, &sender).unwrap();
+            let offset = seed_offset(deps.as_ref().storage, 5).unwrap();
+            assert_eq!(
+                seed_offset(deps.as_ref().storage, 5).unwrap(),
+                offset,
+                "expected the same offset until new data is mixed into the seed"
+            );
+            offsets.push(offset);
+        }
+        assert!(offsets.iter().all(|offset| *offset < 5));
+        assert!(
+            offsets.iter().any(|offset| *offset != offsets[0]),
+            "expected different offsets drawn"
+        );
+
+        assert_eq!(seed_offset(deps.as_ref().storage, 1).unwrap(), 0);
+        seed_offset(deps.as_ref().storage, 0).unwrap_err();
+    }
+}
@@ -1,8 +1,12 @@
+use bs_std::fees::{AddressFee, FeeShare};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Timestamp};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
-use crate::msg::PartyType;
+use crate::msg::{
+    AllowlistPhase, BurnToMint, DelayedReveal, DutchAuction, EditionTier, MintLimitTarget,
+    MintLimits, MintStage, MinterHandover, PartyType, Raffle, TokenGate,
+};
 
 /// Smart contract configuration structure.
 #[cw_serde]
@@ -15,36 +19,287 @@ pub struct Config {
     pub name: String,
     /// URI of the NFT contract
     pub uri: String,
+    /// Hash of the content shared by every edition.
+    pub content_hash: Option<String>,
     /// Price of single nft minting.
     pub price: Coin,
     /// Maximum amount of token an address can mint.
     pub max_per_address: Option<u32>,
+    /// Address the per address limits are applied to when minting to a recipient.
+    pub limit_target: MintLimitTarget,
+    /// Limits of the mint per block, per transaction and per address over time.
+    pub mint_limits: Option<MintLimits>,
+    /// Editions reserved for the creator airdrops not yet minted.
+    pub reserved_supply: u32,
+    /// Dutch auction giving the price of the public mint.
+    pub dutch_auction: Option<DutchAuction>,
+    /// Burn-to-mint mode, editions can only be minted by redeeming tokens of another collection.
+    pub burn_to_mint: Option<BurnToMint>,
+    /// Raffle mode, editions can only be won in the raffle.
+    pub raffle: Option<Raffle>,
+    /// Token gate restricting or discounting the public mint to the holders of other collections.
+    pub token_gate: Option<TokenGate>,
+    /// Delayed reveal, editions are minted with a placeholder URI until revealed.
+    pub reveal: Option<DelayedReveal>,
+    /// What happens to the minter role of the collection when the launchparty is finalized.
+    pub minter_handover: MinterHandover,
+    /// Number of editions of the collection, recorded when the launchparty is finalized. No
+    /// more editions can be minted by the launchparty afterwards.
+    pub final_supply: Option<u32>,
     /// ID of the next NFT that will be minted. The first NFT will be minted with ID == 1.
     pub next_token_id: u32,
     /// Address of the bs721 royalties contract.
     pub payment_address: Addr,
     pub seller_fee_bps: u16,
-    pub referral_fee_bps: u16,
-    pub protocol_fee_bps: u16,
+    /// Split of the mint price among the royalties, the referral, the protocol and any other
+    /// address.
+    pub fee_split: Vec<FeeShare>,
+    /// If true, referral fees are accrued in the contract and claimed by the referrals.
+    pub accrue_referrals: bool,
     /// Start time of the launchparty.
     pub start_time: Timestamp,
     /// End condition of the collection launchparty.
     pub party_type: PartyType,
     /// Address of the bs721 metadata-onchain token contract.
     pub bs721_address: Option<Addr>,
+    /// If true, minting is paused by the creator.
+    pub paused: bool,
+    /// If true, the party has been ended early by the creator.
+    pub ended: bool,
+    /// Minimum amount to raise if the launchparty is a crowdfund.
+    pub min_raise: Option<Uint128>,
+    /// If true, minters receive a random not yet minted index of the collection as token ID, with
+    /// the token URI `{uri}/{index}.json`.
+    pub randomized: bool,
+    /// Address of the cw20 token used to pay for mints. If None, mints are paid in native coins.
+    pub cw20_address: Option<Addr>,
+    /// Address receiving the protocol fee when it cannot be sent to the community pool, i.e.
+    /// when mints are paid with a cw20 token. Set to the admin chosen by the factory.
+    pub protocol_address: Option<Addr>,
 }
 
 /// Stores the contract's configuration
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const ADDRESS_TOKENS: Map<&Addr, u32> = Map::new("address_tokens");
+/// Editions minted in the last block with mints, against the maximum per block.
+#[cw_serde]
+pub struct BlockMints {
+    pub height: u64,
+    pub minted: u32,
+}
+
+/// Mints of an address, against its cooldown and maximum per window.
+#[cw_serde]
+pub struct MintActivity {
+    pub last_mint: Timestamp,
+    pub window_start: Timestamp,
+    /// Editions minted since the start of the window.
+    pub window_minted: u32,
+}
+
+pub const BLOCK_MINTS: Item<BlockMints> = Item::new("block_mints");
+pub const MINT_ACTIVITY: Map<&Addr, MintActivity> = Map::new("mint_activity");
+/// Allowlist phases indexed by their position in the instantiation message.
+pub const ALLOWLIST_PHASES: Map<u32, AllowlistPhase> = Map::new("allowlist_phases");
+/// Number of tokens minted by an address during an allowlist phase.
+pub const ALLOWLIST_MINTED: Map<(u32, &Addr), u32> = Map::new("allowlist_minted");
+/// Funds escrowed during a crowdfund, split as they will be released.
+#[cw_serde]
+#[derive(Default)]
+pub struct Escrow {
+    /// Total amount paid by minters and not refunded.
+    pub raised: Uint128,
+    pub royalties: Uint128,
+    pub protocol: Uint128,
+    /// Amounts of the fee split addresses.
+    pub addresses: Vec<AddressFee>,
+    /// True if the escrowed funds have been released.
+    pub released: bool,
+}
+
+/// Amount paid for a token minted during a crowdfund, split as it will be released.
+#[cw_serde]
+pub struct TokenPayment {
+    pub referral: Option<Addr>,
+    pub referral_amount: Uint128,
+    pub royalties_amount: Uint128,
+    pub protocol_amount: Uint128,
+    pub addresses: Vec<AddressFee>,
+}
+
+impl TokenPayment {
+    /// Returns the full amount paid for the token.
+    pub fn total(&self) -> Uint128 {
+        self.addresses.iter().fold(
+            self.referral_amount + self.royalties_amount + self.protocol_amount,
+            |acc, fee| acc + fee.amount,
+        )
+    }
+}
+
+pub const ESCROW: Item<Escrow> = Item::new("escrow");
+/// Total amount paid by minters, net of refunds.
+pub const RAISED: Item<Uint128> = Item::new("raised");
+/// Payments escrowed for each token minted during a crowdfund.
+pub const TOKEN_PAYMENTS: Map<u32, TokenPayment> = Map::new("token_payments");
+/// Referral fees escrowed for each referral address during a crowdfund.
+pub const REFERRAL_ESCROW: Map<&Addr, Uint128> = Map::new("referral_escrow");
+
+/// Activity of a referral address, amounts are in the denom of the launchparty price.
+#[cw_serde]
+#[derive(Default)]
+pub struct ReferralStats {
+    /// Number of tokens minted with the address as referral, net of refunds.
+    pub mints: u32,
+    /// Total referral fees earned, net of refunds.
+    pub earned: Uint128,
+    /// Referral fees accrued and not yet claimed.
+    pub claimable: Uint128,
+}
+
+pub struct ReferralIndexes<'a> {
+    pub earned: MultiIndex<'a, u128, ReferralStats, Addr>,
+}
+
+impl<'a> IndexList<ReferralStats> for ReferralIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<ReferralStats>> + '_> {
+        let v: Vec<&dyn Index<ReferralStats>> = vec![&self.earned];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Stats of each referral address, indexed by amount earned for the leaderboard.
+pub fn referrals<'a>() -> IndexedMap<'a, &'a Addr, ReferralStats, ReferralIndexes<'a>> {
+    let indexes = ReferralIndexes {
+        earned: MultiIndex::new(
+            |_pk: &[u8], d: &ReferralStats| d.earned.u128(),
+            "referrals",
+            "referrals__earned",
+        ),
+    };
+    IndexedMap::new("referrals", indexes)
+}
+/// Edition tiers indexed by their position in the instantiation message.
+pub const TIERS: Map<u32, EditionTier> = Map::new("tiers");
+/// Tier assigned to each minted edition.
+pub const TOKEN_TIERS: Map<u32, u32> = Map::new("token_tiers");
+/// Address that used a token, identified by collection and token ID, to open the gate of the
+/// public mint.
+pub const GATE_CLAIMS: Map<(&str, &str), Addr> = Map::new("gate_claims");
+/// Tokens sent by an address for a burn-to-mint redemption not yet completed.
+pub const REDEEM_PENDING: Map<&Addr, Vec<String>> = Map::new("redeem_pending");
+
+/// Final metadata of a delayed reveal launchparty, and progress of the update of the editions.
+#[cw_serde]
+pub struct RevealState {
+    /// Base URI of the final metadata, without trailing slash.
+    pub base_uri: String,
+    /// Offset shifting the metadata assigned to the editions.
+    pub offset: u32,
+    /// Number of editions the metadata is assigned to.
+    pub size: u32,
+    /// Last edition updated, the next batch starts after it.
+    pub last_token_id: Option<String>,
+    /// True if the URI of every edition has been updated.
+    pub completed: bool,
+}
+
+impl RevealState {
+    /// Returns the final URI of the edition `token_id`.
+    pub fn token_uri(&self, token_id: u32) -> String {
+        let index = (token_id - 1 + self.offset) % self.size + 1;
+        format!("{}/{index}.json", self.base_uri)
+    }
+}
+
+pub const REVEAL: Item<RevealState> = Item::new("reveal");
+
+/// Tickets entered in the raffle and its draw.
+#[cw_serde]
+pub struct RaffleState {
+    /// Number of tickets entered, each one identified by its entry order.
+    pub tickets: u32,
+    /// Number of editions won in the raffle.
+    pub editions: u32,
+    /// Hex encoded hash chained over the entries. Once the entries are closed it is the input of
+    /// the beacon VRF.
+    pub entries_hash: String,
+    /// Hex encoded VRF proof of the beacon, once submitted.
+    pub beacon: Option<String>,
+    /// Hex encoded VRF output of the beacon, the seed of the draw.
+    pub seed: Option<String>,
+    /// Number of winning tickets drawn so far.
+    pub drawn: u32,
+    /// Number of winning tickets whose editions have been claimed.
+    pub claimed: u32,
+}
+
+impl RaffleState {
+    /// Returns true if every winning ticket is known. Every ticket wins when the tickets do not
+    /// exceed the editions.
+    pub fn winners_drawn(&self) -> bool {
+        self.tickets <= self.editions || self.drawn == self.editions
+    }
 
+    /// Returns the number of winning tickets whose editions have not been claimed yet.
+    pub fn unclaimed(&self) -> u32 {
+        self.tickets.min(self.editions) - self.claimed
+    }
+}
+
+/// Tickets entered in the raffle by an address.
+#[cw_serde]
+#[derive(Default)]
+pub struct RaffleEntry {
+    pub tickets: Vec<u32>,
+    /// Total amount deposited for the tickets.
+    pub deposit: Uint128,
+    /// True if the editions won and the refund have been claimed.
+    pub claimed: bool,
+}
+
+pub const RAFFLE: Item<RaffleState> = Item::new("raffle");
+pub const RAFFLE_ENTRIES: Map<&Addr, RaffleEntry> = Map::new("raffle_entries");
+
+/// Sales of the public mint during a dutch auction.
 #[cw_serde]
-pub struct Trait {
-    pub display_type: Option<String>,
-    pub trait_type: String,
-    pub value: String,
+#[derive(Default)]
+pub struct AuctionSales {
+    /// Price paid by the last buyer, the lowest price paid since the price only decreases.
+    pub clearing_price: Option<Uint128>,
+    /// Number of tokens sold.
+    pub sold: u32,
+    /// Number of tokens sold with a referral while the amount over the floor price is held.
+    pub referred: u32,
+    /// True if the amount held for the rebate has been settled at the clearing price.
+    pub settled: bool,
 }
 
+/// Tokens bought by an address during a dutch auction with rebate, and the amount paid over the
+/// floor price.
+#[cw_serde]
+#[derive(Default)]
+pub struct AuctionPayment {
+    pub minted: u32,
+    pub excess: Uint128,
+}
+
+pub const AUCTION_SALES: Item<AuctionSales> = Item::new("auction_sales");
+/// Payments held for the rebate of each buyer of a dutch auction.
+pub const AUCTION_PAYMENTS: Map<&Addr, AuctionPayment> = Map::new("auction_payments");
+/// Tokens sold with each referral during a dutch auction with rebate. The referral fees on the
+/// amount held are claimable once the auction is settled.
+pub const AUCTION_REFERRALS: Map<&Addr, u32> = Map::new("auction_referrals");
+/// Stages of the public mint indexed by their position.
+pub const STAGES: Map<u32, MintStage> = Map::new("stages");
+/// Number of tokens minted during a stage.
+pub const STAGE_MINTED: Map<u32, u32> = Map::new("stage_minted");
+/// Number of tokens minted by an address during a stage.
+pub const STAGE_ADDRESS_TOKENS: Map<(u32, &Addr), u32> = Map::new("stage_address_tokens");
+
+pub use bs721::Trait;
+
+/// Token extension, a subset of [`bs721::Metadata`] so that the collection stores and exports it.
 #[cw_serde]
 pub struct EditionMetadata {
     pub name: String,
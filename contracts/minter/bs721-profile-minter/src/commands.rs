@@ -12,14 +12,14 @@ use cw_utils::must_pay;
 
 use crate::state::PROFILE_MARKETPLACE;
 use crate::{
-    state::{ADMIN, CONFIG, PROFILE_COLLECTION, PAUSED, SUDO_PARAMS, WHITELISTS},
+    state::{ADMIN, CONFIG, PROFILE_COLLECTION, PAUSED, SUDO_PARAMS},
     ContractError,
 };
 
 pub fn execute_mint_and_list(
     deps: DepsMut,
     info: MessageInfo,
-    env: Env,
+    _env: Env,
     name: &str,
 ) -> Result<Response, ContractError> {
     if PAUSED.load(deps.storage)? {
@@ -112,6 +112,7 @@ pub fn execute_mint_and_list(
         token_id: name.to_string(),
         owner: sender.to_string(),
         token_uri: None,
+        content_hash: None,
         extension: Metadata::default(),
         seller_fee_bps: None,
         payment_addr: None,
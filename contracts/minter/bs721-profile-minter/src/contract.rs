@@ -1,24 +1,21 @@
 use std::vec;
 
 use bs721::CollectionInfo;
-use bs_profile::market::ExecuteMsg as MarketplaceExecuteMsg;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coin, to_json_binary, Addr, Coin, Decimal, DepsMut, Empty, Env, Event, MessageInfo, Reply,
-    Response, StdError, SubMsg, Uint128, WasmMsg,
+    to_json_binary, Addr, DepsMut, Empty, Env, MessageInfo, Reply,
+    Response, StdError, SubMsg, WasmMsg,
 };
 use cw2::set_contract_version;
-use cw_utils::{maybe_addr, must_pay, parse_reply_instantiate_data};
+use cw_utils::{maybe_addr, parse_reply_instantiate_data};
 
 use semver::Version;
 
-use bs721_profile::{
-    ExecuteMsg as NameCollectionExecuteMsg, InstantiateMsg as BsProfileCollectionInstantiateMsg,
-};
-use bs_profile::common::{charge_fees, SECONDS_PER_YEAR};
+use bs721_profile::InstantiateMsg as BsProfileCollectionInstantiateMsg;
+use bs_profile::common::SECONDS_PER_YEAR;
 use bs_profile::minter::{Config, SudoParams, PUBLIC_MINT_START_TIME_IN_SECONDS};
-use bs_profile::{BsProfileExecuteMsg, Metadata};
+use bs_profile::BsProfileExecuteMsg;
 
 use crate::commands::{execute_mint_and_list, execute_pause, execute_update_config};
 use crate::error::ContractError;
@@ -87,6 +84,7 @@ pub fn instantiate(
             creator: info.sender.to_string(),
             description: "Stargaze Names".to_string(),
             image: "ipfs://example.com".to_string(),
+            image_hash: None,
             external_link: None,
             explicit_content: None,
             start_trading_time: Some(
@@ -233,10 +231,7 @@ pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, Contract
 mod tests {
     use cosmwasm_std::{coin, Addr, MessageInfo};
 
-    use crate::{
-        commands::{validate_name, validate_payment},
-        contract,
-    };
+    use crate::commands::{validate_name, validate_payment};
 
     use super::*;
 
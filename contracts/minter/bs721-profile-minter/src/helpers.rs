@@ -1,8 +1,10 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_json_binary, Addr, CosmosMsg, QuerierWrapper, QueryRequest, StdResult, WasmMsg, WasmQuery
+    to_json_binary, Addr, Coin, CosmosMsg, QuerierWrapper, QueryRequest, StdResult, WasmMsg, WasmQuery
 };
-use bs_profile::minter::SudoParams;
+use bs_profile::minter::{Config, SudoParams};
+use cw_controllers::AdminResponse;
+use serde::de::DeserializeOwned;
 
 use crate::msg::{ExecuteMsg, QueryMsg};
 
@@ -16,15 +18,72 @@ impl NameMinterContract {
     }
 
     pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    pub fn call_with_funds<T: Into<ExecuteMsg>>(
+        &self,
+        msg: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
         let msg = to_json_binary(&msg.into())?;
         Ok(WasmMsg::Execute {
             contract_addr: self.addr().into(),
             msg,
-            funds: vec![],
+            funds,
         }
         .into())
     }
 
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        req: QueryMsg,
+    ) -> StdResult<T> {
+        querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&req)?,
+        }))
+    }
+
+    /// Mints a name and lists it on the marketplace, `funds` must cover the name price.
+    pub fn mint_and_list(&self, name: &str, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::MintAndList {
+                name: name.to_string(),
+            },
+            funds,
+        )
+    }
+
+    pub fn update_admin(&self, admin: Option<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdateAdmin { admin })
+    }
+
+    pub fn pause(&self, pause: bool) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Pause { pause })
+    }
+
+    pub fn update_config(&self, config: Config) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdateConfig { config })
+    }
+
+    pub fn admin(&self, querier: &QuerierWrapper) -> StdResult<AdminResponse> {
+        self.query(querier, QueryMsg::Admin {})
+    }
+
+    pub fn whitelists(&self, querier: &QuerierWrapper) -> StdResult<Vec<Addr>> {
+        self.query(querier, QueryMsg::Whitelists {})
+    }
+
+    pub fn collection(&self, querier: &QuerierWrapper) -> StdResult<Addr> {
+        self.query(querier, QueryMsg::Collection {})
+    }
+
+    pub fn config(&self, querier: &QuerierWrapper) -> StdResult<Config> {
+        self.query(querier, QueryMsg::Config {})
+    }
+
     pub fn params(&self, querier: &QuerierWrapper) -> StdResult<SudoParams> {
         let res: SudoParams = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: self.addr().into(),
@@ -11,13 +11,10 @@ use bs721_profile::{ExecuteMsg as Bs721ProfileExecuteMsg, SudoParams as Bs721Sud
 use bs_profile::{
     common::SECONDS_PER_YEAR,
     market::{
-        state::Bid, state::SudoParams as ProfileMarketplaceParams,
-        ExecuteMsg as MarketplaceExecuteMsg, QueryMsg as MarketplaceQueryMsg,
-        QueryMsg as ProfileMarketplaceQueryMsg, SudoMsg as MarketplaceSudoMsg,
-    },
-    minter::{
-        BsProfileMinterQueryMsg, SudoParams as NameMinterParams, PUBLIC_MINT_START_TIME_IN_SECONDS,
+        state::Bid,
+        ExecuteMsg as MarketplaceExecuteMsg, QueryMsg as MarketplaceQueryMsg, SudoMsg as MarketplaceSudoMsg,
     },
+    minter::PUBLIC_MINT_START_TIME_IN_SECONDS,
     BsProfileExecuteMsg, BsProfileQueryMsg,
 };
 use bs_std::NATIVE_DENOM;
@@ -27,7 +24,6 @@ use cw_multi_test::{
     App as TestApp, AppResponse, BankSudo, Contract, ContractWrapper, Executor,
     SudoMsg as CwSudoMsg,
 };
-use profile_marketplace::helpers::get_char_price;
 
 // use whitelist_updatable::msg::QueryMsg as PercentWhitelistQueryMsg;
 // use whitelist_updatable_flatrate::msg::{
@@ -89,27 +85,23 @@ pub fn contract_nft() -> Box<dyn Contract<Empty, Empty>> {
 
 const USER: &str = "user";
 const USER2: &str = "user2";
-const USER3: &str = "user3";
 const USER4: &str = "user4";
 const BIDDER: &str = "bidder";
 const BIDDER2: &str = "bidder2";
 const ADMIN: &str = "admin";
 const ADMIN2: &str = "admin2";
 const NAME: &str = "bobo";
-const NAME2: &str = "mccool";
 const VERIFIER: &str = "verifier";
 const OPERATOR: &str = "operator";
 
 const TRADING_FEE_BPS: u64 = 200; // 2%
 const BASE_PRICE: u128 = 100_000_000;
 const BID_AMOUNT: u128 = 1_000_000_000;
-const PER_ADDRESS_LIMIT: u32 = 2;
 const TRADING_START_TIME_OFFSET_IN_SECONDS: u64 = 2 * SECONDS_PER_YEAR;
 
 const MKT: &str = "contract0";
 const MINTER: &str = "contract1";
 const COLLECTION: &str = "contract2";
-const WHITELIST: &str = "contract3";
 
 // NOTE: This are mostly Marketplace integration tests. They could possibly be moved into the marketplace contract.
 
@@ -820,7 +812,7 @@ mod execute {
 mod admin {
     // use whitelist_updatable_flatrate::state::Config;
 
-    use crate::msg::QueryMsg;
+    
 
     use super::*;
 
@@ -890,10 +882,10 @@ mod admin {
 mod query {
     use bs721_base::msg::CollectionInfoResponse;
     use bs721_base::msg::QueryMsg as Bs721QueryMsg;
-    use bs_profile::market::AskRenewPriceResponse;
+    
     use bs_profile::market::{state::Ask, BidOffset};
-    use bs_std::NATIVE_DENOM;
-    use cosmwasm_std::{coin, Coin, StdResult};
+    
+    use cosmwasm_std::StdResult;
 
     use super::*;
 
@@ -1629,7 +1621,7 @@ mod query {
 mod collection {
     use bs721_profile::contract::QueryMsg as Bs721ProfileQueryMsg;
     use bs_profile::{
-        market::state::{Ask, SudoParams},
+        market::state::Ask,
         {Metadata, TextRecord, NFT},
     };
     use cosmwasm_std::{to_json_binary, StdResult};
@@ -2128,13 +2120,11 @@ mod collection {
 }
 
 mod whitelist {
-    use crate::msg::QueryMsg;
+    
     // use whitelist_updatable_flatrate::{msg::QueryMsg as WhitelistQueryMsg, state::Config};
 
     use super::*;
 
-    const WHITELIST2: &str = "contract4";
-
     #[test]
     fn init() {
         let _ = instantiate_contracts(None, Some(ADMIN.to_string()), None);
@@ -2610,6 +2600,7 @@ mod associate_address {
                 creator: ADMIN.to_string(),
                 description: "Stargaze Names".to_string(),
                 image: "ipfs://example.com".to_string(),
+                image_hash: None,
                 external_link: None,
                 explicit_content: None,
                 start_trading_time: None,
@@ -2676,6 +2667,7 @@ mod associate_address {
                 creator: USER.to_string(),
                 description: "Stargaze Names".to_string(),
                 image: "ipfs://example.com".to_string(),
+                image_hash: None,
                 external_link: None,
                 explicit_content: None,
                 start_trading_time: None,
@@ -2703,6 +2695,7 @@ mod associate_address {
                 creator: USER.to_string(),
                 description: "Stargaze Names".to_string(),
                 image: "ipfs://example.com".to_string(),
+                image_hash: None,
                 external_link: None,
                 explicit_content: None,
                 start_trading_time: None,
@@ -2754,6 +2747,7 @@ mod associate_address {
                 creator: USER.to_string(),
                 description: "Stargaze Names".to_string(),
                 image: "ipfs://example.com".to_string(),
+                image_hash: None,
                 external_link: None,
                 explicit_content: None,
                 start_trading_time: None,
@@ -2781,6 +2775,7 @@ mod associate_address {
                 creator: USER.to_string(),
                 description: "Stargaze Names".to_string(),
                 image: "ipfs://example.com".to_string(),
+                image_hash: None,
                 external_link: None,
                 explicit_content: None,
                 start_trading_time: None,
@@ -2835,6 +2830,7 @@ mod associate_address {
                 creator: USER.to_string(),
                 description: "Stargaze Names".to_string(),
                 image: "ipfs://example.com".to_string(),
+                image_hash: None,
                 external_link: None,
                 explicit_content: None,
                 start_trading_time: None,
@@ -1,10 +1,7 @@
-#[cfg(not(feature = "library"))]
-use cosmwasm_std::entry_point;
-use cosmwasm_std::{Addr, DepsMut, Env, Event, Response, Uint128};
+use cosmwasm_std::{Addr, DepsMut, Event, Response, Uint128};
 use bs_profile::minter::SudoParams;
 
 use crate::{
-    msg::SudoMsg,
     state::{PROFILE_COLLECTION, PROFILE_MARKETPLACE, SUDO_PARAMS},
     ContractError,
 };
@@ -14,7 +11,7 @@ pub fn sudo_update_params(
     min_name_length: u32,
     max_name_length: u32,
     base_price: Uint128,
-    fair_burn_bps: u64,
+    _fair_burn_bps: u64,
 ) -> Result<Response, ContractError> {
     SUDO_PARAMS.save(
         deps.storage,
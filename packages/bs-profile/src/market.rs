@@ -52,7 +52,7 @@ pub mod state {
 
     /// (renewal_time (in seconds), id) -> [token_id]
     pub const RENEWAL_QUEUE: Map<(u64, u64), TokenId> = Map::new("rq");
-    /// (new_owner, manager_addr) -> [governance_details]
+    // (new_owner, manager_addr) -> [governance_details]
 
     pub const ASK_COUNT: Item<u64> = Item::new("ask-count");
 
@@ -187,7 +187,7 @@ pub mod state {
 use self::state::{Ask, Bid, Id, SudoParams, TokenId};
 use bs_controllers::HooksResponse;
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, Decimal, StdResult, Timestamp, Uint128};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, Decimal, StdResult, Uint128};
 
 #[cosmwasm_schema::cw_serde]
 pub struct InstantiateMsg {
@@ -0,0 +1,270 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, StdError, StdResult, Uint128};
+use thiserror::Error;
+
+/// Basis points of a full fee split.
+pub const MAX_BPS: u16 = 10_000;
+
+/// Recipient of a share of the fees paid on a mint or burn.
+#[cw_serde]
+pub enum FeeRecipient {
+    /// Payment address of the collection, usually the artist royalties contract. It also receives
+    /// the referral share when there is no referral, and the rounding remainder.
+    Royalties,
+    /// Address referred by the minter or burner.
+    Referral,
+    /// Protocol fee, sent to the community pool.
+    Protocol,
+    /// Any other address, such as a label or a charity.
+    Address(String),
+}
+
+/// Share of the fees, in basis points, sent to a recipient.
+#[cw_serde]
+pub struct FeeShare {
+    pub recipient: FeeRecipient,
+    pub bps: u16,
+}
+
+/// Amount of the fees sent to an `Address` recipient.
+#[cw_serde]
+pub struct AddressFee {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+/// Fees split among the recipients.
+#[cw_serde]
+#[derive(Default)]
+pub struct FeeAmounts {
+    pub royalties: Uint128,
+    pub referral: Uint128,
+    pub protocol: Uint128,
+    pub addresses: Vec<AddressFee>,
+}
+
+impl FeeAmounts {
+    /// Returns the full amount of the fees.
+    pub fn total(&self) -> Uint128 {
+        self.addresses.iter().fold(
+            self.royalties + self.referral + self.protocol,
+            |acc, fee| acc + fee.amount,
+        )
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum FeeSplitError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("fee shares must sum to {MAX_BPS} bps, got {total}")]
+    InvalidTotal { total: u32 },
+
+    #[error("fee shares cannot be zero")]
+    ZeroShare {},
+
+    #[error("fee split requires a royalties share")]
+    MissingRoyalties {},
+
+    #[error("duplicated fee recipient")]
+    DuplicatedRecipient {},
+}
+
+/// Validates a fee split: the shares must be positive, sum to `MAX_BPS` and include the
+/// royalties. Each recipient can appear only once.
+pub fn validate_fee_split(api: &dyn Api, shares: &[FeeShare]) -> Result<(), FeeSplitError> {
+    if !shares
+        .iter()
+        .any(|share| share.recipient == FeeRecipient::Royalties)
+    {
+        return Err(FeeSplitError::MissingRoyalties {});
+    }
+
+    for (i, share) in shares.iter().enumerate() {
+        if share.bps == 0 {
+            return Err(FeeSplitError::ZeroShare {});
+        }
+        if shares[..i].iter().any(|s| s.recipient == share.recipient) {
+            return Err(FeeSplitError::DuplicatedRecipient {});
+        }
+        if let FeeRecipient::Address(address) = &share.recipient {
+            api.addr_validate(address)?;
+        }
+    }
+
+    let total: u32 = shares.iter().map(|share| share.bps as u32).sum();
+    if total != MAX_BPS as u32 {
+        return Err(FeeSplitError::InvalidTotal { total });
+    }
+
+    Ok(())
+}
+
+/// Returns the split sending `referral_bps` to the referral, `protocol_bps` to the protocol and
+/// the rest to the royalties. Zero shares are omitted.
+pub fn royalties_fee_split(referral_bps: u16, protocol_bps: u16) -> Vec<FeeShare> {
+    [
+        (
+            FeeRecipient::Royalties,
+            MAX_BPS.saturating_sub(referral_bps.saturating_add(protocol_bps)),
+        ),
+        (FeeRecipient::Referral, referral_bps),
+        (FeeRecipient::Protocol, protocol_bps),
+    ]
+    .into_iter()
+    .filter(|(_, bps)| *bps > 0)
+    .map(|(recipient, bps)| FeeShare { recipient, bps })
+    .collect()
+}
+
+/// Returns the basis points of the split assigned to `recipient`.
+pub fn fee_share_bps(shares: &[FeeShare], recipient: &FeeRecipient) -> u16 {
+    shares
+        .iter()
+        .filter(|share| &share.recipient == recipient)
+        .map(|share| share.bps)
+        .sum()
+}
+
+/// Splits `amount` among the recipients of a validated fee split. Without a referral, the
+/// referral share goes to the royalties.
+pub fn split_fees(
+    shares: &[FeeShare],
+    amount: Uint128,
+    has_referral: bool,
+) -> StdResult<FeeAmounts> {
+    let mut fees = FeeAmounts::default();
+    for share in shares {
+        let share_amount = amount.multiply_ratio(share.bps, MAX_BPS);
+        match &share.recipient {
+            FeeRecipient::Royalties => {}
+            FeeRecipient::Referral if has_referral => fees.referral = share_amount,
+            FeeRecipient::Referral => {}
+            FeeRecipient::Protocol => fees.protocol = share_amount,
+            // addresses are validated with the split.
+            FeeRecipient::Address(address) => fees.addresses.push(AddressFee {
+                address: Addr::unchecked(address),
+                amount: share_amount,
+            }),
+        }
+    }
+
+    fees.royalties = amount.checked_sub(fees.total())?;
+    Ok(fees)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit tests
+// -------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::testing::MockApi;
+
+    use super::*;
+
+    fn shares() -> Vec<FeeShare> {
+        vec![
+            FeeShare {
+                recipient: FeeRecipient::Royalties,
+                bps: 7_000,
+            },
+            FeeShare {
+                recipient: FeeRecipient::Address("label".to_string()),
+                bps: 1_500,
+            },
+            FeeShare {
+                recipient: FeeRecipient::Referral,
+                bps: 1_000,
+            },
+            FeeShare {
+                recipient: FeeRecipient::Protocol,
+                bps: 500,
+            },
+        ]
+    }
+
+    #[test]
+    fn validate_fee_split_works() {
+        let api = MockApi::default();
+        validate_fee_split(&api, &shares()).unwrap();
+
+        let mut split = shares();
+        split[0].bps = 6_000;
+        assert_eq!(
+            validate_fee_split(&api, &split).unwrap_err(),
+            FeeSplitError::InvalidTotal { total: 9_000 }
+        );
+
+        let mut split = shares();
+        split[0].bps = 8_500;
+        split[3].bps = 0;
+        assert_eq!(
+            validate_fee_split(&api, &split).unwrap_err(),
+            FeeSplitError::ZeroShare {}
+        );
+
+        let mut split = shares();
+        split.remove(0);
+        assert_eq!(
+            validate_fee_split(&api, &split).unwrap_err(),
+            FeeSplitError::MissingRoyalties {}
+        );
+
+        let mut split = shares();
+        split[2].recipient = FeeRecipient::Protocol;
+        assert_eq!(
+            validate_fee_split(&api, &split).unwrap_err(),
+            FeeSplitError::DuplicatedRecipient {}
+        );
+
+        let mut split = shares();
+        split[1].recipient = FeeRecipient::Address("".to_string());
+        assert!(matches!(
+            validate_fee_split(&api, &split).unwrap_err(),
+            FeeSplitError::Std(_)
+        ));
+    }
+
+    #[test]
+    fn split_fees_works() {
+        assert_eq!(fee_share_bps(&shares(), &FeeRecipient::Referral), 1_000);
+
+        let fees = split_fees(&shares(), Uint128::new(1_000), true).unwrap();
+        assert_eq!(
+            fees,
+            FeeAmounts {
+                royalties: Uint128::new(700),
+                referral: Uint128::new(100),
+                protocol: Uint128::new(50),
+                addresses: vec![AddressFee {
+                    address: Addr::unchecked("label"),
+                    amount: Uint128::new(150),
+                }],
+            }
+        );
+
+        let fees = split_fees(&shares(), Uint128::new(1_000), false).unwrap();
+        assert_eq!(
+            fees.royalties,
+            Uint128::new(800),
+            "expected the referral share sent to the royalties"
+        );
+        assert_eq!(fees.referral, Uint128::zero());
+
+        let split = royalties_fee_split(1_000, 500);
+        assert_eq!(split.len(), 3);
+        assert_eq!(fee_share_bps(&split, &FeeRecipient::Royalties), 8_500);
+
+        let fees = split_fees(&shares(), Uint128::new(19), true).unwrap();
+        assert_eq!(fees.referral, Uint128::new(1));
+        assert_eq!(fees.protocol, Uint128::zero());
+        assert_eq!(fees.addresses[0].amount, Uint128::new(2));
+        assert_eq!(
+            fees.royalties,
+            Uint128::new(16),
+            "expected the rounding remainder sent to the royalties"
+        );
+        assert_eq!(fees.total(), Uint128::new(19));
+    }
+}
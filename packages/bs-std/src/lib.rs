@@ -1 +1,3 @@
-pub const NATIVE_DENOM: &str = "ubtsg";
\ No newline at end of file
+pub mod fees;
+
+pub const NATIVE_DENOM: &str = "ubtsg";
@@ -0,0 +1,152 @@
+use crate::MetadataError;
+
+/// Length in bytes of a sha256 digest.
+const SHA256_LENGTH: usize = 32;
+/// Multihash code of the sha2-256 hash function.
+const SHA256_MULTIHASH_CODE: u64 = 0x12;
+/// Multibase prefix of the lowercase base32 encoding, the default one for CIDv1.
+const BASE32_MULTIBASE_PREFIX: char = 'b';
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Validates a content hash used to prove the integrity of the content referenced by an uri.
+///
+/// The hash can either be a hex encoded sha256 digest of the content or an IPFS CIDv1 encoded in
+/// base32, like `bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi`.
+pub fn validate_content_hash(field: &str, hash: &str) -> Result<(), MetadataError> {
+    let is_sha256 = hash.len() == 2 * SHA256_LENGTH && hash.chars().all(|c| c.is_ascii_hexdigit());
+
+    if !is_sha256 && !is_valid_cid_v1(hash) {
+        return Err(MetadataError::InvalidContentHash {
+            field: field.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that the CID is `<multibase prefix><version><codec><multihash>` with version 1 and a
+/// multihash whose digest length matches the declared one.
+fn is_valid_cid_v1(cid: &str) -> bool {
+    let Some(encoded) = cid.strip_prefix(BASE32_MULTIBASE_PREFIX) else {
+        return false;
+    };
+    let Some(bytes) = decode_base32(encoded) else {
+        return false;
+    };
+
+    let mut bytes = bytes.as_slice();
+    let (Some(version), Some(_codec), Some(hash_code), Some(digest_length)) = (
+        read_varint(&mut bytes),
+        read_varint(&mut bytes),
+        read_varint(&mut bytes),
+        read_varint(&mut bytes),
+    ) else {
+        return false;
+    };
+
+    if version != 1 || digest_length == 0 || bytes.len() as u64 != digest_length {
+        return false;
+    }
+    hash_code != SHA256_MULTIHASH_CODE || bytes.len() == SHA256_LENGTH
+}
+
+/// Decodes unpadded RFC 4648 base32 with lowercase alphabet.
+fn decode_base32(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in encoded.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    // leftover bits are only padding and must be zero
+    if bits >= 5 || buffer != 0 {
+        return None;
+    }
+
+    Some(bytes)
+}
+
+/// Reads an unsigned LEB128 varint as used by multiformats, advancing the slice.
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value: u64 = 0;
+
+    // multiformats varints are limited to 9 bytes
+    for (i, byte) in bytes.iter().take(9).enumerate() {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[i + 1..];
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit tests
+// -------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_sha256_works() {
+        validate_content_hash(
+            "content_hash",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        )
+        .unwrap();
+
+        let err = validate_content_hash(
+            "content_hash",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b8",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            MetadataError::InvalidContentHash {
+                field: "content_hash".to_string()
+            },
+            "expected to fail since the digest is too short"
+        );
+    }
+
+    #[test]
+    fn validate_cid_works() {
+        // dag-pb and raw codecs
+        validate_content_hash(
+            "content_hash",
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        )
+        .unwrap();
+        validate_content_hash(
+            "content_hash",
+            "bafkreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy",
+        )
+        .unwrap();
+
+        let invalid = [
+            // CIDv0 is not supported
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            // truncated digest
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbz",
+            // uppercase base32
+            "BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI",
+            // not base32
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbz01",
+            "",
+        ];
+        for cid in invalid {
+            validate_content_hash("content_hash", cid).unwrap_err();
+        }
+    }
+}
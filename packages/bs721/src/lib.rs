@@ -1,10 +1,16 @@
 // mod msg;
+mod content_hash;
+mod metadata;
 mod query;
 mod receiver;
 mod traits;
 
 pub use cw_utils::Expiration;
 
+pub use crate::content_hash::validate_content_hash;
+pub use crate::metadata::{
+    AudioMetadata, DisplayType, MediaType, Metadata, MetadataError, Trait,
+};
 pub use crate::query::{
     AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, Bs721QueryMsg,
     ContractInfoResponse, NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse,
@@ -82,6 +88,8 @@ pub struct CollectionInfo<T> {
     pub creator: String,
     pub description: String,
     pub image: String,
+    /// Sha256 digest or IPFS CIDv1 of the collection image.
+    pub image_hash: Option<String>,
     pub external_link: Option<String>,
     pub explicit_content: Option<bool>,
     pub start_trading_time: Option<Timestamp>,
@@ -97,6 +105,7 @@ where
             creator: "creator".to_string(),
             description: String::new(),
             image: "https://www.beautiful.network".to_string(),
+            image_hash: None,
             external_link: Some("https://www.beautiful.network".to_string()),
             explicit_content: None,
             start_trading_time: None,
@@ -109,6 +118,7 @@ where
 pub struct UpdateCollectionInfoMsg<T> {
     pub description: Option<String>,
     pub image: Option<String>,
+    pub image_hash: Option<Option<String>>,
     pub external_link: Option<Option<String>>,
     pub explicit_content: Option<bool>,
     pub royalty_info: Option<Option<T>>,
@@ -0,0 +1,333 @@
+use cosmwasm_schema::cw_serde;
+use thiserror::Error;
+use url::Url;
+
+/// Maximum number of attributes a single token can carry.
+pub const MAX_ATTRIBUTES: usize = 50;
+/// Maximum length of the token name.
+pub const MAX_NAME_LENGTH: usize = 256;
+/// Maximum length of the token description.
+pub const MAX_DESCRIPTION_LENGTH: usize = 512;
+/// Maximum length of a single url field.
+pub const MAX_URL_LENGTH: usize = 2048;
+/// Maximum length of both trait type and trait value of an attribute.
+pub const MAX_TRAIT_LENGTH: usize = 128;
+/// Maximum length of the inline image data.
+pub const MAX_IMAGE_DATA_LENGTH: usize = 16_384;
+/// Url schemes accepted for the metadata links.
+pub const ALLOWED_URL_SCHEMES: [&str; 3] = ["https", "ipfs", "ar"];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MetadataError {
+    #[error("{field} is not a valid url")]
+    InvalidUrl { field: String },
+
+    #[error("{field} url scheme {scheme} is not supported")]
+    UnsupportedUrlScheme { field: String, scheme: String },
+
+    #[error("{field} exceeds the maximum length of {max}")]
+    TooLong { field: String, max: usize },
+
+    #[error("too many attributes, maximum is {max}")]
+    TooManyAttributes { max: usize },
+
+    #[error("attribute trait type cannot be empty")]
+    EmptyTraitType {},
+
+    #[error("background color must be a six character hexadecimal without a leading #")]
+    InvalidBackgroundColor {},
+
+    #[error("audio fields require the audio media type")]
+    AudioMediaTypeMismatch {},
+
+    #[error("isrc must be 12 alphanumeric characters")]
+    InvalidIsrc {},
+
+    #[error("{field} must be a hex encoded sha256 digest or a base32 CIDv1")]
+    InvalidContentHash { field: String },
+}
+
+/// How marketplaces should render the value of an attribute.
+#[cw_serde]
+pub enum DisplayType {
+    Number,
+    BoostNumber,
+    BoostPercentage,
+    Date,
+}
+
+/// Single attribute of a token, based on the *ERC721 Metadata JSON Schema* attributes.
+#[cw_serde]
+pub struct Trait {
+    pub display_type: Option<DisplayType>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// Kind of content referenced by `animation_url`, or by `image` when no animation is set.
+#[cw_serde]
+pub enum MediaType {
+    Image,
+    Audio,
+    Video,
+}
+
+/// Fields describing a music track. Only allowed along with `MediaType::Audio`.
+#[cw_serde]
+#[derive(Default)]
+pub struct AudioMetadata {
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+    /// Track duration in seconds.
+    pub duration: Option<u32>,
+    /// International Standard Recording Code of the track.
+    pub isrc: Option<String>,
+}
+
+/// On-chain token metadata compatible with the *ERC721 Metadata JSON Schema*.
+#[cw_serde]
+#[derive(Default)]
+pub struct Metadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    /// Raw SVG image data, used when the image is generated on chain.
+    pub image_data: Option<String>,
+    pub external_url: Option<String>,
+    pub animation_url: Option<String>,
+    pub youtube_url: Option<String>,
+    /// Background color of the item, as six character hexadecimal without a leading #.
+    pub background_color: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+    pub media_type: Option<MediaType>,
+    pub audio: Option<AudioMetadata>,
+}
+
+impl Metadata {
+    /// Performs basic validation checks on the metadata before it is stored on chain.
+    ///
+    /// # Validation Checks
+    ///
+    /// - links must be valid urls using one of the `ALLOWED_URL_SCHEMES`.
+    /// - strings must not exceed their maximum length.
+    /// - the number of attributes must not exceed `MAX_ATTRIBUTES`.
+    /// - background color must be an hexadecimal color.
+    /// - audio fields are only allowed for audio tokens.
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        validate_length("name", &self.name, MAX_NAME_LENGTH)?;
+        validate_length("description", &self.description, MAX_DESCRIPTION_LENGTH)?;
+        validate_length("image_data", &self.image_data, MAX_IMAGE_DATA_LENGTH)?;
+
+        validate_url("image", &self.image)?;
+        validate_url("external_url", &self.external_url)?;
+        validate_url("animation_url", &self.animation_url)?;
+        validate_url("youtube_url", &self.youtube_url)?;
+
+        if let Some(color) = &self.background_color {
+            if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(MetadataError::InvalidBackgroundColor {});
+            }
+        }
+
+        if let Some(attributes) = &self.attributes {
+            if attributes.len() > MAX_ATTRIBUTES {
+                return Err(MetadataError::TooManyAttributes {
+                    max: MAX_ATTRIBUTES,
+                });
+            }
+            for attribute in attributes {
+                if attribute.trait_type.is_empty() {
+                    return Err(MetadataError::EmptyTraitType {});
+                }
+                validate_length("trait_type", &Some(&attribute.trait_type), MAX_TRAIT_LENGTH)?;
+                validate_length("trait value", &Some(&attribute.value), MAX_TRAIT_LENGTH)?;
+            }
+        }
+
+        if let Some(audio) = &self.audio {
+            if self.media_type != Some(MediaType::Audio) {
+                return Err(MetadataError::AudioMediaTypeMismatch {});
+            }
+            audio.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AudioMetadata {
+    /// Performs basic validation checks on the audio fields.
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        validate_length("artist", &self.artist, MAX_NAME_LENGTH)?;
+        validate_length("album", &self.album, MAX_NAME_LENGTH)?;
+        validate_length("genre", &self.genre, MAX_TRAIT_LENGTH)?;
+
+        if let Some(isrc) = &self.isrc {
+            if isrc.len() != 12 || !isrc.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(MetadataError::InvalidIsrc {});
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_length<S: AsRef<str>>(
+    field: &str,
+    value: &Option<S>,
+    max: usize,
+) -> Result<(), MetadataError> {
+    match value {
+        Some(value) if value.as_ref().len() > max => Err(MetadataError::TooLong {
+            field: field.to_string(),
+            max,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn validate_url(field: &str, value: &Option<String>) -> Result<(), MetadataError> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+
+    validate_length(field, &Some(value), MAX_URL_LENGTH)?;
+
+    let url = Url::parse(value).map_err(|_| MetadataError::InvalidUrl {
+        field: field.to_string(),
+    })?;
+    if !ALLOWED_URL_SCHEMES.contains(&url.scheme()) {
+        return Err(MetadataError::UnsupportedUrlScheme {
+            field: field.to_string(),
+            scheme: url.scheme().to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit tests
+// -------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn metadata() -> Metadata {
+        Metadata {
+            name: Some("Track #1".to_string()),
+            description: Some("First track of the album".to_string()),
+            image: Some(
+                "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+            ),
+            animation_url: Some("https://bitsong.io/track.mp3".to_string()),
+            background_color: Some("0A0B0c".to_string()),
+            attributes: Some(vec![Trait {
+                display_type: Some(DisplayType::Number),
+                trait_type: "Edition".to_string(),
+                value: "1".to_string(),
+            }]),
+            media_type: Some(MediaType::Audio),
+            audio: Some(AudioMetadata {
+                artist: Some("Artist".to_string()),
+                duration: Some(180),
+                isrc: Some("USRC17607839".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn metadata_validate_works() {
+        metadata().validate().unwrap();
+        Metadata::default().validate().unwrap();
+    }
+
+    #[test]
+    fn metadata_validate_urls() {
+        let mut meta = metadata();
+        meta.image = Some("not a url".to_string());
+        assert_eq!(
+            meta.validate().unwrap_err(),
+            MetadataError::InvalidUrl {
+                field: "image".to_string()
+            },
+            "expected to fail since image is not a url"
+        );
+
+        let mut meta = metadata();
+        meta.external_url = Some("javascript:alert(1)".to_string());
+        assert_eq!(
+            meta.validate().unwrap_err(),
+            MetadataError::UnsupportedUrlScheme {
+                field: "external_url".to_string(),
+                scheme: "javascript".to_string()
+            },
+            "expected to fail since javascript scheme is not allowed"
+        );
+    }
+
+    #[test]
+    fn metadata_validate_limits() {
+        let mut meta = metadata();
+        meta.description = Some("a".repeat(MAX_DESCRIPTION_LENGTH + 1));
+        assert_eq!(
+            meta.validate().unwrap_err(),
+            MetadataError::TooLong {
+                field: "description".to_string(),
+                max: MAX_DESCRIPTION_LENGTH
+            }
+        );
+
+        let mut meta = metadata();
+        meta.attributes = Some(vec![
+            Trait {
+                display_type: None,
+                trait_type: "Type".to_string(),
+                value: "Value".to_string(),
+            };
+            MAX_ATTRIBUTES + 1
+        ]);
+        assert_eq!(
+            meta.validate().unwrap_err(),
+            MetadataError::TooManyAttributes {
+                max: MAX_ATTRIBUTES
+            }
+        );
+
+        let mut meta = metadata();
+        meta.attributes = Some(vec![Trait {
+            display_type: None,
+            trait_type: String::new(),
+            value: "Value".to_string(),
+        }]);
+        assert_eq!(
+            meta.validate().unwrap_err(),
+            MetadataError::EmptyTraitType {}
+        );
+
+        let mut meta = metadata();
+        meta.background_color = Some("#FFFFFF".to_string());
+        assert_eq!(
+            meta.validate().unwrap_err(),
+            MetadataError::InvalidBackgroundColor {}
+        );
+    }
+
+    #[test]
+    fn metadata_validate_audio() {
+        let mut meta = metadata();
+        meta.media_type = Some(MediaType::Image);
+        assert_eq!(
+            meta.validate().unwrap_err(),
+            MetadataError::AudioMediaTypeMismatch {},
+            "expected to fail since audio fields are set on an image"
+        );
+
+        let mut meta = metadata();
+        meta.audio.as_mut().unwrap().isrc = Some("US-RC1-76-07839".to_string());
+        assert_eq!(meta.validate().unwrap_err(), MetadataError::InvalidIsrc {});
+    }
+}
@@ -118,6 +118,8 @@ pub struct NftInfoResponse<T> {
     /// Should point to a JSON file that conforms to the ERC721
     /// Metadata JSON Schema
     pub token_uri: Option<String>,
+    /// Sha256 digest or IPFS CIDv1 of the content referenced by the token uri
+    pub content_hash: Option<String>,
     /// Seller fee basis points, 0-10000
     /// 0 means no fee, 100 means 1%, 10000 means 100%
     /// This is the fee paid by the buyer to the original creator
@@ -67,6 +67,8 @@ fn merge_variants(metadata: TokenStream, left: TokenStream, right: TokenStream)
 /// enum ExecuteMsg {
 ///     AddPreMintHook { hook: String },
 ///     AddPostMintHook { hook: String },
+///     RemovePreMintHook { hook: String },
+///     RemovePostMintHook { hook: String },
 ///     Foo {},
 ///     Bar {},
 /// }
@@ -82,6 +84,8 @@ pub fn sg_mint_hooks_execute(metadata: TokenStream, input: TokenStream) -> Token
             enum Right {
                 AddPreMintHook { hook: String },
                 AddPostMintHook { hook: String },
+                RemovePreMintHook { hook: String },
+                RemovePostMintHook { hook: String },
             }
         }
         .into(),
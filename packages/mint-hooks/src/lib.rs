@@ -80,6 +80,15 @@ pub mod pre {
         Ok(res)
     }
 
+    pub fn remove_premint_hook(deps: DepsMut, hook: String) -> Result<Response, MintHookError> {
+        PREMINT_HOOKS.remove_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+
+        let res = Response::new()
+            .add_attribute("action", "remove_premint_hook")
+            .add_attribute("hook", hook);
+        Ok(res)
+    }
+
     pub fn query_premint_hooks(deps: Deps) -> StdResult<Binary> {
         to_json_binary(&PREMINT_HOOKS.query_hooks(deps)?)
     }
@@ -153,6 +162,15 @@ pub mod post {
         Ok(res)
     }
 
+    pub fn remove_postmint_hook(deps: DepsMut, hook: String) -> Result<Response, MintHookError> {
+        POSTMINT_HOOKS.remove_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+
+        let res = Response::new()
+            .add_attribute("action", "remove_postmint_hook")
+            .add_attribute("hook", hook);
+        Ok(res)
+    }
+
     pub fn query_postmint_hooks(deps: Deps) -> StdResult<Binary> {
         to_json_binary(&POSTMINT_HOOKS.query_hooks(deps)?)
     }